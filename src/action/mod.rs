@@ -17,34 +17,91 @@ use rand_pcg::Lcg64Xsh32;
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, Hash)]
 #[serde(crate = "rocket::serde", tag = "action_type")]
 pub enum PlayerActions {
-    NewGame { seed: Option<u64> },
+    NewGame {
+        seed: Option<u64>,
+    },
     // Encounter actions (Step 7)
-    EncounterPickEncounter { card_id: usize },
-    EncounterPlayCard { card_id: u64 },
-    EncounterApplyScouting { card_ids: Vec<usize> },
+    EncounterPickEncounter {
+        card_id: usize,
+    },
+    EncounterPlayCard {
+        card_id: u64,
+    },
+    EncounterApplyScouting {
+        card_ids: Vec<usize>,
+    },
     EncounterAbort,
+    EncounterRetreat,
     EncounterConcludeEncounter,
     // Crafting-specific actions (Step 9.6)
-    EncounterCraftSwap { from_id: usize, to_id: usize },
-    EncounterCraftCard { target_card_id: usize },
-    EncounterCraftDurability { discipline: String },
+    EncounterCraftSwap {
+        from_id: usize,
+        to_id: usize,
+    },
+    EncounterCraftCard {
+        target_card_id: usize,
+    },
+    EncounterCraftDurability {
+        discipline: crate::library::types::Discipline,
+    },
+    // Rest/crafting action: restore a discipline's durability to its maximum
+    EncounterRepair {
+        discipline: crate::library::types::Discipline,
+    },
+    // Equipment actions (between encounters)
+    EquipTool {
+        card_id: usize,
+    },
+    UnequipTool {
+        discipline: crate::library::types::Discipline,
+    },
+    // Fishing-specific actions
+    EncounterFishingBait {
+        bait: crate::library::types::FishingBait,
+    },
 }
 
+pub(crate) type PlayResult = Result<
+    (
+        rocket::http::Status,
+        Json<crate::library::types::ActionEntry>,
+    ),
+    Either<NotFound<Json<Status>>, BadRequest<Json<Status>>>,
+>;
+
 #[openapi]
 #[post("/action", format = "json", data = "<player_action>")]
 pub async fn play(
     player_data: &State<RandomGeneratorWrapper>,
     game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
+    events: &State<crate::events::EventBus>,
     player_action: Json<PlayerActions>,
-) -> Result<
-    (
-        rocket::http::Status,
-        Json<crate::library::types::ActionEntry>,
-    ),
-    Either<NotFound<Json<Status>>, BadRequest<Json<Status>>>,
-> {
-    let action = player_action.0;
+) -> PlayResult {
+    crate::events::play_published(player_data, game_state, events, player_action.0)
+        .await
+        .0
+}
+
+/// Apply `action` to the given game, which is the served game or one of its forks.
+pub(crate) async fn play_on(
+    player_data: &RandomGeneratorWrapper,
+    game_state: &std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>,
+    action: PlayerActions,
+) -> PlayResult {
+    let result = apply_action(player_data, game_state, action).await;
+    if result.is_ok() {
+        let gs = game_state.lock().await;
+        let rng = player_data.random_generator_state.lock().await;
+        gs.write_snapshot_if_due(&rng);
+    }
+    result
+}
 
+async fn apply_action(
+    player_data: &RandomGeneratorWrapper,
+    game_state: &std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>,
+    action: PlayerActions,
+) -> PlayResult {
     match action {
         PlayerActions::NewGame { seed } => {
             use rand::RngCore;
@@ -68,9 +125,13 @@ pub async fn play(
             gs.encounter_phase = new_gs.encounter_phase;
             gs.last_encounter_result = None;
             gs.encounter_results.clear();
+            gs.encounter_rewards.clear();
+            gs.equipped_tools.clear();
+            gs.stored_tool_durability.clear();
 
             let payload = crate::library::types::ActionPayload::SetSeed { seed: s };
-            let entry = gs.append_action("NewGame", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("NewGame", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         // Step 7: Encounter action handlers
@@ -82,94 +143,14 @@ pub async fn play(
                     card_id
                 )))));
             }
-            // Move encounter from hand → discard
-            if let Err(e) = gs.library.play(card_id) {
-                return Err(Right(BadRequest(new_status(e))));
-            }
-            // Get the encounter kind to dispatch
-            let lib_card = gs.library.get(card_id).ok_or_else(|| {
-                Left(NotFound(new_status(format!(
-                    "Card {} not found in Library",
-                    card_id
-                ))))
-            })?;
-            let encounter_kind = match &lib_card.kind {
-                crate::library::types::CardKind::Encounter { encounter_kind } => {
-                    encounter_kind.clone()
-                }
-                _ => {
-                    return Err(Right(BadRequest(new_status(format!(
-                        "Card {} is not an encounter",
-                        card_id
-                    )))));
-                }
-            };
             let mut rng = player_data.random_generator_state.lock().await;
-            match encounter_kind {
-                crate::library::types::EncounterKind::Combat { .. } => {
-                    // Initialize player health if not set
-                    if gs
-                        .token_balances
-                        .get(&crate::library::types::Token::persistent(
-                            crate::library::types::TokenType::Health,
-                        ))
-                        .copied()
-                        .unwrap_or(0)
-                        == 0
-                    {
-                        gs.token_balances.insert(
-                            crate::library::types::Token::persistent(
-                                crate::library::types::TokenType::Health,
-                            ),
-                            2000,
-                        );
-                    }
-                    match gs.start_combat(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Mining { .. } => {
-                    match gs.start_mining_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Herbalism { .. } => {
-                    match gs.start_herbalism_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Woodcutting { .. } => {
-                    match gs.start_woodcutting_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Fishing { .. } => {
-                    match gs.start_fishing_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Rest { .. } => {
-                    match gs.start_rest_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
-                crate::library::types::EncounterKind::Crafting { .. } => {
-                    match gs.start_crafting_encounter(card_id, &mut rng) {
-                        Ok(()) => {}
-                        Err(e) => return Err(Right(BadRequest(new_status(e)))),
-                    }
-                }
+            if let Err(e) = gs.start_picked_encounter(card_id, &mut rng) {
+                return Err(Right(BadRequest(new_status(e))));
             }
             let payload = crate::library::types::ActionPayload::DrawEncounter {
                 encounter_id: card_id.to_string(),
             };
-            let entry = gs.append_action("EncounterPickEncounter", payload);
+            let entry = gs.append_action("EncounterPickEncounter", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterPlayCard { card_id } => {
@@ -207,7 +188,7 @@ pub async fn play(
                 let payload = crate::library::types::ActionPayload::PlayCard {
                     card_id: card_id as usize,
                 };
-                let entry = gs.append_action("EncounterPlayCard", payload);
+                let entry = gs.append_action("EncounterPlayCard", payload, &rng);
                 return Ok((rocket::http::Status::Created, Json(entry)));
             }
 
@@ -414,7 +395,8 @@ pub async fn play(
             let payload = crate::library::types::ActionPayload::PlayCard {
                 card_id: card_id as usize,
             };
-            let entry = gs.append_action("EncounterPlayCard", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterPlayCard", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterApplyScouting { card_ids } => {
@@ -451,7 +433,8 @@ pub async fn play(
             let payload = crate::library::types::ActionPayload::ApplyScouting {
                 card_ids: card_ids.clone(),
             };
-            let entry = gs.append_action("EncounterApplyScouting", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterApplyScouting", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterAbort => {
@@ -459,7 +442,7 @@ pub async fn play(
             match &gs.current_encounter {
                 Some(crate::library::types::EncounterState::Combat(_)) => {
                     return Err(Right(BadRequest(new_status(
-                        "Cannot abort a combat encounter".to_string(),
+                        "Cannot abort a combat encounter; use EncounterRetreat".to_string(),
                     ))));
                 }
                 Some(crate::library::types::EncounterState::Rest(_)) => {
@@ -483,7 +466,18 @@ pub async fn play(
                 }
             }
             let payload = crate::library::types::ActionPayload::AbortEncounter;
-            let entry = gs.append_action("EncounterAbort", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterAbort", payload, &rng);
+            Ok((rocket::http::Status::Created, Json(entry)))
+        }
+        PlayerActions::EncounterRetreat => {
+            let mut gs = game_state.lock().await;
+            if let Err(e) = gs.retreat_from_combat() {
+                return Err(Right(BadRequest(new_status(e))));
+            }
+            let payload = crate::library::types::ActionPayload::RetreatEncounter;
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterRetreat", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterConcludeEncounter => {
@@ -531,7 +525,8 @@ pub async fn play(
                 }
             }
             let payload = crate::library::types::ActionPayload::ConcludeEncounter;
-            let entry = gs.append_action("EncounterConcludeEncounter", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterConcludeEncounter", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterCraftSwap { from_id, to_id } => {
@@ -548,7 +543,8 @@ pub async fn play(
                 return Err(Right(BadRequest(new_status(e))));
             }
             let payload = crate::library::types::ActionPayload::CraftSwap { from_id, to_id };
-            let entry = gs.append_action("EncounterCraftSwap", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterCraftSwap", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterCraftCard { target_card_id } => {
@@ -565,7 +561,8 @@ pub async fn play(
                 return Err(Right(BadRequest(new_status(e))));
             }
             let payload = crate::library::types::ActionPayload::CraftCard { target_card_id };
-            let entry = gs.append_action("EncounterCraftCard", payload);
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterCraftCard", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
         PlayerActions::EncounterCraftDurability { discipline } => {
@@ -581,10 +578,55 @@ pub async fn play(
             if let Err(e) = gs.resolve_crafting_add_durability(&discipline) {
                 return Err(Right(BadRequest(new_status(e))));
             }
-            let payload = crate::library::types::ActionPayload::CraftDurability {
-                discipline: discipline.clone(),
-            };
-            let entry = gs.append_action("EncounterCraftDurability", payload);
+            let payload = crate::library::types::ActionPayload::CraftDurability { discipline };
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterCraftDurability", payload, &rng);
+            Ok((rocket::http::Status::Created, Json(entry)))
+        }
+        PlayerActions::EncounterRepair { discipline } => {
+            let mut gs = game_state.lock().await;
+            if let Err(e) = gs.resolve_repair(&discipline) {
+                return Err(Right(BadRequest(new_status(e))));
+            }
+            let payload = crate::library::types::ActionPayload::Repair { discipline };
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EncounterRepair", payload, &rng);
+            Ok((rocket::http::Status::Created, Json(entry)))
+        }
+        PlayerActions::EquipTool { card_id } => {
+            let mut gs = game_state.lock().await;
+            if gs.library.get(card_id).is_none() {
+                return Err(Left(NotFound(new_status(format!(
+                    "Card {} does not exist in Library",
+                    card_id
+                )))));
+            }
+            if let Err(e) = gs.equip_tool(card_id) {
+                return Err(Right(BadRequest(new_status(e))));
+            }
+            let payload = crate::library::types::ActionPayload::EquipTool { card_id };
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("EquipTool", payload, &rng);
+            Ok((rocket::http::Status::Created, Json(entry)))
+        }
+        PlayerActions::UnequipTool { discipline } => {
+            let mut gs = game_state.lock().await;
+            if let Err(e) = gs.unequip_tool(&discipline) {
+                return Err(Right(BadRequest(new_status(e))));
+            }
+            let payload = crate::library::types::ActionPayload::UnequipTool { discipline };
+            let rng = player_data.random_generator_state.lock().await;
+            let entry = gs.append_action("UnequipTool", payload, &rng);
+            Ok((rocket::http::Status::Created, Json(entry)))
+        }
+        PlayerActions::EncounterFishingBait { bait } => {
+            let mut gs = game_state.lock().await;
+            let mut rng = player_data.random_generator_state.lock().await;
+            if let Err(e) = gs.resolve_fishing_bait(&bait, &mut rng) {
+                return Err(Right(BadRequest(new_status(e))));
+            }
+            let payload = crate::library::types::ActionPayload::FishingBait { bait };
+            let entry = gs.append_action("EncounterFishingBait", payload, &rng);
             Ok((rocket::http::Status::Created, Json(entry)))
         }
     }
@@ -1,19 +1,72 @@
 use std::fs::OpenOptions;
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, SyncSender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use crate::library::snapshot::GameSnapshot;
 use crate::library::types::ActionEntry;
 
+/// Snapshot files kept next to the action log; older ones are deleted when a new one is written.
+const SNAPSHOTS_KEPT: usize = 3;
+
+/// Destination that `ActionLog::append` forwards every new entry to.
+pub trait ActionSink: std::fmt::Debug + Send + Sync {
+    fn write(&self, entry: &ActionEntry) -> Result<(), String>;
+
+    /// Flush what is pending. Entries written afterwards may be dropped.
+    fn close(&self) {}
+
+    /// The NDJSON file behind this sink, if any. Snapshots are kept next to it.
+    fn file_path(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Keeps every entry it is given, e.g. for tests or in-process consumers.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    entries: Mutex<Vec<ActionEntry>>,
+}
+
+impl MemorySink {
+    pub fn entries(&self) -> Vec<ActionEntry> {
+        match self.entries.lock() {
+            Ok(g) => g.clone(),
+            Err(e) => e.into_inner().clone(),
+        }
+    }
+}
+
+impl ActionSink for MemorySink {
+    fn write(&self, entry: &ActionEntry) -> Result<(), String> {
+        match self.entries.lock() {
+            Ok(mut g) => g.push(entry.clone()),
+            Err(e) => e.into_inner().push(entry.clone()),
+        }
+        Ok(())
+    }
+}
+
+/// Discards every entry.
+#[derive(Debug, Default)]
+pub struct NoopSink;
+
+impl ActionSink for NoopSink {
+    fn write(&self, _entry: &ActionEntry) -> Result<(), String> {
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FileWriter {
     // Shared optional sender so close() can take the sender and drop it.
     sender: Arc<Mutex<Option<SyncSender<ActionEntry>>>>,
     // Keep a handle to the writer thread so it doesn't get dropped
     _handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    path: PathBuf,
 }
 
 impl FileWriter {
@@ -29,6 +82,7 @@ impl FileWriter {
 
         let (tx, rx) = mpsc::sync_channel(queue_size);
         let sender = Arc::new(Mutex::new(Some(tx)));
+        let log_path = path.clone();
         let handle = thread::spawn(move || {
             // Open file in append mode
             let file = OpenOptions::new().create(true).append(true).open(&path);
@@ -97,16 +151,28 @@ impl FileWriter {
         Ok(FileWriter {
             sender,
             _handle: Arc::new(Mutex::new(Some(handle))),
+            path: log_path,
         })
     }
 
+    /// Path of the NDJSON file this writer appends to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn send(&self, entry: ActionEntry) {
         // best-effort send; ignore failures (e.g., receiver dropped)
+        let _ = self.try_send(entry);
+    }
+
+    fn try_send(&self, entry: ActionEntry) -> Result<(), String> {
         let guard = self.sender.lock().unwrap();
-        if let Some(tx) = &*guard {
-            // If the channel is full, this will block and provide backpressure
-            let _ = tx.send(entry);
-        }
+        let Some(tx) = &*guard else {
+            return Err(format!("writer for {:?} is closed", self.path));
+        };
+        // If the channel is full, this will block and provide backpressure
+        tx.send(entry)
+            .map_err(|_| format!("writer thread for {:?} has stopped", self.path))
     }
 
     /// Close the writer: drop the sender and join the writer thread to ensure pending writes flushed.
@@ -124,3 +190,114 @@ impl FileWriter {
         }
     }
 }
+
+/// The NDJSON file sink: entries are batched on the writer thread, with the queue size and
+/// fsync behaviour taken from `ACTION_LOG_QUEUE_SIZE` and `ACTION_LOG_FSYNC`.
+impl ActionSink for FileWriter {
+    fn write(&self, entry: &ActionEntry) -> Result<(), String> {
+        self.try_send(entry.clone())
+    }
+
+    fn close(&self) {
+        FileWriter::close(self);
+    }
+
+    fn file_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
+
+/// Snapshots live next to the log as `<log file name>.snapshot.<seq>.json`.
+fn snapshot_prefix(log_path: &Path) -> String {
+    let name = log_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{}.snapshot.", name)
+}
+
+fn snapshot_dir(log_path: &Path) -> PathBuf {
+    match log_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+pub fn snapshot_path(log_path: &Path, seq: u64) -> PathBuf {
+    snapshot_dir(log_path).join(format!("{}{}.json", snapshot_prefix(log_path), seq))
+}
+
+/// Sequence numbers of the snapshot files next to the log, newest first.
+fn snapshot_seqs(log_path: &Path) -> Vec<u64> {
+    let prefix = snapshot_prefix(log_path);
+    let mut seqs: Vec<u64> = std::fs::read_dir(snapshot_dir(log_path))
+        .map(|dir| {
+            dir.filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    name.strip_prefix(&prefix)?
+                        .strip_suffix(".json")?
+                        .parse()
+                        .ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    seqs.sort_unstable_by(|a, b| b.cmp(a));
+    seqs
+}
+
+/// Write a snapshot next to the log, then delete all but the newest `SNAPSHOTS_KEPT`.
+/// The file is written under a temporary name and renamed, so readers never see half of it.
+pub fn write_snapshot(log_path: &Path, snapshot: &GameSnapshot) -> Result<(), String> {
+    let path = snapshot_path(log_path, snapshot.last_seq);
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string(snapshot).map_err(|e| e.to_string())?;
+    std::fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
+    for seq in snapshot_seqs(log_path).into_iter().skip(SNAPSHOTS_KEPT) {
+        let _ = std::fs::remove_file(snapshot_path(log_path, seq));
+    }
+    Ok(())
+}
+
+/// Delete the snapshots taken after `seq`; a rewind makes them describe a discarded branch.
+pub fn discard_snapshots_after(log_path: &Path, seq: u64) {
+    for snapshot_seq in snapshot_seqs(log_path) {
+        if snapshot_seq > seq {
+            let _ = std::fs::remove_file(snapshot_path(log_path, snapshot_seq));
+        }
+    }
+}
+
+/// Move the log file and its snapshots out of the way by renaming them to
+/// `<log file name>.<reason>.<unix seconds>`, and return the new log path.
+pub fn set_aside(log_path: &Path, reason: &str) -> Result<PathBuf, String> {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let name = log_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let moved = snapshot_dir(log_path).join(format!("{}.{}.{}", name, reason, secs));
+    std::fs::rename(log_path, &moved).map_err(|e| e.to_string())?;
+    for seq in snapshot_seqs(log_path) {
+        std::fs::rename(snapshot_path(log_path, seq), snapshot_path(&moved, seq))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(moved)
+}
+
+/// All snapshots next to the log that can be read, newest first. Unreadable files are skipped.
+pub fn load_snapshots(log_path: &Path) -> Vec<GameSnapshot> {
+    snapshot_seqs(log_path)
+        .into_iter()
+        .filter_map(|seq| {
+            let json = std::fs::read_to_string(snapshot_path(log_path, seq)).ok()?;
+            serde_json::from_str::<GameSnapshot>(&json)
+                .ok()
+                .filter(|snapshot| snapshot.last_seq == seq)
+        })
+        .collect()
+}
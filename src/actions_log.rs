@@ -1,6 +1,7 @@
 use rocket::serde::json::Json;
 use rocket_okapi::openapi;
 
+use crate::library::action_log::{LogIntegrityError, LogQuery};
 use crate::library::{types::ActionEntry, GameState};
 
 #[derive(rocket::serde::Serialize, rocket::serde::Deserialize, rocket_okapi::JsonSchema, Debug)]
@@ -11,37 +12,83 @@ pub struct ActionLogResponse {
     pub limit: usize,
 }
 
+/// Page through the action log. Filters combine: `from_seq`/`to_seq` bound the seqs (both
+/// inclusive), `payload_type` keeps one payload `type` (e.g. `PlayCard`) and `encounter` keeps
+/// the actions of the encounter whose `DrawEncounter` has that seq, through its scouting.
+/// `descending=true` pages from the newest entry; `next_seq` is then the next `to_seq`.
 #[openapi]
-#[get("/actions/log?<from_seq>&<limit>")]
+#[get("/actions/log?<from_seq>&<to_seq>&<payload_type>&<encounter>&<descending>&<limit>")]
 pub async fn list_actions_log(
     from_seq: Option<u64>,
+    to_seq: Option<u64>,
+    payload_type: Option<String>,
+    encounter: Option<u64>,
+    descending: Option<bool>,
     limit: Option<usize>,
     game_state: &rocket::State<std::sync::Arc<rocket::futures::lock::Mutex<GameState>>>,
 ) -> Json<ActionLogResponse> {
     let gs = game_state.lock().await;
-    let entries = gs.action_log.entries();
-    let mut filtered: Vec<ActionEntry> = entries
-        .into_iter()
-        .filter(|e| {
-            if let Some(f) = from_seq {
-                if e.seq < f {
-                    return false;
-                }
-            }
-            true
-        })
-        .collect();
-    let max = limit.unwrap_or(1000);
-    let has_more = filtered.len() > max;
-    filtered.truncate(max);
-    let next_seq = if has_more {
-        filtered.last().map(|e| e.seq + 1)
-    } else {
-        None
-    };
-    Json(ActionLogResponse {
-        entries: filtered,
-        next_seq,
-        limit: max,
+    Json(log_page(
+        &gs,
+        LogQuery {
+            from_seq,
+            to_seq,
+            payload_type,
+            encounter,
+            descending: descending.unwrap_or(false),
+            limit: limit.unwrap_or(1000),
+        },
+    ))
+}
+
+/// One page of `gs`'s action log, as `GET /actions/log` answers it.
+pub(crate) fn log_page(gs: &GameState, query: LogQuery) -> ActionLogResponse {
+    let limit = query.limit;
+    let page = gs.action_log.query(&query);
+    ActionLogResponse {
+        entries: page.entries,
+        next_seq: page.next_seq,
+        limit,
+    }
+}
+
+/// The whole action log in the compact binary format (see `library::compact_log`), deflated
+/// when `compressed=true`. `ActionLog::load_from_file` reads it back.
+#[get("/actions/log?format=compact&<compressed>")]
+pub async fn export_actions_log_compact(
+    compressed: Option<bool>,
+    game_state: &rocket::State<std::sync::Arc<rocket::futures::lock::Mutex<GameState>>>,
+) -> (rocket::http::ContentType, Vec<u8>) {
+    let gs = game_state.lock().await;
+    (
+        rocket::http::ContentType::Binary,
+        gs.action_log.export_compact(compressed.unwrap_or(false)),
+    )
+}
+
+/// Outcome of checking the action log: seqs run 1, 2, 3, ... and every entry chains onto the
+/// previous one's hash. `head_hash` is the last entry's hash, which pins the whole log.
+#[derive(rocket::serde::Serialize, rocket::serde::Deserialize, rocket_okapi::JsonSchema, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct LogVerification {
+    pub valid: bool,
+    pub entries: usize,
+    pub head_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<LogIntegrityError>,
+}
+
+#[openapi]
+#[get("/actions/log/verify")]
+pub async fn verify_actions_log(
+    game_state: &rocket::State<std::sync::Arc<rocket::futures::lock::Mutex<GameState>>>,
+) -> Json<LogVerification> {
+    let gs = game_state.lock().await;
+    let error = gs.action_log.verify().err();
+    Json(LogVerification {
+        valid: error.is_none(),
+        entries: gs.action_log.entries().len(),
+        head_hash: gs.action_log.head_hash(),
+        error,
     })
 }
@@ -1,9 +1,13 @@
 use rocket::response::status::{Created, NotFound};
 use rocket::serde::json::Json;
+use rocket::serde::Serialize;
 use rocket::State;
 use rocket_okapi::openapi;
+use schemars::JsonSchema;
 
-use crate::library::types::{EncounterOutcome, EncounterState};
+use crate::library::types::{
+    EncounterOutcome, EncounterState, EncounterSummary, RewardBreakdown, WoodcuttingPatternPreview,
+};
 use crate::player_data::RandomGeneratorWrapper;
 use crate::status_messages::{new_status, Status};
 
@@ -19,6 +23,17 @@ pub async fn get_encounter(
     }
 }
 
+/// Summaries of the encounters that can be picked right now: discipline, difficulty,
+/// expected rewards and entry cost.
+#[openapi]
+#[get("/encounters/hand")]
+pub async fn get_encounter_hand(
+    game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
+) -> Json<Vec<EncounterSummary>> {
+    let gs = game_state.lock().await;
+    Json(gs.encounter_hand_summaries())
+}
+
 /// Initialize combat for testing purposes.
 ///
 /// **TESTING ENDPOINT ONLY** - Creates combat from the first CombatEncounter
@@ -79,11 +94,40 @@ pub async fn advance_phase(
     Created::new("/tests/combat/advance")
 }
 
+/// An encounter outcome together with the rewards granted for it, if any.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct EncounterResult {
+    pub outcome: EncounterOutcome,
+    pub reward: Option<RewardBreakdown>,
+}
+
+/// Woodcutting patterns still reachable in the active encounter, plus the best one so far.
+#[openapi]
+#[get("/encounter/woodcutting/patterns")]
+pub async fn get_woodcutting_patterns(
+    game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
+) -> Result<Json<WoodcuttingPatternPreview>, NotFound<Json<Status>>> {
+    let gs = game_state.lock().await;
+    gs.woodcutting_pattern_preview()
+        .map(Json)
+        .map_err(|e| NotFound(new_status(e)))
+}
+
 #[openapi]
 #[get("/encounter/results")]
 pub async fn get_encounter_results(
     game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
-) -> Json<Vec<EncounterOutcome>> {
+) -> Json<Vec<EncounterResult>> {
     let gs = game_state.lock().await;
-    Json(gs.encounter_results.clone())
+    Json(
+        gs.encounter_results
+            .iter()
+            .enumerate()
+            .map(|(index, outcome)| EncounterResult {
+                outcome: outcome.clone(),
+                reward: gs.encounter_rewards.get(&index).cloned(),
+            })
+            .collect(),
+    )
 }
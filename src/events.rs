@@ -0,0 +1,318 @@
+//! Server-sent game events, so clients can follow the game without polling after every action.
+
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::stream::{Event, EventStream};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::tokio::sync::broadcast;
+use rocket::{Shutdown, State};
+use rocket_okapi::JsonSchema;
+use std::collections::HashMap;
+
+use crate::action::{PlayResult, PlayerActions};
+use crate::library::types::{ActionEntry, EncounterOutcome, EncounterPhase, Token};
+use crate::library::GameState;
+use crate::player_data::RandomGeneratorWrapper;
+
+/// Events buffered per subscriber before a slow one starts missing them.
+const EVENT_BUFFER: usize = 1024;
+
+/// Something that changed in the game, tagged with the seq of the action that caused it.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+pub struct GameEvent {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub kind: GameEventKind,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde", tag = "event", rename_all = "kebab-case")]
+pub enum GameEventKind {
+    ActionAppended {
+        entry: ActionEntry,
+    },
+    EncounterStarted {
+        encounter_card_id: Option<usize>,
+    },
+    EncounterFinished {
+        outcome: Option<EncounterOutcome>,
+    },
+    TokenChanged {
+        token: Token,
+        from: i64,
+        to: i64,
+    },
+    PhaseChanged {
+        from: EncounterPhase,
+        to: EncounterPhase,
+    },
+    /// The game was rewound: actions after `to_seq` are gone and their seqs will be reused.
+    Rewound {
+        to_seq: u64,
+    },
+}
+
+impl GameEventKind {
+    /// The SSE `event:` name, same as the `event` tag in the JSON data.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameEventKind::ActionAppended { .. } => "action-appended",
+            GameEventKind::EncounterStarted { .. } => "encounter-started",
+            GameEventKind::EncounterFinished { .. } => "encounter-finished",
+            GameEventKind::TokenChanged { .. } => "token-changed",
+            GameEventKind::PhaseChanged { .. } => "phase-changed",
+            GameEventKind::Rewound { .. } => "rewound",
+        }
+    }
+}
+
+/// Fans game events out to every `/events` subscriber. Clones share the bus.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<GameEvent>,
+    /// Held across an action so its before/after observations aren't mixed with another's.
+    playing: std::sync::Arc<rocket::futures::lock::Mutex<()>>,
+    /// The `to_seq` of every rewind published, oldest first.
+    rewinds: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_BUFFER).0,
+            playing: std::sync::Arc::new(rocket::futures::lock::Mutex::new(())),
+            rewinds: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> broadcast::Receiver<GameEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(&self, events: &[GameEvent]) {
+        for event in events {
+            // Without subscribers there is nobody to tell
+            let _ = self.sender.send(event.clone());
+        }
+    }
+}
+
+/// The parts of the game state that events report on.
+struct Observed {
+    phase: EncounterPhase,
+    encounter_card_id: Option<usize>,
+    last_encounter_result: Option<EncounterOutcome>,
+    tokens: HashMap<Token, i64>,
+}
+
+impl Observed {
+    fn of(gs: &GameState) -> Self {
+        Self {
+            phase: gs.encounter_phase.clone(),
+            encounter_card_id: gs.current_encounter.as_ref().map(|e| e.encounter_card_id()),
+            last_encounter_result: gs.last_encounter_result.clone(),
+            tokens: gs.token_balances.clone(),
+        }
+    }
+
+    /// Events for going from `self` to `after` through the logged action `entry`.
+    fn events_to(&self, after: &Observed, entry: &ActionEntry) -> Vec<GameEvent> {
+        let mut kinds = vec![GameEventKind::ActionAppended {
+            entry: entry.clone(),
+        }];
+        let in_encounter = |phase: &EncounterPhase| *phase == EncounterPhase::InEncounter;
+        if !in_encounter(&self.phase) && in_encounter(&after.phase) {
+            kinds.push(GameEventKind::EncounterStarted {
+                encounter_card_id: after.encounter_card_id,
+            });
+        }
+        if in_encounter(&self.phase) && !in_encounter(&after.phase) {
+            kinds.push(GameEventKind::EncounterFinished {
+                outcome: after.last_encounter_result.clone(),
+            });
+        }
+        if self.phase != after.phase {
+            kinds.push(GameEventKind::PhaseChanged {
+                from: self.phase.clone(),
+                to: after.phase.clone(),
+            });
+        }
+        let mut tokens: Vec<&Token> = self.tokens.keys().chain(after.tokens.keys()).collect();
+        tokens.sort_by_key(|t| format!("{:?}", t));
+        tokens.dedup();
+        for token in tokens {
+            let from = self.tokens.get(token).copied().unwrap_or(0);
+            let to = after.tokens.get(token).copied().unwrap_or(0);
+            if from != to {
+                kinds.push(GameEventKind::TokenChanged {
+                    token: token.clone(),
+                    from,
+                    to,
+                });
+            }
+        }
+        kinds
+            .into_iter()
+            .map(|kind| GameEvent {
+                seq: entry.seq,
+                kind,
+            })
+            .collect()
+    }
+}
+
+/// Play `action` like `POST /action` and publish what it changed. Also returns those events.
+pub(crate) async fn play_published(
+    player_data: &RandomGeneratorWrapper,
+    game_state: &std::sync::Arc<rocket::futures::lock::Mutex<GameState>>,
+    bus: &EventBus,
+    action: PlayerActions,
+) -> (PlayResult, Vec<GameEvent>) {
+    let _playing = bus.playing.lock().await;
+    let before = Observed::of(&*game_state.lock().await);
+    let result = crate::action::play_on(player_data, game_state, action).await;
+    let events = match &result {
+        Ok((_, entry)) => before.events_to(&Observed::of(&*game_state.lock().await), entry),
+        Err(_) => Vec::new(),
+    };
+    bus.publish(&events);
+    (result, events)
+}
+
+/// Rewind the game to right after `to_seq` like `POST /game/rewind` and publish a `rewound`
+/// event. Returns how many actions were discarded.
+pub(crate) async fn rewind_published(
+    player_data: &RandomGeneratorWrapper,
+    game_state: &std::sync::Arc<rocket::futures::lock::Mutex<GameState>>,
+    bus: &EventBus,
+    to_seq: u64,
+) -> Result<u64, String> {
+    let _playing = bus.playing.lock().await;
+    let mut gs = game_state.lock().await;
+    let last_seq = gs.action_log.seq.load(std::sync::atomic::Ordering::SeqCst);
+    *player_data.random_generator_state.lock().await = gs.rewind(to_seq)?;
+    bus.rewinds
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(to_seq);
+    bus.publish(&[GameEvent {
+        seq: to_seq,
+        kind: GameEventKind::Rewound { to_seq },
+    }]);
+    Ok(last_seq - to_seq)
+}
+
+/// The SSE id of an event: its seq, followed by `-<rewinds>` once the game has been rewound,
+/// since a seq alone stops naming one action when rewinds reuse it.
+fn event_id(seq: u64, rewinds: usize) -> String {
+    if rewinds == 0 {
+        seq.to_string()
+    } else {
+        format!("{}-{}", seq, rewinds)
+    }
+}
+
+/// The seq and rewind count from a reconnecting client's `Last-Event-ID` header, if it sent
+/// a valid one.
+pub struct LastEventId(pub Option<(u64, usize)>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(LastEventId(
+            request.headers().get_one("Last-Event-ID").and_then(|id| {
+                let (seq, rewinds) = id.trim().split_once('-').unwrap_or((id.trim(), "0"));
+                Some((seq.parse().ok()?, rewinds.parse().ok()?))
+            }),
+        ))
+    }
+}
+
+/// Stream of game events. Every event's SSE id is the seq of the action behind it, suffixed
+/// with `-<n>` after the n-th rewind. A client reconnecting with `Last-Event-ID` first gets an
+/// `action-appended` event for each logged action after that id, read from the action log,
+/// then the live events. If the game was rewound below that seq since the id was sent, a
+/// `rewound` event to the earliest such seq comes first. Only `rewound` and
+/// `action-appended` are resent: the other events of missed actions are not kept, so a client
+/// that needs them refetches the state it tracks after reconnecting. A client that falls more
+/// than the event buffer behind has its stream closed so it reconnects and resumes.
+#[get("/events")]
+pub async fn events(
+    bus: &State<EventBus>,
+    game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<GameState>>>,
+    last_event_id: LastEventId,
+    mut shutdown: Shutdown,
+) -> EventStream![] {
+    // No action or rewind can land between reading the log and subscribing
+    let playing = bus.playing.lock().await;
+    let mut receiver = bus.subscribe();
+    let rewinds = bus
+        .rewinds
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let mut missed: Vec<GameEvent> = Vec::new();
+    if let Some((seq, seen_rewinds)) = last_event_id.0 {
+        let entries = game_state.lock().await.action_log.entries();
+        let head = entries.last().map_or(0, |e| e.seq);
+        // Rewinds the client hasn't seen, or a log that ends before its id, undo what it has
+        let resume_from = rewinds
+            .get(seen_rewinds..)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .chain([head])
+            .fold(seq, u64::min);
+        if resume_from < seq {
+            missed.push(GameEvent {
+                seq: resume_from,
+                kind: GameEventKind::Rewound {
+                    to_seq: resume_from,
+                },
+            });
+        }
+        missed.extend(
+            entries
+                .into_iter()
+                .filter(|e| e.seq > resume_from)
+                .map(|entry| GameEvent {
+                    seq: entry.seq,
+                    kind: GameEventKind::ActionAppended { entry },
+                }),
+        );
+    }
+    drop(playing);
+    let mut rewind_count = rewinds.len();
+    EventStream! {
+        for event in missed {
+            yield sse_event(&event, rewind_count);
+        }
+        loop {
+            let event = rocket::tokio::select! {
+                // Pending events go out before the stream closes on shutdown
+                biased;
+                received = receiver.recv() => match received {
+                    Ok(event) => event,
+                    // Events were dropped; closing makes the client resume with Last-Event-ID
+                    Err(broadcast::error::RecvError::Lagged(_))
+                    | Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            };
+            if matches!(event.kind, GameEventKind::Rewound { .. }) {
+                rewind_count += 1;
+            }
+            yield sse_event(&event, rewind_count);
+        }
+    }
+}
+
+fn sse_event(event: &GameEvent, rewinds: usize) -> Event {
+    Event::json(event)
+        .event(event.kind.name())
+        .id(event_id(event.seq, rewinds))
+}
@@ -0,0 +1,209 @@
+use either::Left;
+use rocket::futures::lock::Mutex;
+use rocket::response::status::{BadRequest, NotFound};
+use rocket::serde::json::Json;
+use rocket::serde::{Deserialize, Serialize};
+use rocket::State;
+use rocket_okapi::openapi;
+use schemars::JsonSchema;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::action::{play_on, PlayResult, PlayerActions};
+use crate::actions_log::{log_page, ActionLogResponse};
+use crate::events::{rewind_published, EventBus};
+use crate::library::action_log::LogQuery;
+use crate::library::types::EncounterState;
+use crate::library::GameState;
+use crate::player_data::RandomGeneratorWrapper;
+use crate::player_tokens::{token_balances, TokenBalance};
+use crate::status_messages::{new_status, Status};
+
+/// A game forked off the served one. It has its own log and RNG and lives in memory only.
+pub struct ForkedGame {
+    pub game_state: Arc<Mutex<GameState>>,
+    pub player_data: RandomGeneratorWrapper,
+}
+
+/// Forked games kept at once. Forking past this drops the oldest fork.
+pub const MAX_FORKED_GAMES: usize = 32;
+
+/// Forked games by id, handed out from 1 upwards.
+#[derive(Default)]
+pub struct ForkedGames {
+    games: Mutex<HashMap<u64, Arc<ForkedGame>>>,
+    last_id: AtomicU64,
+}
+
+impl ForkedGames {
+    async fn insert(&self, game: ForkedGame) -> u64 {
+        let game_id = self.last_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut games = self.games.lock().await;
+        while games.len() >= MAX_FORKED_GAMES {
+            match games.keys().min().copied() {
+                Some(oldest) => games.remove(&oldest),
+                None => break,
+            };
+        }
+        games.insert(game_id, Arc::new(game));
+        game_id
+    }
+
+    async fn remove(&self, game_id: u64) -> Result<(), NotFound<Json<Status>>> {
+        self.games
+            .lock()
+            .await
+            .remove(&game_id)
+            .map(|_| ())
+            .ok_or_else(|| NotFound(new_status(format!("Forked game {} not found", game_id))))
+    }
+
+    async fn get(&self, game_id: u64) -> Result<Arc<ForkedGame>, NotFound<Json<Status>>> {
+        self.games
+            .lock()
+            .await
+            .get(&game_id)
+            .cloned()
+            .ok_or_else(|| NotFound(new_status(format!("Forked game {} not found", game_id))))
+    }
+}
+
+/// Handle to a forked game, used as `<game_id>` in the `/game/<game_id>/...` routes.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ForkResponse {
+    pub game_id: u64,
+    pub from_seq: u64,
+}
+
+/// Outcome of a rewind: the seq the game now ends at and how many actions were discarded.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct RewindResponse {
+    pub to_seq: u64,
+    pub discarded_actions: u64,
+}
+
+/// Rewind the game to right after action `to_seq`: later actions are dropped from the log
+/// and the state and RNG are rebuilt by replay. `to_seq=0` goes back to before `NewGame`.
+/// Publishes a `rewound` event on `/events`.
+#[openapi]
+#[post("/game/rewind?<to_seq>")]
+pub async fn rewind_game(
+    to_seq: u64,
+    player_data: &State<RandomGeneratorWrapper>,
+    game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<GameState>>>,
+    bus: &State<EventBus>,
+) -> Result<Json<RewindResponse>, BadRequest<Json<Status>>> {
+    let discarded_actions = rewind_published(player_data, game_state, bus, to_seq)
+        .await
+        .map_err(|e| BadRequest(new_status(e)))?;
+    Ok(Json(RewindResponse {
+        to_seq,
+        discarded_actions,
+    }))
+}
+
+/// Fork the game right after action `from_seq` into a new in-memory game with its own log and
+/// RNG, rebuilt by replaying the log up to `from_seq`. The served game is not changed. At most
+/// `MAX_FORKED_GAMES` forks are kept; forking past that drops the oldest one.
+#[openapi]
+#[post("/game/fork?<from_seq>")]
+pub async fn fork_game(
+    from_seq: u64,
+    game_state: &State<Arc<Mutex<GameState>>>,
+    forks: &State<ForkedGames>,
+) -> Result<Json<ForkResponse>, BadRequest<Json<Status>>> {
+    let (forked, rng) = game_state
+        .lock()
+        .await
+        .replay_prefix(from_seq)
+        .map_err(|e| BadRequest(new_status(format!("Cannot fork: {}", e))))?;
+    let game_id = forks
+        .insert(ForkedGame {
+            game_state: Arc::new(Mutex::new(forked)),
+            player_data: crate::player_data::resumed(rng),
+        })
+        .await;
+    Ok(Json(ForkResponse { game_id, from_seq }))
+}
+
+/// `POST /action` against a forked game.
+#[openapi]
+#[post("/game/<game_id>/action", format = "json", data = "<player_action>")]
+pub async fn play_forked(
+    game_id: u64,
+    forks: &State<ForkedGames>,
+    player_action: Json<PlayerActions>,
+) -> PlayResult {
+    let game = forks.get(game_id).await.map_err(Left)?;
+    play_on(&game.player_data, &game.game_state, player_action.0).await
+}
+
+/// `GET /player/tokens` for a forked game.
+#[openapi]
+#[get("/game/<game_id>/player/tokens")]
+pub async fn get_forked_player_tokens(
+    game_id: u64,
+    forks: &State<ForkedGames>,
+) -> Result<Json<Vec<TokenBalance>>, NotFound<Json<Status>>> {
+    let game = forks.get(game_id).await?;
+    let gs = game.game_state.lock().await;
+    Ok(Json(token_balances(&gs)))
+}
+
+/// `GET /encounter` for a forked game.
+#[openapi]
+#[get("/game/<game_id>/encounter")]
+pub async fn get_forked_encounter(
+    game_id: u64,
+    forks: &State<ForkedGames>,
+) -> Result<Json<EncounterState>, NotFound<Json<Status>>> {
+    let game = forks.get(game_id).await?;
+    let gs = game.game_state.lock().await;
+    match &gs.current_encounter {
+        Some(encounter) => Ok(Json(encounter.clone())),
+        None => Err(NotFound(new_status("No active encounter".to_string()))),
+    }
+}
+
+/// `GET /actions/log` for a forked game.
+#[openapi]
+#[get("/game/<game_id>/actions/log?<from_seq>&<to_seq>&<payload_type>&<encounter>&<descending>&<limit>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn list_forked_actions_log(
+    game_id: u64,
+    from_seq: Option<u64>,
+    to_seq: Option<u64>,
+    payload_type: Option<String>,
+    encounter: Option<u64>,
+    descending: Option<bool>,
+    limit: Option<usize>,
+    forks: &State<ForkedGames>,
+) -> Result<Json<ActionLogResponse>, NotFound<Json<Status>>> {
+    let game = forks.get(game_id).await?;
+    let gs = game.game_state.lock().await;
+    Ok(Json(log_page(
+        &gs,
+        LogQuery {
+            from_seq,
+            to_seq,
+            payload_type,
+            encounter,
+            descending: descending.unwrap_or(false),
+            limit: limit.unwrap_or(1000),
+        },
+    )))
+}
+
+/// Drop a forked game. Its id is not handed out again.
+#[openapi]
+#[delete("/game/<game_id>")]
+pub async fn delete_forked_game(
+    game_id: u64,
+    forks: &State<ForkedGames>,
+) -> Result<Json<Status>, NotFound<Json<Status>>> {
+    forks.remove(game_id).await?;
+    Ok(new_status(format!("Forked game {} deleted", game_id)))
+}
@@ -25,10 +25,13 @@ use rocket_okapi::swagger_ui::{make_swagger_ui, SwaggerUIConfig};
 pub mod action;
 pub mod actions_log;
 pub mod combat;
+pub mod events;
+pub mod game;
 pub mod library;
 pub mod player_data;
 pub mod player_tokens;
 pub mod status_messages;
+pub mod websocket;
 
 // Re-export for tests
 pub use crate::player_data::new as player_data_new;
@@ -54,35 +57,66 @@ pub fn rocket_initialize() -> rocket::Rocket<rocket::Build> {
     use crate::action::play;
     use crate::actions_log::list_actions_log;
     use crate::actions_log::okapi_add_operation_for_list_actions_log_;
+    use crate::actions_log::okapi_add_operation_for_verify_actions_log_;
+    use crate::actions_log::verify_actions_log;
     use crate::combat::okapi_add_operation_for_get_encounter_;
+    use crate::combat::okapi_add_operation_for_get_encounter_hand_;
     use crate::combat::okapi_add_operation_for_get_encounter_results_;
+    use crate::combat::okapi_add_operation_for_get_woodcutting_patterns_;
     use crate::combat::okapi_add_operation_for_initialize_combat_;
-    use crate::combat::{get_encounter, get_encounter_results, initialize_combat};
+    use crate::combat::{
+        get_encounter, get_encounter_hand, get_encounter_results, get_woodcutting_patterns,
+        initialize_combat,
+    };
+    use crate::game::okapi_add_operation_for_delete_forked_game_;
+    use crate::game::okapi_add_operation_for_fork_game_;
+    use crate::game::okapi_add_operation_for_get_forked_encounter_;
+    use crate::game::okapi_add_operation_for_get_forked_player_tokens_;
+    use crate::game::okapi_add_operation_for_list_forked_actions_log_;
+    use crate::game::okapi_add_operation_for_play_forked_;
+    use crate::game::okapi_add_operation_for_rewind_game_;
+    use crate::game::{
+        delete_forked_game, fork_game, get_forked_encounter, get_forked_player_tokens,
+        list_forked_actions_log, play_forked, rewind_game,
+    };
     use crate::library::add_test_library_card;
     use crate::library::list_card_effects;
     use crate::library::list_library_cards;
     use crate::library::okapi_add_operation_for_list_card_effects_;
-    use crate::player_tokens::get_player_tokens;
     use crate::player_tokens::okapi_add_operation_for_get_player_tokens_;
+    use crate::player_tokens::okapi_add_operation_for_get_player_tools_;
+    use crate::player_tokens::{get_player_tokens, get_player_tools};
 
     #[allow(clippy::no_effect_underscore_binding)]
     let _ = env_logger::try_init();
 
     use rocket::fairing::AdHoc;
 
-    let gs = std::sync::Arc::new(rocket::futures::lock::Mutex::new(library::GameState::new()));
+    let (game_state, player_data) = initial_game();
+    let gs = std::sync::Arc::new(rocket::futures::lock::Mutex::new(game_state));
 
     let rocket = rocket::build()
         .mount(
             "/",
             openapi_get_routes![
                 get_encounter,
+                get_encounter_hand,
                 initialize_combat,
                 play,
                 get_player_tokens,
+                get_player_tools,
                 get_encounter_results,
+                get_woodcutting_patterns,
                 list_card_effects,
                 list_actions_log,
+                verify_actions_log,
+                rewind_game,
+                fork_game,
+                play_forked,
+                get_forked_player_tokens,
+                get_forked_encounter,
+                list_forked_actions_log,
+                delete_forked_game,
             ],
         )
         .mount("/swagger", make_swagger_ui(&get_docs()))
@@ -91,12 +125,18 @@ pub fn rocket_initialize() -> rocket::Rocket<rocket::Build> {
             rocket::routes![
                 list_library_cards,
                 add_test_library_card,
+                crate::actions_log::export_actions_log_compact,
                 crate::combat::enemy_play,
                 crate::combat::advance_phase,
+                crate::events::events,
+                crate::websocket::action_socket,
+                crate::websocket::action_socket_upgrade_required,
             ],
         )
-        .manage(player_data::new())
+        .manage(player_data)
         .manage(gs.clone())
+        .manage(game::ForkedGames::default())
+        .manage(events::EventBus::default())
         .attach(AdHoc::on_liftoff("actionlog-shutdown", |rocket| {
             Box::pin(async move {
                 // When the process receives SIGINT/SIGTERM (or ctrl-c), flush the action log writer
@@ -133,6 +173,54 @@ pub fn rocket_initialize() -> rocket::Rocket<rocket::Build> {
     rocket
 }
 
+/// A fresh game, or with `ACTION_LOG_FILE` set, the game persisted there: resumed from its
+/// newest valid snapshot plus the log tail, with new actions appended to the same file.
+/// A log that exists but can't be resumed is moved aside with its snapshots before a fresh
+/// game starts in its place; if it can't be moved, the server refuses to start.
+fn initial_game() -> (library::GameState, player_data::RandomGeneratorWrapper) {
+    let Ok(path) = std::env::var("ACTION_LOG_FILE") else {
+        return (library::GameState::new(), player_data::new());
+    };
+    let (mut game_state, player_data) = match library::GameState::resume_from_file(&path) {
+        Ok((game_state, rng)) => (game_state, player_data::resumed(rng)),
+        Err(e) if std::path::Path::new(&path).exists() => {
+            // Never append a new game to a log that can't be resumed: keep it for inspection
+            eprintln!("ActionLog: cannot resume from {}: {}", path, e);
+            match action::persistence::set_aside(std::path::Path::new(&path), "unreadable") {
+                Ok(moved) => eprintln!("ActionLog: moved it to {}", moved.display()),
+                Err(e) => panic!("ActionLog: cannot move {} aside: {}", path, e),
+            }
+            (library::GameState::new(), player_data::new())
+        }
+        Err(_) => (library::GameState::new(), player_data::new()),
+    };
+    if library::compact_log::is_compact_file(std::path::Path::new(&path)) {
+        // New actions are appended as NDJSON, so the resumed log is rewritten in that format
+        match action::persistence::set_aside(std::path::Path::new(&path), "compact") {
+            Ok(moved) => eprintln!(
+                "ActionLog: converting compact log {} to NDJSON, original kept as {}",
+                path,
+                moved.display()
+            ),
+            Err(e) => panic!("ActionLog: cannot move compact log {} aside: {}", path, e),
+        }
+        if let Err(e) = game_state.action_log.write_all_to_file(&path) {
+            panic!("ActionLog: cannot write {} as NDJSON: {}", path, e);
+        }
+    }
+    match action::persistence::FileWriter::new(std::path::PathBuf::from(&path)) {
+        Ok(writer) => match std::sync::Arc::get_mut(&mut game_state.action_log) {
+            Some(log) => log.add_sink(std::sync::Arc::new(writer)),
+            None => eprintln!(
+                "ActionLog FileWriter: the action log is shared, {} will not be written",
+                path
+            ),
+        },
+        Err(e) => eprintln!("ActionLog FileWriter: failed to open {}: {}", path, e),
+    }
+    (game_state, player_data)
+}
+
 fn get_docs() -> SwaggerUIConfig {
     SwaggerUIConfig {
         url: "/openapi.json".to_string(),
@@ -1,40 +1,174 @@
+use super::compact_log;
+use super::state_digest::StateDigest;
 use super::types::{ActionEntry, ActionPayload};
-use crate::action::persistence::FileWriter;
+use crate::action::persistence::ActionSink;
+use rocket::serde::{Deserialize, Serialize};
+use rocket_okapi::JsonSchema;
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{mpsc, Arc, Mutex};
-use std::thread;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The first line (1-based) where a log's seqs or hash chain break, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct LogIntegrityError {
+    pub line: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for LogIntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.reason)
+    }
+}
+
+fn head_hash(entries: &[ActionEntry]) -> String {
+    entries
+        .last()
+        .map_or_else(|| ActionEntry::GENESIS_HASH.to_string(), |e| e.hash.clone())
+}
+
+fn check_hash(entry: &ActionEntry) -> Result<(), String> {
+    if entry.hash_is_valid() {
+        Ok(())
+    } else {
+        Err(format!("seq {} does not match its hash", entry.seq))
+    }
+}
+
+/// `entry` must directly follow `previous` (or start the log) and carry intact hashes.
+fn check_link(entry: &ActionEntry, previous: Option<&ActionEntry>) -> Result<(), String> {
+    let expected_seq = previous.map_or(1, |p| p.seq + 1);
+    if entry.seq != expected_seq {
+        return Err(format!(
+            "expected seq {}, found {}",
+            expected_seq, entry.seq
+        ));
+    }
+    let expected_prev = previous.map_or(ActionEntry::GENESIS_HASH, |p| p.hash.as_str());
+    if entry.prev_hash != expected_prev {
+        return Err(format!(
+            "seq {} does not chain onto the previous entry",
+            entry.seq
+        ));
+    }
+    check_hash(entry)
+}
+
+/// A `Rewind { to_seq }` marker has seq `to_seq` and chains onto the entry at `to_seq`, which
+/// after truncation is the last of `kept`.
+fn check_rewind_marker(marker: &ActionEntry, kept: &[ActionEntry]) -> Result<(), String> {
+    let last_seq = kept.last().map_or(0, |e| e.seq);
+    if marker.seq != last_seq {
+        return Err(format!("rewind to seq {} not found in the log", marker.seq));
+    }
+    if marker.prev_hash != head_hash(kept) {
+        return Err(format!(
+            "rewind to seq {} does not chain onto that entry",
+            marker.seq
+        ));
+    }
+    check_hash(marker)
+}
+
+/// Seqs of the logged entries grouped for queries, kept up to date on every append.
+#[derive(Debug, Clone, Default)]
+struct LogIndex {
+    by_type: HashMap<&'static str, Vec<u64>>,
+    /// Per encounter: the `DrawEncounter` seq and the `ApplyScouting` seq that closes it.
+    /// Open for the encounter still in progress.
+    encounters: Vec<(u64, Option<u64>)>,
+}
+
+impl LogIndex {
+    fn build(entries: &[ActionEntry]) -> Self {
+        let mut index = LogIndex::default();
+        for e in entries {
+            index.add(e);
+        }
+        index
+    }
+
+    fn add(&mut self, entry: &ActionEntry) {
+        self.by_type
+            .entry(entry.payload.type_name())
+            .or_default()
+            .push(entry.seq);
+        match entry.payload {
+            ActionPayload::DrawEncounter { .. } | ActionPayload::SetSeed { .. } => {
+                // A new encounter or game ends one that never reached scouting
+                self.close_open_encounter(entry.seq.saturating_sub(1));
+                if matches!(entry.payload, ActionPayload::DrawEncounter { .. }) {
+                    self.encounters.push((entry.seq, None));
+                }
+            }
+            ActionPayload::ApplyScouting { .. } => self.close_open_encounter(entry.seq),
+            _ => {}
+        }
+    }
+
+    fn close_open_encounter(&mut self, end: u64) {
+        if let Some((_, open @ None)) = self.encounters.last_mut() {
+            *open = Some(end);
+        }
+    }
+
+    /// Seq range of the encounter whose `DrawEncounter` has seq `start`.
+    fn encounter_range(&self, start: u64) -> Option<(u64, u64)> {
+        let i = self
+            .encounters
+            .binary_search_by_key(&start, |(s, _)| *s)
+            .ok()?;
+        let (start, end) = self.encounters[i];
+        Some((start, end.unwrap_or(u64::MAX)))
+    }
+}
+
+/// Which entries `ActionLog::query` returns, and in which order.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    pub from_seq: Option<u64>,
+    pub to_seq: Option<u64>,
+    /// Only entries whose payload `type` is this, e.g. `PlayCard`.
+    pub payload_type: Option<String>,
+    /// Only the entries of the encounter started by the `DrawEncounter` with this seq,
+    /// up to and including the `ApplyScouting` that closes it.
+    pub encounter: Option<u64>,
+    pub descending: bool,
+    pub limit: usize,
+}
+
+/// One page of a query. `next_seq` is the seq the next page starts at: pass it as
+/// `from_seq`, or as `to_seq` when paging in descending order.
+#[derive(Debug, Clone)]
+pub struct LogPage {
+    pub entries: Vec<ActionEntry>,
+    pub next_seq: Option<u64>,
+}
 
 #[derive(Debug)]
 pub struct ActionLog {
     pub entries: Arc<Mutex<Vec<ActionEntry>>>,
     pub seq: AtomicU64,
-    pub sender: mpsc::Sender<ActionEntry>,
-    pub writer: Option<FileWriter>,
+    /// Every appended entry is also written to each of these.
+    sinks: Vec<Arc<dyn ActionSink>>,
+    sink_errors: Mutex<Vec<String>>,
+    index: Mutex<LogIndex>,
 }
 
 impl Clone for ActionLog {
+    /// A copy of the entries with no sinks. Once the copy diverges, writing both to the
+    /// same sink would interleave conflicting seqs, so sinks are attached with `add_sink`.
     fn clone(&self) -> Self {
-        // snapshot existing entries and seq
-        let entries_vec = match self.entries.lock() {
-            Ok(g) => g.clone(),
-            Err(e) => e.into_inner().clone(),
-        };
-        let seq_val = self.seq.load(Ordering::SeqCst);
-        // create a fresh ActionLog (spawns its own worker)
-        let new = ActionLog::new();
-        // replace entries with the snapshot
-        match new.entries.lock() {
-            Ok(mut g) => *g = entries_vec,
-            Err(err) => *err.into_inner() = entries_vec,
-        }
-        new.seq.store(seq_val, Ordering::SeqCst);
         Self {
-            entries: new.entries,
-            seq: new.seq,
-            sender: new.sender,
-            writer: self.writer.clone(),
+            entries: Arc::new(Mutex::new(self.entries())),
+            seq: AtomicU64::new(self.seq.load(Ordering::SeqCst)),
+            sinks: Vec::new(),
+            sink_errors: Mutex::new(Vec::new()),
+            index: Mutex::new(self.lock_index().clone()),
         }
     }
 }
@@ -47,46 +181,148 @@ impl Default for ActionLog {
 
 impl ActionLog {
     pub fn new() -> Self {
-        let entries = Arc::new(Mutex::new(Vec::new()));
-        let (tx, rx) = mpsc::channel::<ActionEntry>();
-        let _worker_entries = Arc::clone(&entries);
-        thread::spawn(move || {
-            // Dedicated worker receives entries for offloaded processing (persistence, analytics, etc.).
-            // Currently it consumes the channel and drops entries after receipt to keep the worker alive
-            // without duplicating in-memory storage (append() writes directly into entries).
-            for _entry in rx {
-                // placeholder: persist or forward the entry to external systems
-            }
-        });
         ActionLog {
-            entries,
+            entries: Arc::new(Mutex::new(Vec::new())),
             seq: AtomicU64::new(0),
-            sender: tx,
-            writer: None,
+            sinks: Vec::new(),
+            sink_errors: Mutex::new(Vec::new()),
+            index: Mutex::new(LogIndex::default()),
+        }
+    }
+
+    fn lock_index(&self) -> MutexGuard<'_, LogIndex> {
+        match self.index.lock() {
+            Ok(g) => g,
+            Err(e) => e.into_inner(),
         }
     }
 
-    pub fn set_writer(&mut self, writer: Option<FileWriter>) {
-        self.writer = writer;
+    pub fn add_sink(&mut self, sink: Arc<dyn ActionSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn sinks(&self) -> &[Arc<dyn ActionSink>] {
+        &self.sinks
+    }
+
+    /// The NDJSON file the log is persisted to, if one of its sinks writes one.
+    pub fn file_path(&self) -> Option<&Path> {
+        self.sinks.iter().find_map(|sink| sink.file_path())
     }
 
+    /// Write `entry` to every sink. Failures are reported on stderr and kept for
+    /// `sink_errors`; the entry stays in the in-memory log either way.
+    pub fn write_to_sinks(&self, entry: &ActionEntry) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.write(entry) {
+                let message = format!("seq {}: {}", entry.seq, e);
+                eprintln!("ActionLog sink failed at {}", message);
+                match self.sink_errors.lock() {
+                    Ok(mut g) => g.push(message),
+                    Err(err) => err.into_inner().push(message),
+                }
+            }
+        }
+    }
+
+    /// Sink failures so far, oldest first.
+    pub fn sink_errors(&self) -> Vec<String> {
+        match self.sink_errors.lock() {
+            Ok(g) => g.clone(),
+            Err(e) => e.into_inner().clone(),
+        }
+    }
+
+    /// Flush and close every sink.
+    pub fn close_sinks(&self) {
+        for sink in &self.sinks {
+            sink.close();
+        }
+    }
+
+    /// Load an NDJSON or compact log, verifying seq continuity and the hash chain entry by
+    /// entry. A `Rewind` marker must chain onto the entry it rewinds to; it drops the entries
+    /// after it.
     pub fn load_from_file(path: &str) -> Result<ActionLog, String> {
         let file = File::open(path).map_err(|e| e.to_string())?;
-        let reader = BufReader::new(file);
-        let mut entries = Vec::new();
-        let mut max_seq = 0u64;
-        for line in reader.lines() {
-            let line = line.map_err(|e| e.to_string())?;
-            if line.trim().is_empty() {
-                continue;
+        let mut reader = BufReader::new(file);
+        if compact_log::is_compact(reader.fill_buf().map_err(|e| e.to_string())?) {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+            return Self::import_compact(&bytes);
+        }
+        let lines = reader.lines().enumerate().filter_map(|(index, line)| {
+            let entry = match line {
+                Ok(line) if line.trim().is_empty() => return None,
+                Ok(line) => serde_json::from_str(&line).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            Some((index + 1, entry))
+        });
+        Self::from_checked_entries(lines)
+    }
+
+    /// Read a log exported by `export_compact`.
+    pub fn import_compact(bytes: &[u8]) -> Result<ActionLog, String> {
+        let entries = compact_log::decode(bytes)?;
+        Self::from_checked_entries(
+            entries
+                .into_iter()
+                .enumerate()
+                .map(|(index, entry)| (index + 1, Ok(entry))),
+        )
+    }
+
+    /// The whole log in the compact binary format, deflated if `compress`.
+    pub fn export_compact(&self, compress: bool) -> Vec<u8> {
+        compact_log::encode(&self.entries(), compress)
+    }
+
+    /// Build a log from `(line, entry)` pairs, applying `Rewind` markers and failing on the
+    /// first line that doesn't parse or doesn't continue the chain. Entries written before
+    /// hashing was added have empty hashes; a log may start with such entries, which are
+    /// chained here, and continue with hashed ones.
+    fn from_checked_entries(
+        lines: impl Iterator<Item = (usize, Result<ActionEntry, String>)>,
+    ) -> Result<ActionLog, String> {
+        let mut entries: Vec<ActionEntry> = Vec::new();
+        let mut legacy_prefix = true;
+        for (line, entry) in lines {
+            let bad_line = |reason: String| LogIntegrityError { line, reason };
+            let mut entry = entry.map_err(|e| bad_line(e).to_string())?;
+            if entry.hash.is_empty() && entry.prev_hash.is_empty() {
+                if !legacy_prefix {
+                    return Err(bad_line(format!(
+                        "seq {} has no hash but follows hashed entries",
+                        entry.seq
+                    ))
+                    .to_string());
+                }
+                let kept: &[ActionEntry] = match entry.payload {
+                    ActionPayload::Rewind { to_seq } => {
+                        let end = entries.partition_point(|e| e.seq <= to_seq);
+                        &entries[..end]
+                    }
+                    _ => &entries,
+                };
+                entry = ActionEntry {
+                    digest: entry.digest,
+                    ..ActionEntry::new(entry.seq, &head_hash(kept), entry.payload)
+                };
+            } else {
+                legacy_prefix = false;
             }
-            let entry: ActionEntry = serde_json::from_str(&line).map_err(|e| e.to_string())?;
-            if entry.seq > max_seq {
-                max_seq = entry.seq;
+            if let ActionPayload::Rewind { to_seq } = entry.payload {
+                entries.retain(|e| e.seq <= to_seq);
+                check_rewind_marker(&entry, &entries).map_err(|e| bad_line(e).to_string())?;
+                continue;
             }
+            check_link(&entry, entries.last()).map_err(|e| bad_line(e).to_string())?;
             entries.push(entry);
         }
+        let max_seq = entries.last().map_or(0, |e| e.seq);
         let log = ActionLog::new();
+        *log.lock_index() = LogIndex::build(&entries);
         {
             match log.entries.lock() {
                 Ok(mut g) => *g = entries,
@@ -112,19 +348,124 @@ impl ActionLog {
         f.flush().map_err(|e| e.to_string())
     }
 
-    /// Append an action entry, assigning an incrementing sequence number.
-    pub fn append(&self, _action_type: &str, payload: ActionPayload) -> ActionEntry {
+    /// Append an action entry, assigning an incrementing sequence number and chaining it
+    /// onto the previous entry's hash.
+    pub fn append(&self, action_type: &str, payload: ActionPayload) -> ActionEntry {
+        self.append_with_digest(action_type, payload, None)
+    }
+
+    /// `append`, recording the state digest the action resulted in.
+    pub fn append_with_digest(
+        &self,
+        _action_type: &str,
+        payload: ActionPayload,
+        digest: Option<StateDigest>,
+    ) -> ActionEntry {
+        let mut entries = match self.entries.lock() {
+            Ok(g) => g,
+            Err(e) => e.into_inner(),
+        };
         let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
-        let entry = ActionEntry {
-            seq,
-            payload: payload.clone(),
+        let mut entry = ActionEntry::new(seq, &head_hash(&entries), payload);
+        entry.digest = digest;
+        self.lock_index().add(&entry);
+        entries.push(entry.clone());
+        drop(entries);
+        self.write_to_sinks(&entry);
+        entry
+    }
+
+    /// Store an entry that already has its seq, e.g. one copied from another log.
+    pub fn record(&self, entry: ActionEntry) {
+        let seq = entry.seq;
+        let mut entries = match self.entries.lock() {
+            Ok(g) => g,
+            Err(e) => e.into_inner(),
         };
+        self.lock_index().add(&entry);
+        entries.push(entry);
+        drop(entries);
+        self.seq.fetch_max(seq, Ordering::SeqCst);
+    }
+
+    /// `hash` of the last entry, which the next entry chains onto.
+    pub fn head_hash(&self) -> String {
         match self.entries.lock() {
-            Ok(mut g) => g.push(entry.clone()),
-            Err(e) => e.into_inner().push(entry.clone()),
+            Ok(g) => head_hash(&g),
+            Err(e) => head_hash(&e.into_inner()),
+        }
+    }
+
+    /// Check that seqs run 1, 2, 3, ... and that every entry's hashes are intact and chained.
+    pub fn verify(&self) -> Result<(), LogIntegrityError> {
+        let entries = self.entries();
+        let mut previous = None;
+        for (index, entry) in entries.iter().enumerate() {
+            check_link(entry, previous).map_err(|reason| LogIntegrityError {
+                line: index + 1,
+                reason,
+            })?;
+            previous = Some(entry);
+        }
+        Ok(())
+    }
+
+    /// One page of the entries matching `query`. Only the returned entries are cloned: seq
+    /// ranges are found by binary search and type and encounter filters use the index.
+    pub fn query(&self, query: &LogQuery) -> LogPage {
+        let entries = match self.entries.lock() {
+            Ok(g) => g,
+            Err(e) => e.into_inner(),
+        };
+        let index = self.lock_index();
+        let mut from = query.from_seq.unwrap_or(0);
+        let mut to = query.to_seq.unwrap_or(u64::MAX);
+        if let Some(start) = query.encounter {
+            let Some((start, end)) = index.encounter_range(start) else {
+                return LogPage {
+                    entries: Vec::new(),
+                    next_seq: None,
+                };
+            };
+            from = from.max(start);
+            to = to.min(end);
+        }
+        let seqs: Box<dyn DoubleEndedIterator<Item = u64>> = match &query.payload_type {
+            Some(payload_type) => {
+                let seqs = index
+                    .by_type
+                    .get(payload_type.as_str())
+                    .map_or(&[][..], |s| s.as_slice());
+                let lo = seqs.partition_point(|s| *s < from);
+                let hi = seqs.partition_point(|s| *s <= to);
+                Box::new(seqs[lo..hi.max(lo)].iter().copied())
+            }
+            None => {
+                let lo = entries.partition_point(|e| e.seq < from);
+                let hi = entries.partition_point(|e| e.seq <= to);
+                Box::new(entries[lo..hi.max(lo)].iter().map(|e| e.seq))
+            }
+        };
+        let mut page: Vec<u64> = if query.descending {
+            seqs.rev().take(query.limit + 1).collect()
+        } else {
+            seqs.take(query.limit + 1).collect()
+        };
+        let next_seq = if page.len() > query.limit {
+            page.pop()
+        } else {
+            None
+        };
+        LogPage {
+            entries: page
+                .into_iter()
+                .filter_map(|seq| {
+                    let i = entries.binary_search_by_key(&seq, |e| e.seq).ok()?;
+                    Some(entries[i].clone())
+                })
+                .collect(),
+            next_seq,
         }
-        let _ = self.sender.send(entry.clone());
-        entry
     }
 
     /// Return a cloned snapshot of entries for replay/inspection
@@ -0,0 +1,542 @@
+//! Compact binary encoding of action log entries, for exporting and importing long logs.
+//!
+//! Layout: the `MAGIC` bytes, a version byte, a flags byte, then the body (deflated when
+//! `FLAG_DEFLATE` is set). The body holds the entry count, then per entry the zigzag varint
+//! delta from the previous seq, the payload tag and the payload fields as varints, and ends
+//! with the 32-byte hash of the last entry. Hashes are rebuilt on import and checked against
+//! that head hash.
+//!
+//! With `FLAG_DIGESTS` each entry is followed by its state digest: a presence byte, the
+//! encounter result tag, the two 8-byte short hashes, then the token balances that changed
+//! since the previous digest and the tokens it no longer has. A token type is spelled out
+//! the first time it appears and referred to by its index after that. The body then ends
+//! with a SHA-256 of everything before it, since digests are not covered by the hash chain.
+//! Logs written without the flag decode with no digests.
+
+use super::state_digest::StateDigest;
+use super::types::{
+    ActionEntry, ActionPayload, CombatPhase, Discipline, EncounterOutcome, FishingBait, Token,
+    TokenLifecycle, TokenType,
+};
+use std::collections::HashMap;
+
+pub const MAGIC: &[u8; 7] = b"MLCGLOG";
+const VERSION: u8 = 1;
+const FLAG_DEFLATE: u8 = 1;
+const FLAG_DIGESTS: u8 = 2;
+/// Hex length of the digest's short hashes, stored as half as many bytes.
+const SHORT_HASH_BYTES: usize = 8;
+/// Upper bound for an inflated body, so a corrupt file can't exhaust memory.
+const MAX_BODY_BYTES: usize = 1 << 30;
+
+/// Whether `bytes` start like a compact log.
+pub fn is_compact(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Whether the file at `path` starts like a compact log.
+pub fn is_compact_file(path: &std::path::Path) -> bool {
+    use std::io::Read;
+    let mut head = [0u8; MAGIC.len()];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut head))
+        .is_ok_and(|_| is_compact(&head))
+}
+
+/// Encode `entries` (in log order). With `compress` the body is deflated.
+pub fn encode(entries: &[ActionEntry], compress: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    write_varint(&mut body, entries.len() as u64);
+    let mut previous_seq = 0u64;
+    let mut digests = DigestTable::default();
+    for entry in entries {
+        write_varint(&mut body, zigzag(entry.seq as i64 - previous_seq as i64));
+        previous_seq = entry.seq;
+        write_payload(&mut body, &entry.payload);
+        digests.write(&mut body, entry.digest.as_ref());
+    }
+    let head = entries
+        .last()
+        .map_or(ActionEntry::GENESIS_HASH, |e| e.hash.as_str());
+    body.extend(hex_to_bytes::<32>(head).unwrap_or([0; 32]));
+    body.extend(checksum(&body));
+
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    if compress {
+        out.push(FLAG_DIGESTS | FLAG_DEFLATE);
+        out.extend(miniz_oxide::deflate::compress_to_vec(&body, 6));
+    } else {
+        out.push(FLAG_DIGESTS);
+        out.extend(body);
+    }
+    out
+}
+
+/// Decode a compact log back into entries with their hashes rebuilt. A `Rewind` marker is
+/// chained onto the entry it rewinds to, as in the NDJSON file. Fails when the bytes are
+/// malformed, the rebuilt chain doesn't end in the recorded head hash or the digests don't
+/// match the body checksum.
+pub fn decode(bytes: &[u8]) -> Result<Vec<ActionEntry>, String> {
+    let rest = bytes
+        .strip_prefix(MAGIC.as_slice())
+        .ok_or("Not a compact action log")?;
+    let [version, flags, rest @ ..] = rest else {
+        return Err("Truncated compact log header".to_string());
+    };
+    if *version != VERSION {
+        return Err(format!("Unsupported compact log version {}", version));
+    }
+    let body = if flags & FLAG_DEFLATE != 0 {
+        miniz_oxide::inflate::decompress_to_vec_with_limit(rest, MAX_BODY_BYTES)
+            .map_err(|e| format!("Compact log does not inflate: {:?}", e.status))?
+    } else {
+        rest.to_vec()
+    };
+
+    let with_digests = flags & FLAG_DIGESTS != 0;
+    let mut reader = Reader { bytes: &body };
+    let count = reader.varint()?;
+    let mut entries: Vec<ActionEntry> = Vec::new();
+    // Entries the next one chains onto: a rewind drops the ones after its target
+    let mut kept: Vec<ActionEntry> = Vec::new();
+    let mut seq = 0u64;
+    let mut digests = DigestTable::default();
+    for _ in 0..count {
+        seq = seq
+            .checked_add_signed(unzigzag(reader.varint()?))
+            .ok_or("Compact log seq out of range")?;
+        let payload = reader.payload()?;
+        if let ActionPayload::Rewind { to_seq } = payload {
+            kept.retain(|e| e.seq <= to_seq);
+        }
+        let prev_hash = kept
+            .last()
+            .map_or(ActionEntry::GENESIS_HASH, |e| e.hash.as_str());
+        let mut entry = ActionEntry::new(seq, prev_hash, payload);
+        if with_digests {
+            entry.digest = digests.read(&mut reader)?;
+        }
+        if !matches!(entry.payload, ActionPayload::Rewind { .. }) {
+            kept.push(entry.clone());
+        }
+        entries.push(entry);
+    }
+    let head = reader.take(32)?;
+    let rebuilt = entries
+        .last()
+        .map_or(ActionEntry::GENESIS_HASH, |e| e.hash.as_str());
+    if hex_to_bytes::<32>(rebuilt).as_ref().map(|h| h.as_slice()) != Some(head) {
+        return Err("Compact log does not match its head hash".to_string());
+    }
+    if with_digests {
+        let summed = &body[..body.len() - reader.bytes.len()];
+        if reader.take(32)? != checksum(summed).as_slice() {
+            return Err("Compact log does not match its checksum".to_string());
+        }
+    }
+    if !reader.bytes.is_empty() {
+        return Err("Trailing bytes after the compact log".to_string());
+    }
+    Ok(entries)
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn hex_to_bytes<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn checksum(bytes: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes).into()
+}
+
+fn outcome_tag(outcome: Option<&EncounterOutcome>) -> u64 {
+    match outcome {
+        None => 0,
+        Some(EncounterOutcome::Undecided) => 1,
+        Some(EncounterOutcome::PlayerWon) => 2,
+        Some(EncounterOutcome::PlayerLost) => 3,
+    }
+}
+
+fn phase_tag(phase: &CombatPhase) -> u64 {
+    match phase {
+        CombatPhase::Defending => 0,
+        CombatPhase::Attacking => 1,
+        CombatPhase::Resourcing => 2,
+    }
+}
+
+/// What the digests written or read so far have in common with the next one.
+#[derive(Default)]
+struct DigestTable {
+    /// Token type names in the order they first appeared.
+    names: Vec<String>,
+    /// The balances of the previous digest.
+    balances: HashMap<Token, i64>,
+}
+
+impl DigestTable {
+    /// The digest of one entry: absent when the entry has none or its short hashes aren't
+    /// the hex `StateDigest` produces.
+    fn write(&mut self, out: &mut Vec<u8>, digest: Option<&StateDigest>) {
+        let hashes = digest.and_then(|d| {
+            Some((
+                hex_to_bytes::<SHORT_HASH_BYTES>(&d.library_hash)?,
+                hex_to_bytes::<SHORT_HASH_BYTES>(&d.rng_hash)?,
+            ))
+        });
+        let (Some(digest), Some((library_hash, rng_hash))) = (digest, hashes) else {
+            out.push(0);
+            return;
+        };
+        out.push(1);
+        write_varint(out, outcome_tag(digest.last_encounter_result.as_ref()));
+        out.extend(library_hash);
+        out.extend(rng_hash);
+
+        let mut changed: Vec<(&Token, i64)> = digest
+            .token_balances
+            .iter()
+            .filter(|(token, value)| self.balances.get(*token) != Some(*value))
+            .map(|(token, value)| (token, *value))
+            .collect();
+        changed.sort_by_cached_key(|(token, _)| format!("{:?}", token));
+        let mut removed: Vec<Token> = self
+            .balances
+            .keys()
+            .filter(|token| !digest.token_balances.contains_key(*token))
+            .cloned()
+            .collect();
+        removed.sort_by_cached_key(|token| format!("{:?}", token));
+        write_varint(out, changed.len() as u64);
+        for (token, value) in changed {
+            self.write_token(out, token);
+            write_varint(out, zigzag(value));
+        }
+        write_varint(out, removed.len() as u64);
+        for token in &removed {
+            self.write_token(out, token);
+        }
+        self.balances = digest.token_balances.clone();
+    }
+
+    fn write_token(&mut self, out: &mut Vec<u8>, token: &Token) {
+        let name = format!("{:?}", token.token_type);
+        match self.names.iter().position(|n| *n == name) {
+            Some(index) => write_varint(out, index as u64),
+            None => {
+                write_varint(out, self.names.len() as u64);
+                write_varint(out, name.len() as u64);
+                out.extend(name.as_bytes());
+                self.names.push(name);
+            }
+        }
+        write_lifecycle(out, &token.lifecycle);
+    }
+
+    fn read(&mut self, reader: &mut Reader) -> Result<Option<StateDigest>, String> {
+        match reader.take(1)?[0] {
+            0 => return Ok(None),
+            1 => {}
+            tag => return Err(format!("Unknown digest tag {}", tag)),
+        }
+        let last_encounter_result = match reader.varint()? {
+            0 => None,
+            1 => Some(EncounterOutcome::Undecided),
+            2 => Some(EncounterOutcome::PlayerWon),
+            3 => Some(EncounterOutcome::PlayerLost),
+            tag => return Err(format!("Unknown encounter result tag {}", tag)),
+        };
+        let library_hash = bytes_to_hex(reader.take(SHORT_HASH_BYTES)?);
+        let rng_hash = bytes_to_hex(reader.take(SHORT_HASH_BYTES)?);
+        for _ in 0..reader.len()? {
+            let token = self.read_token(reader)?;
+            let value = unzigzag(reader.varint()?);
+            self.balances.insert(token, value);
+        }
+        for _ in 0..reader.len()? {
+            let token = self.read_token(reader)?;
+            self.balances.remove(&token);
+        }
+        Ok(Some(StateDigest {
+            token_balances: self.balances.clone(),
+            last_encounter_result,
+            library_hash,
+            rng_hash,
+        }))
+    }
+
+    fn read_token(&mut self, reader: &mut Reader) -> Result<Token, String> {
+        let index = reader.usize()?;
+        if index == self.names.len() {
+            let len = reader.usize()?;
+            let name = std::str::from_utf8(reader.take(len)?).map_err(|e| e.to_string())?;
+            self.names.push(name.to_string());
+        }
+        let name = self
+            .names
+            .get(index)
+            .ok_or_else(|| format!("Unknown token type index {}", index))?;
+        let token_type: TokenType = serde_json::from_str(&format!("\"{}\"", name))
+            .map_err(|_| format!("Unknown token type {}", name))?;
+        Ok(Token {
+            token_type,
+            lifecycle: reader.lifecycle()?,
+        })
+    }
+}
+
+fn write_lifecycle(out: &mut Vec<u8>, lifecycle: &TokenLifecycle) {
+    match lifecycle {
+        TokenLifecycle::Permanent => write_varint(out, 0),
+        TokenLifecycle::PersistentCounter => write_varint(out, 1),
+        TokenLifecycle::FixedDuration { duration } => {
+            write_varint(out, 2);
+            write_varint(out, *duration);
+        }
+        TokenLifecycle::FixedTypeDuration { duration, phases } => {
+            write_varint(out, 3);
+            write_varint(out, *duration);
+            write_varint(out, phases.len() as u64);
+            for phase in phases {
+                write_varint(out, phase_tag(phase));
+            }
+        }
+        TokenLifecycle::UntilNextAction => write_varint(out, 4),
+        TokenLifecycle::SingleUse => write_varint(out, 5),
+        TokenLifecycle::Conditional => write_varint(out, 6),
+    }
+}
+
+fn discipline_tag(discipline: &Discipline) -> u64 {
+    Discipline::ALL
+        .iter()
+        .position(|d| d == discipline)
+        .unwrap_or(0) as u64
+}
+
+fn bait_tag(bait: &FishingBait) -> u64 {
+    match bait {
+        FishingBait::Chum => 0,
+        FishingBait::Glowworm => 1,
+    }
+}
+
+fn write_payload(out: &mut Vec<u8>, payload: &ActionPayload) {
+    match payload {
+        ActionPayload::SetSeed { seed } => {
+            write_varint(out, 0);
+            write_varint(out, *seed);
+        }
+        ActionPayload::DrawEncounter { encounter_id } => {
+            write_varint(out, 1);
+            write_varint(out, encounter_id.len() as u64);
+            out.extend(encounter_id.as_bytes());
+        }
+        ActionPayload::PlayCard { card_id } => {
+            write_varint(out, 2);
+            write_varint(out, *card_id as u64);
+        }
+        ActionPayload::ApplyScouting { card_ids } => {
+            write_varint(out, 3);
+            write_varint(out, card_ids.len() as u64);
+            for id in card_ids {
+                write_varint(out, *id as u64);
+            }
+        }
+        ActionPayload::AbortEncounter => write_varint(out, 4),
+        ActionPayload::RetreatEncounter => write_varint(out, 5),
+        ActionPayload::ConcludeEncounter => write_varint(out, 6),
+        ActionPayload::CraftSwap { from_id, to_id } => {
+            write_varint(out, 7);
+            write_varint(out, *from_id as u64);
+            write_varint(out, *to_id as u64);
+        }
+        ActionPayload::CraftCard { target_card_id } => {
+            write_varint(out, 8);
+            write_varint(out, *target_card_id as u64);
+        }
+        ActionPayload::CraftDurability { discipline } => {
+            write_varint(out, 9);
+            write_varint(out, discipline_tag(discipline));
+        }
+        ActionPayload::FishingBait { bait } => {
+            write_varint(out, 10);
+            write_varint(out, bait_tag(bait));
+        }
+        ActionPayload::Repair { discipline } => {
+            write_varint(out, 11);
+            write_varint(out, discipline_tag(discipline));
+        }
+        ActionPayload::EquipTool { card_id } => {
+            write_varint(out, 12);
+            write_varint(out, *card_id as u64);
+        }
+        ActionPayload::UnequipTool { discipline } => {
+            write_varint(out, 13);
+            write_varint(out, discipline_tag(discipline));
+        }
+        ActionPayload::Rewind { to_seq } => {
+            write_varint(out, 14);
+            write_varint(out, *to_seq);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < n {
+            return Err("Compact log ends mid-entry".to_string());
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Compact log varint is too long".to_string())
+    }
+
+    fn usize(&mut self) -> Result<usize, String> {
+        usize::try_from(self.varint()?).map_err(|e| e.to_string())
+    }
+
+    fn discipline(&mut self) -> Result<Discipline, String> {
+        let tag = self.usize()?;
+        Discipline::ALL
+            .get(tag)
+            .cloned()
+            .ok_or_else(|| format!("Unknown discipline tag {}", tag))
+    }
+
+    /// A count of the items that follow. Every item takes at least one byte, which bounds
+    /// the count by what is left.
+    fn len(&mut self) -> Result<usize, String> {
+        let len = self.usize()?;
+        if len > self.bytes.len() {
+            return Err("Compact log ends mid-entry".to_string());
+        }
+        Ok(len)
+    }
+
+    fn lifecycle(&mut self) -> Result<TokenLifecycle, String> {
+        Ok(match self.varint()? {
+            0 => TokenLifecycle::Permanent,
+            1 => TokenLifecycle::PersistentCounter,
+            2 => TokenLifecycle::FixedDuration {
+                duration: self.varint()?,
+            },
+            3 => {
+                let duration = self.varint()?;
+                let phases = (0..self.len()?)
+                    .map(|_| match self.varint()? {
+                        0 => Ok(CombatPhase::Defending),
+                        1 => Ok(CombatPhase::Attacking),
+                        2 => Ok(CombatPhase::Resourcing),
+                        tag => Err(format!("Unknown combat phase tag {}", tag)),
+                    })
+                    .collect::<Result<_, _>>()?;
+                TokenLifecycle::FixedTypeDuration { duration, phases }
+            }
+            4 => TokenLifecycle::UntilNextAction,
+            5 => TokenLifecycle::SingleUse,
+            6 => TokenLifecycle::Conditional,
+            tag => return Err(format!("Unknown lifecycle tag {}", tag)),
+        })
+    }
+
+    fn payload(&mut self) -> Result<ActionPayload, String> {
+        Ok(match self.varint()? {
+            0 => ActionPayload::SetSeed {
+                seed: self.varint()?,
+            },
+            1 => {
+                let len = self.usize()?;
+                let encounter_id =
+                    String::from_utf8(self.take(len)?.to_vec()).map_err(|e| e.to_string())?;
+                ActionPayload::DrawEncounter { encounter_id }
+            }
+            2 => ActionPayload::PlayCard {
+                card_id: self.usize()?,
+            },
+            3 => {
+                let len = self.len()?;
+                let card_ids = (0..len).map(|_| self.usize()).collect::<Result<_, _>>()?;
+                ActionPayload::ApplyScouting { card_ids }
+            }
+            4 => ActionPayload::AbortEncounter,
+            5 => ActionPayload::RetreatEncounter,
+            6 => ActionPayload::ConcludeEncounter,
+            7 => ActionPayload::CraftSwap {
+                from_id: self.usize()?,
+                to_id: self.usize()?,
+            },
+            8 => ActionPayload::CraftCard {
+                target_card_id: self.usize()?,
+            },
+            9 => ActionPayload::CraftDurability {
+                discipline: self.discipline()?,
+            },
+            10 => ActionPayload::FishingBait {
+                bait: match self.varint()? {
+                    0 => FishingBait::Chum,
+                    1 => FishingBait::Glowworm,
+                    tag => return Err(format!("Unknown bait tag {}", tag)),
+                },
+            },
+            11 => ActionPayload::Repair {
+                discipline: self.discipline()?,
+            },
+            12 => ActionPayload::EquipTool {
+                card_id: self.usize()?,
+            },
+            13 => ActionPayload::UnequipTool {
+                discipline: self.discipline()?,
+            },
+            14 => ActionPayload::Rewind {
+                to_seq: self.varint()?,
+            },
+            tag => return Err(format!("Unknown payload tag {}", tag)),
+        })
+    }
+}
@@ -5,7 +5,7 @@ use crate::library::types::{
 use crate::library::{GameState, Library};
 use std::collections::HashMap;
 
-use crate::library::game_state::roll_concrete_effect;
+use crate::library::game_state::{roll_concrete_effect, roll_range};
 
 pub(crate) fn register_combat_cards(lib: &mut Library, rng: &mut rand_pcg::Lcg64Xsh32) {
     // Attack card: deals damage to opponent
@@ -58,6 +58,8 @@ pub(crate) fn register_combat_cards(lib: &mut Library, rng: &mut rand_pcg::Lcg64
                     initial_tokens: HashMap::from([
                         (types::Token::persistent(types::TokenType::Health), 2000),
                         (types::Token::persistent(types::TokenType::MaxHealth), 2000),
+                        (types::Token::persistent(types::TokenType::Stamina), 300),
+                        (types::Token::persistent(types::TokenType::Mana), 100),
                     ]),
                     attack_deck: vec![types::EnemyCardDef {
                         effects: vec![roll_concrete_effect(rng, 4, lib)],
@@ -86,6 +88,41 @@ pub(crate) fn register_combat_cards(lib: &mut Library, rng: &mut rand_pcg::Lcg64
                             discard: 0,
                         },
                     }],
+                    retreat_cost: types::RetreatCost::Stamina { amount: 200 },
+                    rewards: HashMap::from([(
+                        types::Token::persistent(types::TokenType::Renown),
+                        5,
+                    )]),
+                    loot_table: Some(types::LootTable {
+                        rolls: 2,
+                        entries: vec![
+                            types::LootEntry {
+                                token_type: types::TokenType::Insight,
+                                min: 5,
+                                max: 15,
+                                weight: 2,
+                            },
+                            types::LootEntry {
+                                token_type: types::TokenType::Ore,
+                                min: 10,
+                                max: 30,
+                                weight: 3,
+                            },
+                            types::LootEntry {
+                                token_type: types::TokenType::Lumber,
+                                min: 10,
+                                max: 30,
+                                weight: 3,
+                            },
+                            types::LootEntry {
+                                token_type: types::TokenType::Renown,
+                                min: 1,
+                                max: 3,
+                                weight: 1,
+                            },
+                        ],
+                    }),
+                    entry_cost: vec![],
                 },
             },
         },
@@ -174,16 +211,123 @@ pub(crate) fn register_combat_cards(lib: &mut Library, rng: &mut rand_pcg::Lcg64
             discard: 0,
         },
     );
+
+    // Finishing blow PlayerCardEffect: extra damage only while the enemy has no Shield
+    let finishing_blow_idx = lib.cards.len();
+    lib.add_card(
+        CardKind::PlayerCardEffect {
+            kind: types::CardEffectKind::ConditionalLoseTokens {
+                condition: types::EffectCondition::OpponentShieldZero,
+                target: types::EffectTarget::OnOpponent,
+                token_type: types::TokenType::Health,
+                min: 200,
+                max: 400,
+            },
+        },
+        CardCounts {
+            library: 1,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    // Second wind PlayerCardEffect: heal only while own Health is below 30%
+    let second_wind_idx = lib.cards.len();
+    lib.add_card(
+        CardKind::PlayerCardEffect {
+            kind: types::CardEffectKind::ConditionalGainTokens {
+                condition: types::EffectCondition::OwnHealthBelowPercent { percent: 30 },
+                target: types::EffectTarget::OnSelf,
+                token_type: types::TokenType::Health,
+                min: 150,
+                max: 250,
+            },
+        },
+        CardCounts {
+            library: 1,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    // Thorns PlayerCardEffect: reflect part of each hit taken this round
+    let thorns_idx = lib.cards.len();
+    lib.add_card(
+        CardKind::PlayerCardEffect {
+            kind: types::CardEffectKind::ReflectDamage {
+                min_percent: 30,
+                max_percent: 50,
+            },
+        },
+        CardCounts {
+            library: 1,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    // Conditional cards start in the library; crafting swaps them into the deck.
+    lib.add_card(
+        CardKind::Attack {
+            effects: vec![
+                roll_concrete_effect(rng, 0, lib),
+                roll_concrete_effect(rng, finishing_blow_idx, lib),
+            ],
+        },
+        CardCounts {
+            library: 2,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    lib.add_card(
+        CardKind::Defence {
+            effects: vec![
+                roll_concrete_effect(rng, 1, lib),
+                roll_concrete_effect(rng, thorns_idx, lib),
+            ],
+        },
+        CardCounts {
+            library: 2,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    lib.add_card(
+        CardKind::Resource {
+            effects: vec![
+                roll_concrete_effect(rng, 2, lib),
+                roll_concrete_effect(rng, second_wind_idx, lib),
+            ],
+        },
+        CardCounts {
+            library: 2,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
 }
 
 /// Apply card effects to combat using concrete rolled values.
-/// Only processes GainTokens and LoseTokens effects; DrawCards effects are handled separately.
+/// DrawCards effects are handled separately. Conditional effects only apply when their
+/// condition holds; ReflectDamage registers an on-hit trigger for the rest of the round.
+/// `resolved` accumulates what the card has done so far (damage dealt, tokens gained)
+/// so a later Drain effect on the same card can convert part of it into recovery.
 fn apply_card_effects(
     effects: &[ConcreteEffect],
     is_player: bool,
     player_tokens: &mut HashMap<types::Token, i64>,
     combat: &mut CombatEncounterState,
     library: &Library,
+    resolved: &mut HashMap<types::TokenType, i64>,
 ) {
     for effect in effects {
         let kind = match library.resolve_effect(effect.effect_id) {
@@ -198,64 +342,250 @@ fn apply_card_effects(
             types::CardEffectKind::LoseTokens {
                 target, token_type, ..
             } => (target, token_type, true),
+            types::CardEffectKind::ConditionalLoseTokens {
+                condition,
+                target,
+                token_type,
+                ..
+            } => {
+                if !condition_holds(condition, is_player, player_tokens, &combat.enemy_tokens) {
+                    continue;
+                }
+                (target, token_type, true)
+            }
+            types::CardEffectKind::ConditionalGainTokens {
+                condition,
+                target,
+                token_type,
+                ..
+            } => {
+                if !condition_holds(condition, is_player, player_tokens, &combat.enemy_tokens) {
+                    continue;
+                }
+                (target, token_type, false)
+            }
+            types::CardEffectKind::ReflectDamage { .. } => {
+                combat.triggers.push(types::CombatTrigger {
+                    owner: combatant(is_player),
+                    kind: types::CombatTriggerKind::ReflectOnHit {
+                        percent: effect.rolled_value.max(0) as u32,
+                    },
+                });
+                continue;
+            }
+            types::CardEffectKind::Drain {
+                source_token,
+                target_token,
+                percent,
+            } => {
+                let drain = types::DrainEffect {
+                    source_token: source_token.clone(),
+                    target_token: target_token.clone(),
+                    percent: *percent,
+                };
+                let recovered = drain.recovery(resolved);
+                if recovered > 0 {
+                    let owner_tokens = combatant_tokens(is_player, player_tokens, combat);
+                    *types::token_entry_by_type(owner_tokens, target_token) += recovered;
+                    types::clamp_to_max(owner_tokens, target_token);
+                }
+                continue;
+            }
             types::CardEffectKind::DrawCards { .. } => continue,
         };
 
-        let target_tokens = match (target, is_player) {
-            (types::EffectTarget::OnSelf, true) | (types::EffectTarget::OnOpponent, false) => {
-                &mut *player_tokens
-            }
-            (types::EffectTarget::OnOpponent, true) | (types::EffectTarget::OnSelf, false) => {
-                &mut combat.enemy_tokens
+        let target_is_player = matches!(
+            (target, is_player),
+            (types::EffectTarget::OnSelf, true) | (types::EffectTarget::OnOpponent, false)
+        );
+        let targets_owner = target_is_player == is_player;
+
+        if is_loss && *token_type == types::TokenType::Health {
+            let dealt =
+                deal_health_damage(effect.rolled_value, target_is_player, player_tokens, combat);
+            if !targets_owner {
+                *resolved.entry(token_type.clone()).or_insert(0) += dealt;
             }
-        };
+            continue;
+        }
 
+        let target_tokens = combatant_tokens(target_is_player, player_tokens, combat);
+        let key = types::Token::persistent(token_type.clone());
+        let before = target_tokens.get(&key).copied().unwrap_or(0);
         if is_loss {
-            let damage = effect.rolled_value;
-            if *token_type == types::TokenType::Health {
-                // Dodge absorbs first (timing-based, expires after Defending phase)
-                let dodge = target_tokens
-                    .get(&types::Token::dodge())
-                    .copied()
-                    .unwrap_or(0);
-                let dodge_absorbed = dodge.min(damage);
-                target_tokens.insert(types::Token::dodge(), (dodge - dodge_absorbed).max(0));
-                let after_dodge = damage - dodge_absorbed;
-                // Shield absorbs next (persists for encounter, blocks 1:1)
-                let shield_key = types::Token::persistent(types::TokenType::Shield);
-                let shield = target_tokens.get(&shield_key).copied().unwrap_or(0);
-                let shield_absorbed = shield.min(after_dodge);
-                target_tokens.insert(shield_key, (shield - shield_absorbed).max(0));
-                let remaining_damage = after_dodge - shield_absorbed;
-                if remaining_damage > 0 {
-                    let health = target_tokens
-                        .entry(types::Token::persistent(types::TokenType::Health))
-                        .or_insert(0);
-                    *health = (*health - remaining_damage).max(0);
-                }
-            } else {
-                let entry = target_tokens
-                    .entry(types::Token::persistent(token_type.clone()))
-                    .or_insert(0);
-                *entry = (*entry - damage).max(0);
+            let entry = target_tokens.entry(key).or_insert(0);
+            *entry = (*entry - effect.rolled_value).max(0);
+            if !targets_owner {
+                *resolved.entry(token_type.clone()).or_insert(0) += before - *entry;
             }
         } else {
             // GainTokens: granted = cap * gain_percent / 100, clamped so balance <= cap
             let grant_amount = match (effect.rolled_cap, effect.rolled_gain_percent) {
                 (Some(cap), Some(pct)) => {
                     let raw_gain = cap * pct as i64 / 100;
-                    let key = types::Token::persistent(token_type.clone());
-                    let current = target_tokens.get(&key).copied().unwrap_or(0);
-                    raw_gain.min((cap - current).max(0))
+                    raw_gain.min((cap - before).max(0))
                 }
                 _ => effect.rolled_value,
             };
-            let entry = target_tokens
-                .entry(types::Token::persistent(token_type.clone()))
-                .or_insert(0);
+            let entry = target_tokens.entry(key).or_insert(0);
             *entry = (*entry + grant_amount).max(0);
+            if targets_owner {
+                *resolved.entry(token_type.clone()).or_insert(0) += *entry - before;
+            }
+        }
+    }
+}
+
+/// A card is payable if it has no effects, or any single effect is costless or affordable.
+fn effects_payable(effects: &[ConcreteEffect], tokens: &HashMap<types::Token, i64>) -> bool {
+    effects.is_empty()
+        || effects
+            .iter()
+            .any(|effect| GameState::preview_costs(std::slice::from_ref(effect), tokens).is_ok())
+}
+
+/// Enemy counterpart of `all_combat_hand_cards_unpayable`: true when the enemy holds cards
+/// across its attack/defence/resource hands and none of them can be paid from `enemy_tokens`.
+fn all_enemy_hand_cards_unpayable(combat: &CombatEncounterState) -> bool {
+    let mut hand_cards = combat
+        .enemy_attack_deck
+        .iter()
+        .chain(&combat.enemy_defence_deck)
+        .chain(&combat.enemy_resource_deck)
+        .filter(|card| card.counts.hand > 0)
+        .peekable();
+    if hand_cards.peek().is_none() {
+        return false;
+    }
+    hand_cards.all(|card| !effects_payable(&card.effects, &combat.enemy_tokens))
+}
+
+/// Roll a loot table: each roll picks a weighted entry and a random amount in its range.
+fn roll_loot(
+    table: &types::LootTable,
+    rng: &mut rand_pcg::Lcg64Xsh32,
+) -> HashMap<types::Token, i64> {
+    use rand::RngCore;
+    let mut loot = HashMap::new();
+    let total_weight: u64 = table.entries.iter().map(|e| e.weight as u64).sum();
+    if total_weight == 0 {
+        return loot;
+    }
+    for _ in 0..table.rolls {
+        let mut pick = rng.next_u64() % total_weight;
+        for entry in &table.entries {
+            if pick < entry.weight as u64 {
+                let amount = roll_range(rng, entry.min, entry.max);
+                *loot
+                    .entry(types::Token::persistent(entry.token_type.clone()))
+                    .or_insert(0) += amount;
+                break;
+            }
+            pick -= entry.weight as u64;
+        }
+    }
+    loot
+}
+
+fn combatant(is_player: bool) -> types::Combatant {
+    if is_player {
+        types::Combatant::Player
+    } else {
+        types::Combatant::Enemy
+    }
+}
+
+fn combatant_tokens<'a>(
+    is_player: bool,
+    player_tokens: &'a mut HashMap<types::Token, i64>,
+    combat: &'a mut CombatEncounterState,
+) -> &'a mut HashMap<types::Token, i64> {
+    if is_player {
+        player_tokens
+    } else {
+        &mut combat.enemy_tokens
+    }
+}
+
+/// Evaluate a conditional effect's condition from the card owner's point of view.
+fn condition_holds(
+    condition: &types::EffectCondition,
+    owner_is_player: bool,
+    player_tokens: &HashMap<types::Token, i64>,
+    enemy_tokens: &HashMap<types::Token, i64>,
+) -> bool {
+    let (own, opponent) = if owner_is_player {
+        (player_tokens, enemy_tokens)
+    } else {
+        (enemy_tokens, player_tokens)
+    };
+    match condition {
+        types::EffectCondition::OpponentShieldZero => {
+            types::token_balance_by_type(opponent, &types::TokenType::Shield) <= 0
         }
+        types::EffectCondition::OwnHealthBelowPercent { percent } => {
+            let max_health = types::token_balance_by_type(own, &types::TokenType::MaxHealth);
+            let health = types::token_balance_by_type(own, &types::TokenType::Health);
+            max_health > 0 && health * 100 < max_health * *percent as i64
+        }
+    }
+}
+
+/// Damage pipeline: the defender absorbs the hit (Dodge → Shield → Health), then the
+/// defender's on-hit triggers fire. Reflected damage is absorbed the same way but
+/// fires no further triggers. Returns the Health the defender actually lost.
+fn deal_health_damage(
+    damage: i64,
+    defender_is_player: bool,
+    player_tokens: &mut HashMap<types::Token, i64>,
+    combat: &mut CombatEncounterState,
+) -> i64 {
+    let defender = combatant(defender_is_player);
+    let reflected: i64 = combat
+        .triggers
+        .iter()
+        .filter(|t| t.owner == defender)
+        .map(|t| match t.kind {
+            types::CombatTriggerKind::ReflectOnHit { percent } => damage * percent as i64 / 100,
+        })
+        .sum();
+    let health_lost = absorb_health_damage(
+        combatant_tokens(defender_is_player, player_tokens, combat),
+        damage,
+    );
+    if reflected > 0 {
+        absorb_health_damage(
+            combatant_tokens(!defender_is_player, player_tokens, combat),
+            reflected,
+        );
+    }
+    health_lost
+}
+
+fn absorb_health_damage(target_tokens: &mut HashMap<types::Token, i64>, damage: i64) -> i64 {
+    // Dodge absorbs first (timing-based, expires after Defending phase)
+    let dodge = target_tokens
+        .get(&types::Token::dodge())
+        .copied()
+        .unwrap_or(0);
+    let dodge_absorbed = dodge.min(damage);
+    target_tokens.insert(types::Token::dodge(), (dodge - dodge_absorbed).max(0));
+    let after_dodge = damage - dodge_absorbed;
+    // Shield absorbs next (persists for encounter, blocks 1:1)
+    let shield_key = types::Token::persistent(types::TokenType::Shield);
+    let shield = target_tokens.get(&shield_key).copied().unwrap_or(0);
+    let shield_absorbed = shield.min(after_dodge);
+    target_tokens.insert(shield_key, (shield - shield_absorbed).max(0));
+    let remaining_damage = after_dodge - shield_absorbed;
+    if remaining_damage <= 0 {
+        return 0;
     }
+    let health = target_tokens
+        .entry(types::Token::persistent(types::TokenType::Health))
+        .or_insert(0);
+    let health_lost = remaining_damage.min(*health);
+    *health -= health_lost;
+    health_lost
 }
 
 /// Check if combat has ended (either side at 0 health).
@@ -323,12 +653,63 @@ impl GameState {
             enemy_attack_deck,
             enemy_defence_deck,
             enemy_resource_deck,
+            triggers: Vec::new(),
         };
         self.current_encounter = Some(EncounterState::Combat(snapshot));
         self.encounter_phase = types::EncounterPhase::InEncounter;
         Ok(())
     }
 
+    /// Flee the current combat by paying the enemy's retreat cost.
+    /// The encounter is recorded as PlayerLost and the player moves to Scouting.
+    pub fn retreat_from_combat(&mut self) -> Result<(), String> {
+        let encounter_card_id = match &self.current_encounter {
+            Some(EncounterState::Combat(c)) if c.outcome == EncounterOutcome::Undecided => {
+                c.encounter_card_id
+            }
+            _ => return Err("No active combat to retreat from".to_string()),
+        };
+        let retreat_cost = match self.library.get(encounter_card_id).map(|c| &c.kind) {
+            Some(CardKind::Encounter {
+                encounter_kind: EncounterKind::Combat { combatant_def },
+            }) => combatant_def.retreat_cost.clone(),
+            _ => {
+                return Err(format!(
+                    "Card {} is not a combat encounter",
+                    encounter_card_id
+                ))
+            }
+        };
+        match retreat_cost {
+            types::RetreatCost::Impossible => {
+                return Err("This enemy cannot be fled".to_string());
+            }
+            types::RetreatCost::Stamina { amount } => {
+                let cost = types::TokenAmount {
+                    token_type: types::TokenType::Stamina,
+                    amount,
+                    cap: None,
+                };
+                Self::check_and_deduct_gathering_costs(
+                    std::slice::from_ref(&cost),
+                    &mut self.token_balances,
+                )?;
+            }
+            types::RetreatCost::MaterialFraction { percent } => {
+                for (token, balance) in &mut self.token_balances {
+                    if token.token_type.is_gathering_material() {
+                        *balance -= *balance * percent as i64 / 100;
+                    }
+                }
+            }
+        }
+        self.last_encounter_result = Some(EncounterOutcome::PlayerLost);
+        self.encounter_results.push(EncounterOutcome::PlayerLost);
+        self.current_encounter = None;
+        self.encounter_phase = types::EncounterPhase::Scouting;
+        Ok(())
+    }
+
     /// Resolve a player card play against the current combat encounter.
     pub fn resolve_player_card(
         &mut self,
@@ -364,6 +745,7 @@ impl GameState {
         // A previous effect can grant tokens that a later effect needs.
         // If an effect's cost cannot be paid, it is skipped (partial success).
         let (mut atk_draws, mut def_draws, mut res_draws) = (0u32, 0u32, 0u32);
+        let mut resolved = HashMap::new();
         for effect in &effects {
             // Try to pay cost for this single effect
             if Self::check_and_deduct_costs(std::slice::from_ref(effect), &mut self.token_balances)
@@ -387,6 +769,7 @@ impl GameState {
                 &mut self.token_balances,
                 combat,
                 &self.library,
+                &mut resolved,
             );
         }
         check_combat_end(&self.token_balances, combat);
@@ -400,23 +783,11 @@ impl GameState {
                 combat.outcome = EncounterOutcome::PlayerLost;
             }
         }
-        let outcome = match &self.current_encounter {
-            Some(EncounterState::Combat(c)) => c.outcome.clone(),
-            _ => EncounterOutcome::Undecided,
-        };
-        if outcome != EncounterOutcome::Undecided {
-            if outcome == EncounterOutcome::PlayerWon {
-                let entry = types::token_entry_by_type(
-                    &mut self.token_balances,
-                    &types::TokenType::MilestoneInsight,
-                );
-                *entry += 100;
+        if let Some(EncounterState::Combat(c)) = &self.current_encounter {
+            if c.outcome != EncounterOutcome::Undecided {
+                let (outcome, encounter_card_id) = (c.outcome.clone(), c.encounter_card_id);
+                self.finish_combat(outcome, encounter_card_id, rng);
             }
-            self.last_encounter_result = Some(outcome.clone());
-            self.encounter_results.push(outcome);
-            self.current_encounter = None;
-            self.encounter_phase = types::EncounterPhase::Scouting;
-            self.check_player_death();
         }
         self.draw_player_cards_by_type(atk_draws, def_draws, res_draws, rng);
         Ok(())
@@ -464,29 +835,40 @@ impl GameState {
     }
 
     /// Resolve an enemy card play from hand in the current combat phase.
+    /// The enemy only picks among cards it can pay for from `enemy_tokens`, and each
+    /// effect pays its own `rolled_costs` (unaffordable effects are skipped, like the player's).
     /// Played cards move to discard. DrawCards effects trigger per-type enemy draws.
+    /// If every enemy hand card is unpayable afterwards, the enemy loses.
     pub fn resolve_enemy_play(&mut self, rng: &mut rand_pcg::Lcg64Xsh32) -> Result<(), String> {
-        let combat = match &self.current_encounter {
-            Some(EncounterState::Combat(c)) => c,
-            _ => return Err("No active combat".to_string()),
-        };
-        let phase = combat.phase.clone();
-
         let combat = match &mut self.current_encounter {
             Some(EncounterState::Combat(c)) => c,
             _ => return Err("No active combat".to_string()),
         };
-        let deck = match phase {
+        let enemy_tokens = combat.enemy_tokens.clone();
+        let deck = match combat.phase {
             types::CombatPhase::Attacking => &mut combat.enemy_attack_deck,
             types::CombatPhase::Defending => &mut combat.enemy_defence_deck,
             types::CombatPhase::Resourcing => &mut combat.enemy_resource_deck,
         };
 
-        if let Some(card_idx) = crate::library::game_state::deck_play_random(rng, deck) {
+        if let Some(card_idx) =
+            crate::library::game_state::deck_play_random_matching(rng, deck, |card| {
+                effects_payable(&card.effects, &enemy_tokens)
+            })
+        {
             let effects = deck[card_idx].effects.clone();
 
             let (mut atk_draws, mut def_draws, mut res_draws) = (0u32, 0u32, 0u32);
+            let mut resolved = HashMap::new();
             for effect in &effects {
+                if Self::check_and_deduct_costs(
+                    std::slice::from_ref(effect),
+                    &mut combat.enemy_tokens,
+                )
+                .is_err()
+                {
+                    continue;
+                }
                 if let Some(types::CardEffectKind::DrawCards {
                     attack,
                     defence,
@@ -497,15 +879,15 @@ impl GameState {
                     def_draws += defence;
                     res_draws += resource;
                 }
+                apply_card_effects(
+                    std::slice::from_ref(effect),
+                    false,
+                    &mut self.token_balances,
+                    combat,
+                    &self.library,
+                    &mut resolved,
+                );
             }
-
-            apply_card_effects(
-                &effects,
-                false,
-                &mut self.token_balances,
-                combat,
-                &self.library,
-            );
             check_combat_end(&self.token_balances, combat);
 
             // Handle enemy draws per deck type
@@ -514,25 +896,71 @@ impl GameState {
                 Self::enemy_draw_n(rng, &mut combat.enemy_defence_deck, def_draws);
                 Self::enemy_draw_n(rng, &mut combat.enemy_resource_deck, res_draws);
             }
+        }
 
-            if combat.outcome != EncounterOutcome::Undecided {
-                if combat.outcome == EncounterOutcome::PlayerWon {
-                    let entry = types::token_entry_by_type(
-                        &mut self.token_balances,
-                        &types::TokenType::MilestoneInsight,
-                    );
-                    *entry += 100;
-                }
-                self.last_encounter_result = Some(combat.outcome.clone());
-                self.encounter_results.push(combat.outcome.clone());
-                self.current_encounter = None;
-                self.encounter_phase = types::EncounterPhase::Scouting;
-                self.check_player_death();
-            }
+        // Autoloss: an enemy that cannot pay for any card in hand loses
+        if combat.outcome == EncounterOutcome::Undecided && all_enemy_hand_cards_unpayable(combat) {
+            combat.outcome = EncounterOutcome::PlayerWon;
+        }
+
+        if combat.outcome != EncounterOutcome::Undecided {
+            let (outcome, encounter_card_id) = (combat.outcome.clone(), combat.encounter_card_id);
+            self.finish_combat(outcome, encounter_card_id, rng);
         }
         Ok(())
     }
 
+    /// Record a decided combat and move to Scouting. Victory grants MilestoneInsight,
+    /// the enemy's fixed rewards and its rolled loot; the breakdown is kept for
+    /// `/encounter/results`.
+    fn finish_combat(
+        &mut self,
+        outcome: EncounterOutcome,
+        encounter_card_id: usize,
+        rng: &mut rand_pcg::Lcg64Xsh32,
+    ) {
+        if outcome == EncounterOutcome::PlayerWon {
+            let entry = types::token_entry_by_type(
+                &mut self.token_balances,
+                &types::TokenType::MilestoneInsight,
+            );
+            *entry += 100;
+        }
+        self.last_encounter_result = Some(outcome.clone());
+        self.encounter_results.push(outcome.clone());
+        self.current_encounter = None;
+        self.encounter_phase = types::EncounterPhase::Scouting;
+        if outcome == EncounterOutcome::PlayerWon {
+            self.grant_combat_rewards(encounter_card_id, rng);
+        }
+        self.check_player_death();
+    }
+
+    /// Grant the defeated enemy's fixed rewards plus its loot table rolls, recording the
+    /// breakdown against the latest entry of `encounter_results`.
+    fn grant_combat_rewards(&mut self, encounter_card_id: usize, rng: &mut rand_pcg::Lcg64Xsh32) {
+        let combatant_def = match self.library.get(encounter_card_id).map(|c| &c.kind) {
+            Some(CardKind::Encounter {
+                encounter_kind: EncounterKind::Combat { combatant_def },
+            }) => combatant_def.clone(),
+            _ => return,
+        };
+        let loot = match &combatant_def.loot_table {
+            Some(table) => roll_loot(table, rng),
+            None => HashMap::new(),
+        };
+        let breakdown = types::RewardBreakdown {
+            rewards: combatant_def.rewards,
+            loot,
+        };
+        for (token, amount) in breakdown.rewards.iter().chain(&breakdown.loot) {
+            let entry = self.token_balances.entry(token.clone()).or_insert(0);
+            *entry += amount;
+        }
+        let result_index = self.encounter_results.len().saturating_sub(1);
+        self.encounter_rewards.insert(result_index, breakdown);
+    }
+
     /// Draw `count` random cards from a single enemy deck to hand, recycling discard if needed.
     fn enemy_draw_n(rng: &mut rand_pcg::Lcg64Xsh32, deck: &mut [types::EnemyCardDef], count: u32) {
         for _ in 0..count {
@@ -541,12 +969,17 @@ impl GameState {
     }
 
     /// Advance combat phase to next (Defending → Attacking → Resourcing → Defending).
+    /// Wrapping back to Defending starts a new round and clears round-scoped triggers.
     pub fn advance_combat_phase(&mut self) -> Result<(), String> {
         let combat = match &mut self.current_encounter {
             Some(EncounterState::Combat(c)) => c,
             _ => return Err("No active combat".to_string()),
         };
         combat.phase = combat.phase.next();
+        if combat.phase == types::CombatPhase::Defending {
+            combat.round += 1;
+            combat.triggers.clear();
+        }
         Ok(())
     }
 }
@@ -204,6 +204,7 @@ pub(crate) fn register_crafting_cards(lib: &mut Library, _rng: &mut rand_pcg::Lc
                 crafting_def: types::CraftingDef {
                     initial_crafting_tokens: 10,
                     enemy_crafting_deck: vec![enemy_ore, enemy_plant, enemy_lumber, enemy_fish],
+                    entry_cost: vec![],
                 },
             },
         },
@@ -327,8 +328,11 @@ impl GameState {
         Ok(())
     }
 
-    /// Add durability to a discipline. Costs 1 crafting token + wood or ore.
-    pub fn resolve_crafting_add_durability(&mut self, discipline: &str) -> Result<(), String> {
+    /// Add durability to a discipline, up to its maximum. Costs 1 crafting token + wood or ore.
+    pub fn resolve_crafting_add_durability(
+        &mut self,
+        discipline: &types::Discipline,
+    ) -> Result<(), String> {
         let crafting = match &self.current_encounter {
             Some(EncounterState::Crafting(c)) if c.outcome == EncounterOutcome::Undecided => c,
             _ => return Err("No active crafting encounter".to_string()),
@@ -338,34 +342,12 @@ impl GameState {
             return Err("Not enough crafting tokens".to_string());
         }
 
-        let (durability_token, cost_token, cost_amount) = match discipline {
-            "Mining" => (
-                types::TokenType::MiningDurability,
-                types::TokenType::Ore,
-                50,
-            ),
-            "Herbalism" => (
-                types::TokenType::HerbalismDurability,
-                types::TokenType::Lumber,
-                50,
-            ),
-            "Woodcutting" => (
-                types::TokenType::WoodcuttingDurability,
-                types::TokenType::Lumber,
-                50,
-            ),
-            "Fishing" => (
-                types::TokenType::FishingDurability,
-                types::TokenType::Ore,
-                50,
-            ),
-            _ => {
-                return Err(format!(
-                    "Unknown discipline '{}'. Valid: Mining, Herbalism, Woodcutting, Fishing",
-                    discipline
-                ))
-            }
-        };
+        let durability_token = discipline.durability_token();
+        let cost_token = discipline.repair_material();
+        let cost_amount = 50;
+        if self.repair_cost(discipline) == 0 {
+            return Err(format!("{:?} is already at its maximum", durability_token));
+        }
 
         // Check and deduct material cost
         let cost_key = types::Token::persistent(cost_token);
@@ -379,8 +361,9 @@ impl GameState {
         *self.token_balances.entry(cost_key).or_insert(0) -= cost_amount;
 
         // Grant durability
-        let dur_key = types::Token::persistent(durability_token);
+        let dur_key = types::Token::persistent(durability_token.clone());
         *self.token_balances.entry(dur_key).or_insert(0) += 500;
+        types::clamp_to_max(&mut self.token_balances, &durability_token);
 
         // Deduct crafting token
         if let Some(EncounterState::Crafting(c)) = &mut self.current_encounter {
@@ -725,5 +708,6 @@ fn is_player_card(kind: &CardKind) -> bool {
             | CardKind::Fishing { .. }
             | CardKind::Rest { .. }
             | CardKind::Crafting { .. }
+            | CardKind::Tool { .. }
     )
 }
@@ -16,6 +16,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -37,6 +38,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -58,6 +60,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -115,6 +118,30 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                         types::Token::persistent(types::TokenType::Fish),
                         1000,
                     )]),
+                    species: vec![
+                        types::FishSpecies {
+                            name: "Pike".to_string(),
+                            behaviors: vec![types::FishBehavior::Greedy { min_fish_amount: 2 }],
+                            rewards: HashMap::from([(
+                                types::Token::persistent(types::TokenType::Fish),
+                                300,
+                            )]),
+                        },
+                        types::FishSpecies {
+                            name: "Trout".to_string(),
+                            behaviors: vec![types::FishBehavior::Skittish { lost_turns: 3 }],
+                            rewards: HashMap::from([(
+                                types::Token::persistent(types::TokenType::Fish),
+                                500,
+                            )]),
+                        },
+                        types::FishSpecies {
+                            name: "Carp".to_string(),
+                            behaviors: vec![types::FishBehavior::Telegraphed],
+                            rewards: HashMap::new(),
+                        },
+                    ],
+                    entry_cost: vec![],
                 },
             },
         },
@@ -141,6 +168,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     amount: -150,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -166,6 +194,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     amount: 150,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -205,6 +234,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                         cap: None,
                     },
                 ],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -230,6 +260,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     amount: 1,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -262,6 +293,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     amount: -1,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -287,6 +319,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     amount: 200,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -315,6 +348,7 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
                     },
                 ],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -326,6 +360,66 @@ pub(crate) fn register_fishing_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg
     );
 }
 
+/// Apply one fishing gain: encounter-scoped tokens go to encounter state, others to player balances.
+fn apply_fishing_gain(
+    gain: &types::TokenAmount,
+    encounter: &mut Option<EncounterState>,
+    token_balances: &mut HashMap<types::Token, i64>,
+) {
+    match gain.token_type {
+        types::TokenType::FishingRangeMin
+        | types::TokenType::FishingRangeMax
+        | types::TokenType::FishAmount => {
+            if let Some(EncounterState::Fishing(f)) = encounter {
+                let key = types::Token::persistent(gain.token_type.clone());
+                let entry = f.encounter_tokens.entry(key).or_insert(0);
+                *entry += gain.amount;
+            }
+        }
+        _ => {
+            let entry = types::token_entry_by_type(token_balances, &gain.token_type);
+            *entry += gain.amount;
+            types::clamp_to_max(token_balances, &gain.token_type);
+        }
+    }
+}
+
+/// Pick the fish card a fish will play next without moving it, refilling the hand
+/// from discard first when it is empty. A greedy pick takes the highest hand value.
+fn fish_pick(
+    rng: &mut rand_pcg::Lcg64Xsh32,
+    fish_deck: &mut [types::FishCard],
+    greedy: bool,
+) -> Option<usize> {
+    use rand::RngCore;
+    if fish_deck.iter().all(|c| c.counts.hand == 0) {
+        for card in fish_deck.iter_mut() {
+            card.counts.hand += card.counts.discard;
+            card.counts.discard = 0;
+        }
+    }
+    if greedy {
+        return fish_deck
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.counts.hand > 0)
+            .max_by_key(|&(idx, c)| (c.value, std::cmp::Reverse(idx)))
+            .map(|(idx, _)| idx);
+    }
+    let total_hand: u32 = fish_deck.iter().map(|c| c.counts.hand).sum();
+    if total_hand == 0 {
+        return None;
+    }
+    let mut pick = (rng.next_u64() as u32) % total_hand;
+    for (idx, card) in fish_deck.iter().enumerate() {
+        if pick < card.counts.hand {
+            return Some(idx);
+        }
+        pick -= card.counts.hand;
+    }
+    None
+}
+
 impl GameState {
     /// Initialize a fishing gathering encounter from a Library Encounter card.
     pub fn start_fishing_encounter(
@@ -351,6 +445,17 @@ impl GameState {
         };
         let mut fish_deck = fishing_def.fish_deck;
         crate::library::game_state::deck_shuffle_hand(rng, &mut fish_deck);
+        let species = if fishing_def.species.is_empty() {
+            None
+        } else {
+            use rand::Rng;
+            let idx = rng.gen_range(0..fishing_def.species.len());
+            Some(fishing_def.species[idx].clone())
+        };
+        let mut rewards = fishing_def.rewards;
+        for (token, amount) in species.iter().flat_map(|s| &s.rewards) {
+            *rewards.entry(token.clone()).or_insert(0) += amount;
+        }
         // Initialize encounter-scoped tokens
         let mut encounter_tokens = std::collections::HashMap::new();
         encounter_tokens.insert(
@@ -372,11 +477,78 @@ impl GameState {
             valid_range_min: fishing_def.valid_range_min,
             valid_range_max: fishing_def.valid_range_max,
             fish_deck,
-            rewards: fishing_def.rewards,
+            rewards,
             encounter_tokens,
+            species,
+            turns_lost: 0,
+            revealed_fish: None,
+            bait: None,
         };
         self.current_encounter = Some(EncounterState::Fishing(state));
         self.encounter_phase = types::EncounterPhase::InEncounter;
+        self.reveal_next_fish(rng);
+        Ok(())
+    }
+
+    /// Spend Plant on bait before the first cast: `Chum` moves the highest fish cards
+    /// from the fish hand to discard, `Glowworm` widens the valid range.
+    pub fn resolve_fishing_bait(
+        &mut self,
+        bait: &types::FishingBait,
+        rng: &mut rand_pcg::Lcg64Xsh32,
+    ) -> Result<(), String> {
+        match &self.current_encounter {
+            Some(EncounterState::Fishing(f)) if f.bait.is_some() => {
+                return Err("Bait has already been used in this encounter".to_string())
+            }
+            Some(EncounterState::Fishing(f)) if f.round != 1 => {
+                return Err("Bait can only be used before the first cast".to_string())
+            }
+            Some(EncounterState::Fishing(_)) => {}
+            _ => return Err("No active fishing encounter".to_string()),
+        }
+        Self::check_and_deduct_gathering_costs(&[bait.cost()], &mut self.token_balances)?;
+
+        let fishing = match &mut self.current_encounter {
+            Some(EncounterState::Fishing(f)) => f,
+            _ => return Err("No active fishing encounter".to_string()),
+        };
+        match bait {
+            types::FishingBait::Chum => {
+                for _ in 0..types::FishingBait::CHUM_DISCARDS {
+                    let highest = fishing
+                        .fish_deck
+                        .iter_mut()
+                        .filter(|c| c.counts.hand > 0)
+                        .max_by_key(|c| c.value);
+                    match highest {
+                        Some(card) => {
+                            card.counts.hand -= 1;
+                            card.counts.discard += 1;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            types::FishingBait::Glowworm => {
+                let min_key = types::Token::persistent(types::TokenType::FishingRangeMin);
+                let max_key = types::Token::persistent(types::TokenType::FishingRangeMax);
+                let min = fishing.encounter_tokens.entry(min_key).or_insert(0);
+                *min = (*min - types::FishingBait::GLOWWORM_WIDEN).max(0);
+                fishing.valid_range_min = *min;
+                let max = fishing.encounter_tokens.entry(max_key).or_insert(0);
+                *max += types::FishingBait::GLOWWORM_WIDEN;
+                fishing.valid_range_max = *max;
+            }
+        }
+        fishing.bait = Some(bait.clone());
+        // Chum may have discarded the revealed card
+        let revealed_gone = fishing
+            .revealed_fish
+            .is_some_and(|idx| fishing.fish_deck[idx].counts.hand == 0);
+        if revealed_gone {
+            self.reveal_next_fish(rng);
+        }
         Ok(())
     }
 
@@ -391,7 +563,7 @@ impl GameState {
             .get(card_id)
             .ok_or_else(|| format!("Card {} not found in Library", card_id))?
             .clone();
-        let fishing_effect = match &lib_card.kind {
+        let mut fishing_effect = match &lib_card.kind {
             CardKind::Fishing { fishing_effect } => fishing_effect.clone(),
             _ => return Err("Cannot play a non-fishing card in fishing encounter".to_string()),
         };
@@ -418,24 +590,19 @@ impl GameState {
             return Ok(());
         }
 
-        // Apply gains: encounter-scoped tokens go to encounter state, others to player balances
+        // An equipped fishing tool adds its modifiers to the card's gains
+        fishing_effect
+            .gains
+            .extend(self.use_tool(&types::Discipline::Fishing));
+
+        // Apply gains, then drains of the resolved gains
+        let mut resolved = HashMap::new();
         for gain in &fishing_effect.gains {
-            match gain.token_type {
-                types::TokenType::FishingRangeMin
-                | types::TokenType::FishingRangeMax
-                | types::TokenType::FishAmount => {
-                    if let Some(EncounterState::Fishing(f)) = &mut self.current_encounter {
-                        let key = types::Token::persistent(gain.token_type.clone());
-                        let entry = f.encounter_tokens.entry(key).or_insert(0);
-                        *entry += gain.amount;
-                    }
-                }
-                _ => {
-                    let entry =
-                        types::token_entry_by_type(&mut self.token_balances, &gain.token_type);
-                    *entry += gain.amount;
-                }
-            }
+            apply_fishing_gain(gain, &mut self.current_encounter, &mut self.token_balances);
+            *resolved.entry(gain.token_type.clone()).or_insert(0) += gain.amount;
+        }
+        for gain in &types::drain_gains(&fishing_effect.drains, &resolved) {
+            apply_fishing_gain(gain, &mut self.current_encounter, &mut self.token_balances);
         }
 
         // If card has no values, skip the fishing duel (utility-only card)
@@ -485,8 +652,8 @@ impl GameState {
             _ => return Err("No active fishing encounter".to_string()),
         };
 
-        // Auto-resolve fish play: pick random fish card from hand
-        let fish_value = Self::fish_play_random(rng, &mut self.current_encounter);
+        // Auto-resolve fish play: the revealed card, else the species' pick from hand
+        let fish_value = Self::fish_play(rng, &mut self.current_encounter, fish_amount);
 
         // Choose the best player value (the one that wins if possible)
         let best_value = fishing_effect
@@ -512,13 +679,15 @@ impl GameState {
         let turn_won = result >= valid_min && result <= valid_max;
 
         // Update encounter state
-        let (all_turns_used, enough_wins) = {
+        let (all_turns_used, enough_wins, escaped) = {
             let fishing = match &mut self.current_encounter {
                 Some(EncounterState::Fishing(f)) => f,
                 _ => return Err("No active fishing encounter".to_string()),
             };
             if turn_won {
                 fishing.turns_won += fish_amount as u32;
+            } else {
+                fishing.turns_lost += 1;
             }
             fishing.round += 1;
             // Sync range fields from tokens for display
@@ -526,14 +695,20 @@ impl GameState {
             fishing.valid_range_max = valid_max;
             let enough_wins = fishing.turns_won >= win_turns_needed;
             let all_turns_used = (fishing.round - 1) as u32 >= fishing.max_turns;
-            (all_turns_used, enough_wins)
+            let escaped = fishing
+                .species
+                .as_ref()
+                .and_then(|s| s.escape_after())
+                .is_some_and(|lost_turns| fishing.turns_lost >= lost_turns);
+            (all_turns_used, enough_wins, escaped)
         };
 
         if enough_wins {
             self.finish_fishing_encounter(true);
-        } else if all_turns_used {
+        } else if all_turns_used || escaped {
             self.finish_fishing_encounter(false);
         } else {
+            self.reveal_next_fish(rng);
             self.draw_player_fishing_card(rng);
 
             // Check autoloss: if all fishing hand cards are unpayable, player loses
@@ -553,26 +728,65 @@ impl GameState {
         })
     }
 
-    fn fish_play_random(
+    /// Play the fish card for this turn and return its value. A revealed card is played
+    /// as promised; a greedy species with enough `FishAmount` plays its highest card;
+    /// otherwise a random hand card is played.
+    fn fish_play(
         rng: &mut rand_pcg::Lcg64Xsh32,
         encounter: &mut Option<EncounterState>,
+        fish_amount: i64,
     ) -> i64 {
-        let fish_deck = match encounter {
-            Some(EncounterState::Fishing(f)) => &mut f.fish_deck,
+        let fishing = match encounter {
+            Some(EncounterState::Fishing(f)) => f,
             _ => return 0,
         };
-        match crate::library::game_state::deck_play_random(rng, fish_deck) {
+        let greedy = fishing
+            .species
+            .as_ref()
+            .and_then(|s| s.greedy_threshold())
+            .is_some_and(|threshold| fish_amount >= threshold);
+        let picked = match fishing.revealed_fish.take() {
+            Some(idx) => Some(idx),
+            None if greedy => fish_pick(rng, &mut fishing.fish_deck, true),
+            None => {
+                return crate::library::game_state::deck_play_random(rng, &mut fishing.fish_deck)
+                    .map(|idx| fishing.fish_deck[idx].value)
+                    .unwrap_or(0)
+            }
+        };
+        match picked {
             Some(idx) => {
-                let fish_deck = match encounter {
-                    Some(EncounterState::Fishing(f)) => &f.fish_deck,
-                    _ => return 0,
-                };
-                fish_deck[idx].value
+                let card = &mut fishing.fish_deck[idx];
+                card.counts.hand -= 1;
+                card.counts.discard += 1;
+                card.value
             }
             None => 0,
         }
     }
 
+    /// Let a telegraphed species pick and reveal the fish card it will play next.
+    fn reveal_next_fish(&mut self, rng: &mut rand_pcg::Lcg64Xsh32) {
+        let fishing = match &mut self.current_encounter {
+            Some(EncounterState::Fishing(f)) => f,
+            _ => return,
+        };
+        let species = match &fishing.species {
+            Some(species) if species.is_telegraphed() => species,
+            _ => return,
+        };
+        let fish_amount = fishing
+            .encounter_tokens
+            .get(&types::Token::persistent(types::TokenType::FishAmount))
+            .copied()
+            .unwrap_or(1)
+            .max(1);
+        let greedy = species
+            .greedy_threshold()
+            .is_some_and(|threshold| fish_amount >= threshold);
+        fishing.revealed_fish = fish_pick(rng, &mut fishing.fish_deck, greedy);
+    }
+
     /// Conclude a fishing encounter voluntarily: grant rewards if any accumulated.
     pub fn conclude_fishing_encounter(&mut self) -> Result<(), String> {
         match &self.current_encounter {
@@ -19,6 +19,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     types: vec![types::PlantCharacteristic::Fragile],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -45,6 +46,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     ],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -72,6 +74,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     ],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -95,6 +98,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                                 hand: 1,
                                 discard: 0,
                             },
+                            potency: 80,
                         },
                         types::PlantCard {
                             characteristics: vec![
@@ -106,6 +110,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                                 hand: 1,
                                 discard: 0,
                             },
+                            potency: 120,
                         },
                         types::PlantCard {
                             characteristics: vec![
@@ -117,6 +122,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                                 hand: 1,
                                 discard: 0,
                             },
+                            potency: 150,
                         },
                         types::PlantCard {
                             characteristics: vec![
@@ -128,6 +134,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                                 hand: 1,
                                 discard: 0,
                             },
+                            potency: 100,
                         },
                         types::PlantCard {
                             characteristics: vec![types::PlantCharacteristic::Luminous],
@@ -136,12 +143,15 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                                 hand: 1,
                                 discard: 0,
                             },
+                            potency: 90,
                         },
                     ],
                     rewards: HashMap::from([(
                         types::Token::persistent(types::TokenType::Plant),
                         500,
                     )]),
+                    consolation_percent: 20,
+                    entry_cost: vec![],
                 },
             },
         },
@@ -174,6 +184,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     types: vec![],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -205,6 +216,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     types: vec![],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -238,6 +250,7 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     ],
                 },
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -263,6 +276,35 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
                     amount: 200,
                     cap: None,
                 }],
+                drains: vec![],
+            },
+        },
+        CardCounts {
+            library: 0,
+            deck: 3,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    // Gentle hands card: narrow match, guards half the plants of the next play
+    lib.add_card(
+        CardKind::Herbalism {
+            herbalism_effect: types::HerbalismCardEffect {
+                costs: vec![types::TokenAmount {
+                    token_type: types::TokenType::HerbalismDurability,
+                    amount: 100,
+                    cap: None,
+                }],
+                match_mode: types::HerbalismMatchMode::Or {
+                    types: vec![types::PlantCharacteristic::Luminous],
+                },
+                gains: vec![types::TokenAmount {
+                    token_type: types::TokenType::HerbalismGuard,
+                    amount: 1,
+                    cap: None,
+                }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -274,6 +316,34 @@ pub(crate) fn register_herbalism_cards(lib: &mut Library, _rng: &mut rand_pcg::L
     );
 }
 
+/// Rewards for harvesting the plants still in hand: Plant scales with their average potency
+/// (weighted by copies), other reward tokens are paid as is.
+fn herbalism_reward(herbalism: &types::HerbalismEncounterState) -> HashMap<types::Token, i64> {
+    let (copies, total_potency) =
+        herbalism
+            .plant_hand
+            .iter()
+            .fold((0u64, 0u64), |(copies, potency), plant| {
+                let hand = u64::from(plant.counts.hand);
+                (copies + hand, potency + hand * u64::from(plant.potency))
+            });
+    let potency = match copies {
+        0 => 100,
+        _ => (total_potency / copies) as i64,
+    };
+    herbalism
+        .rewards
+        .iter()
+        .map(|(token, amount)| {
+            let scaled = match token.token_type {
+                types::TokenType::Plant => amount * potency / 100,
+                _ => *amount,
+            };
+            (token.clone(), scaled)
+        })
+        .collect()
+}
+
 impl GameState {
     /// Initialize an herbalism gathering encounter from a Library Encounter card.
     pub fn start_herbalism_encounter(
@@ -299,13 +369,17 @@ impl GameState {
         };
         let mut plant_hand = herbalism_def.plant_hand;
         crate::library::game_state::deck_shuffle_hand(rng, &mut plant_hand);
-        let state = HerbalismEncounterState {
+        let mut state = HerbalismEncounterState {
             round: 1,
             encounter_card_id,
             outcome: EncounterOutcome::Undecided,
             plant_hand,
             rewards: herbalism_def.rewards,
+            consolation_percent: herbalism_def.consolation_percent,
+            guarded_plants: Vec::new(),
+            projected_reward: HashMap::new(),
         };
+        state.projected_reward = herbalism_reward(&state);
         self.current_encounter = Some(EncounterState::Herbalism(state));
         self.encounter_phase = types::EncounterPhase::InEncounter;
         Ok(())
@@ -324,7 +398,7 @@ impl GameState {
             .get(card_id)
             .ok_or_else(|| format!("Card {} not found in Library", card_id))?
             .clone();
-        let herbalism_effect = match &lib_card.kind {
+        let mut herbalism_effect = match &lib_card.kind {
             CardKind::Herbalism { herbalism_effect } => herbalism_effect.clone(),
             _ => return Err("Cannot play a non-herbalism card in herbalism encounter".to_string()),
         };
@@ -351,11 +425,21 @@ impl GameState {
             return Ok(());
         }
 
-        // Apply gains
-        for gain in &herbalism_effect.gains {
-            let entry = types::token_entry_by_type(&mut self.token_balances, &gain.token_type);
-            *entry += gain.amount;
-        }
+        // A guard granted by an earlier play protects this one; consume it first so this
+        // card's own gains can grant a guard for the next play
+        let guarded = self.consume_herbalism_guard();
+
+        // An equipped herbalism tool adds its modifiers to the card's gains
+        herbalism_effect
+            .gains
+            .extend(self.use_tool(&types::Discipline::Herbalism));
+
+        // Apply gains and their drains
+        Self::apply_gathering_gains(
+            &herbalism_effect.gains,
+            &herbalism_effect.drains,
+            &mut self.token_balances,
+        );
 
         // Remove plant cards based on match mode
         {
@@ -364,79 +448,61 @@ impl GameState {
                 _ => return Err("No active herbalism encounter".to_string()),
             };
 
-            match &herbalism_effect.match_mode {
+            // (target characteristics, whether a plant must have all of them)
+            let (targets, match_all) = match &herbalism_effect.match_mode {
                 types::HerbalismMatchMode::Or {
                     types: target_types,
-                } => {
-                    for plant_card in &mut herbalism.plant_hand {
-                        if plant_card.counts.hand == 0 {
-                            continue;
-                        }
-                        let shares_characteristic = plant_card
-                            .characteristics
-                            .iter()
-                            .any(|c| target_types.contains(c));
-                        if shares_characteristic {
-                            plant_card.counts.hand = 0;
-                        }
-                    }
-                }
+                } => (target_types.clone(), false),
                 types::HerbalismMatchMode::And {
                     types: target_types,
-                } => {
-                    for plant_card in &mut herbalism.plant_hand {
-                        if plant_card.counts.hand == 0 {
-                            continue;
-                        }
-                        let has_all = target_types
-                            .iter()
-                            .all(|c| plant_card.characteristics.contains(c));
-                        if has_all {
-                            plant_card.counts.hand = 0;
-                        }
-                    }
-                }
-                types::HerbalismMatchMode::MostCommon { limit, .. } => {
-                    let targets = Self::herbalism_most_common_characteristics(
+                } => (target_types.clone(), true),
+                types::HerbalismMatchMode::MostCommon { limit, .. } => (
+                    Self::herbalism_most_common_characteristics(&herbalism.plant_hand, rng, *limit),
+                    false,
+                ),
+                types::HerbalismMatchMode::LeastCommon { limit, .. } => (
+                    Self::herbalism_least_common_characteristics(
                         &herbalism.plant_hand,
                         rng,
                         *limit,
-                    );
-                    for plant_card in &mut herbalism.plant_hand {
-                        if plant_card.counts.hand == 0 {
-                            continue;
-                        }
-                        let shares = plant_card
-                            .characteristics
+                    ),
+                    false,
+                ),
+            };
+            let mut removed: Vec<usize> = herbalism
+                .plant_hand
+                .iter()
+                .enumerate()
+                .filter(|(_, plant_card)| plant_card.counts.hand > 0)
+                .filter(|(_, plant_card)| {
+                    if match_all {
+                        targets
                             .iter()
-                            .any(|c| targets.contains(c));
-                        if shares {
-                            plant_card.counts.hand = 0;
-                        }
-                    }
-                }
-                types::HerbalismMatchMode::LeastCommon { limit, .. } => {
-                    let targets = Self::herbalism_least_common_characteristics(
-                        &herbalism.plant_hand,
-                        rng,
-                        *limit,
-                    );
-                    for plant_card in &mut herbalism.plant_hand {
-                        if plant_card.counts.hand == 0 {
-                            continue;
-                        }
-                        let shares = plant_card
+                            .all(|c| plant_card.characteristics.contains(c))
+                    } else {
+                        plant_card
                             .characteristics
                             .iter()
-                            .any(|c| targets.contains(c));
-                        if shares {
-                            plant_card.counts.hand = 0;
-                        }
+                            .any(|c| targets.contains(c))
                     }
-                }
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+
+            herbalism.guarded_plants = Vec::new();
+            if guarded {
+                use rand::seq::SliceRandom;
+                removed.shuffle(rng);
+                let spared = removed.len() / 2;
+                herbalism.guarded_plants = removed.drain(..spared).collect();
+                herbalism.guarded_plants.sort_unstable();
+            }
+            for idx in removed {
+                herbalism.plant_hand[idx].counts.hand = 0;
             }
 
             herbalism.round += 1;
+            herbalism.projected_reward = herbalism_reward(herbalism);
         }
 
         // Check win/loss based on remaining plant cards
@@ -450,6 +516,7 @@ impl GameState {
         if remaining == 1 {
             self.finish_herbalism_encounter(true);
         } else if remaining == 0 {
+            self.grant_herbalism_consolation();
             self.finish_herbalism_encounter(false);
         } else {
             // Draw 1 herbalism card for player
@@ -464,6 +531,20 @@ impl GameState {
         Ok(())
     }
 
+    /// Use up one HerbalismGuard token if the player holds one.
+    fn consume_herbalism_guard(&mut self) -> bool {
+        let guard = self.token_balances.iter_mut().find(|(token, amount)| {
+            token.token_type == types::TokenType::HerbalismGuard && **amount > 0
+        });
+        match guard {
+            Some((_, amount)) => {
+                *amount -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Check if all herbalism hand cards are unpayable (pre-play costs unaffordable).
     fn all_herbalism_hand_cards_unpayable(&self) -> bool {
         self.all_gathering_hand_cards_unpayable(|k| match k {
@@ -536,10 +617,30 @@ impl GameState {
         Ok(())
     }
 
+    /// Over-harvest: pay `consolation_percent`% of the base rewards.
+    fn grant_herbalism_consolation(&mut self) {
+        let consolation: Vec<(types::Token, i64)> = match &self.current_encounter {
+            Some(EncounterState::Herbalism(h)) => h
+                .rewards
+                .iter()
+                .map(|(token, amount)| {
+                    (
+                        token.clone(),
+                        amount * i64::from(h.consolation_percent) / 100,
+                    )
+                })
+                .collect(),
+            _ => return,
+        };
+        for (token, amount) in consolation {
+            *self.token_balances.entry(token).or_insert(0) += amount;
+        }
+    }
+
     fn finish_herbalism_encounter(&mut self, is_win: bool) {
         if is_win {
             let rewards = match &self.current_encounter {
-                Some(EncounterState::Herbalism(h)) => h.rewards.clone(),
+                Some(EncounterState::Herbalism(h)) => herbalism_reward(h),
                 _ => return,
             };
             for (token, amount) in &rewards {
@@ -552,6 +653,12 @@ impl GameState {
         } else {
             EncounterOutcome::PlayerLost
         };
+        // Guards only last for the encounter they were granted in
+        for (token, amount) in self.token_balances.iter_mut() {
+            if token.token_type == types::TokenType::HerbalismGuard {
+                *amount = 0;
+            }
+        }
         self.last_encounter_result = Some(outcome.clone());
         self.encounter_results.push(outcome);
         self.current_encounter = None;
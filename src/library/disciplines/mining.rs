@@ -3,6 +3,7 @@ use crate::library::types::{
     MiningEncounterState,
 };
 use crate::library::{GameState, Library};
+use std::collections::HashMap;
 
 pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg64Xsh32) {
     // Mining power card: high power, no cost
@@ -15,6 +16,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 500,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -35,6 +38,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 300,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -59,6 +64,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 200,
                     cap: Some(500),
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -87,6 +94,7 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                                 hand: 6,
                                 discard: 0,
                             },
+                            hazard: None,
                         },
                         types::OreCard {
                             damages: vec![
@@ -106,6 +114,7 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                                 hand: 8,
                                 discard: 0,
                             },
+                            hazard: None,
                         },
                         types::OreCard {
                             damages: vec![types::TokenAmount {
@@ -118,6 +127,7 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                                 hand: 4,
                                 discard: 0,
                             },
+                            hazard: None,
                         },
                         types::OreCard {
                             damages: vec![types::TokenAmount {
@@ -130,8 +140,10 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                                 hand: 2,
                                 discard: 0,
                             },
+                            hazard: None,
                         },
                     ],
+                    entry_cost: vec![],
                 },
             },
         },
@@ -157,6 +169,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 800,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -181,6 +195,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 600,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -205,6 +221,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 1000,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -229,6 +247,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 350,
                     cap: Some(600),
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -249,6 +269,8 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
                     amount: 200,
                     cap: None,
                 }],
+                drains: vec![],
+                ventilates: false,
             },
         },
         CardCounts {
@@ -258,6 +280,137 @@ pub(crate) fn register_mining_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg6
             discard: 0,
         },
     );
+
+    // Ventilation card: weak power, clears gas
+    lib.add_card(
+        CardKind::Mining {
+            mining_effect: types::MiningCardEffect {
+                costs: vec![],
+                gains: vec![types::TokenAmount {
+                    token_type: types::TokenType::MiningPower,
+                    amount: 100,
+                    cap: None,
+                }],
+                drains: vec![],
+                ventilates: true,
+            },
+        },
+        CardCounts {
+            library: 0,
+            deck: 3,
+            hand: 0,
+            discard: 0,
+        },
+    );
+
+    // Mining encounter: Deep Shaft, with cave-ins, gas pockets and gem veins
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: types::EncounterKind::Mining {
+                mining_def: types::MiningDef {
+                    initial_light_level: 250,
+                    ore_deck: vec![
+                        types::OreCard {
+                            damages: vec![types::TokenAmount {
+                                token_type: types::TokenType::MiningLightLevel,
+                                amount: 40,
+                                cap: None,
+                            }],
+                            counts: types::DeckCounts {
+                                deck: 0,
+                                hand: 8,
+                                discard: 0,
+                            },
+                            hazard: None,
+                        },
+                        types::OreCard {
+                            damages: vec![types::TokenAmount {
+                                token_type: types::TokenType::MiningDurability,
+                                amount: 150,
+                                cap: None,
+                            }],
+                            counts: types::DeckCounts {
+                                deck: 0,
+                                hand: 1,
+                                discard: 0,
+                            },
+                            hazard: Some(types::OreHazard::CaveIn { yield_percent: 50 }),
+                        },
+                        types::OreCard {
+                            damages: vec![],
+                            counts: types::DeckCounts {
+                                deck: 0,
+                                hand: 3,
+                                discard: 0,
+                            },
+                            hazard: Some(types::OreHazard::Gas {
+                                stamina_per_turn: 30,
+                            }),
+                        },
+                        types::OreCard {
+                            damages: vec![],
+                            counts: types::DeckCounts {
+                                deck: 0,
+                                hand: 2,
+                                discard: 0,
+                            },
+                            hazard: Some(types::OreHazard::GemVein {
+                                token_type: types::TokenType::Insight,
+                                amount: 25,
+                                turns: 2,
+                            }),
+                        },
+                    ],
+                    entry_cost: vec![],
+                },
+            },
+        },
+        CardCounts {
+            library: 1,
+            deck: 2,
+            hand: 0,
+            discard: 0,
+        },
+    );
+}
+
+/// Apply one mining gain and return the token type and amount it resolved to:
+/// MiningPower→yield (scaled by light level), MiningLightLevel→light with cap,
+/// anything else straight to player balances.
+fn apply_mining_gain(
+    gain: &types::TokenAmount,
+    encounter_tokens: &mut HashMap<types::Token, i64>,
+    token_balances: &mut HashMap<types::Token, i64>,
+) -> (types::TokenType, i64) {
+    match gain.token_type {
+        types::TokenType::MiningPower => {
+            // yield += mining_power × light_level / 100
+            let light_key = types::Token::persistent(types::TokenType::MiningLightLevel);
+            let light_level = encounter_tokens.get(&light_key).copied().unwrap_or(0);
+            let yield_increase = gain.amount * light_level / 100;
+            let yield_key = types::Token::persistent(types::TokenType::MiningYield);
+            let yield_val = encounter_tokens.entry(yield_key).or_insert(0);
+            *yield_val += yield_increase;
+            (types::TokenType::MiningYield, yield_increase)
+        }
+        types::TokenType::MiningLightLevel => {
+            let light_key = types::Token::persistent(types::TokenType::MiningLightLevel);
+            let light_val = encounter_tokens.entry(light_key).or_insert(0);
+            let light_gain = match gain.cap {
+                Some(cap) => (cap - *light_val).max(0).min(gain.amount),
+                None => gain.amount,
+            };
+            *light_val += light_gain;
+            (types::TokenType::MiningLightLevel, light_gain)
+        }
+        _ => {
+            // Direct token addition to player balances (e.g., Stamina)
+            let entry = types::token_entry_by_type(token_balances, &gain.token_type);
+            *entry += gain.amount;
+            types::clamp_to_max(token_balances, &gain.token_type);
+            (gain.token_type.clone(), gain.amount)
+        }
+    }
 }
 
 impl GameState {
@@ -300,6 +453,8 @@ impl GameState {
             outcome: EncounterOutcome::Undecided,
             ore_deck,
             encounter_tokens,
+            gas_per_turn: 0,
+            gem_vein: None,
         };
         self.current_encounter = Some(EncounterState::Mining(state));
         self.encounter_phase = types::EncounterPhase::InEncounter;
@@ -308,7 +463,7 @@ impl GameState {
 
     /// Resolve a player mining card play against the current mining encounter.
     /// Processes token-based gains (MiningPower→yield, MiningLightLevel→light with cap),
-    /// auto-resolves ore play, draws cards, and checks encounter end.
+    /// then drains of the resolved gains, auto-resolves ore play, draws cards, and checks encounter end.
     pub fn resolve_player_mining_card(
         &mut self,
         card_id: usize,
@@ -319,7 +474,7 @@ impl GameState {
             .get(card_id)
             .ok_or_else(|| format!("Card {} not found in Library", card_id))?
             .clone();
-        let mining_effect = match &lib_card.kind {
+        let mut mining_effect = match &lib_card.kind {
             CardKind::Mining { mining_effect } => mining_effect.clone(),
             _ => return Err("Cannot play a non-mining card in mining encounter".to_string()),
         };
@@ -327,38 +482,28 @@ impl GameState {
         // Check and deduct pre-play costs (stamina, lumber etc.)
         Self::check_and_deduct_gathering_costs(&mining_effect.costs, &mut self.token_balances)?;
 
+        // An equipped mining tool adds its modifiers to the card's gains
+        mining_effect
+            .gains
+            .extend(self.use_tool(&types::Discipline::Mining));
+
         // Process gains using encounter tokens for encounter-scoped values
         let encounter_tokens = match &mut self.current_encounter {
             Some(EncounterState::Mining(m)) => &mut m.encounter_tokens,
             _ => return Err("No active mining encounter".to_string()),
         };
+        let mut resolved = HashMap::new();
         for gain in &mining_effect.gains {
-            match gain.token_type {
-                types::TokenType::MiningPower => {
-                    // yield += mining_power × light_level / 100
-                    let light_key = types::Token::persistent(types::TokenType::MiningLightLevel);
-                    let light_level = encounter_tokens.get(&light_key).copied().unwrap_or(0);
-                    let yield_increase = gain.amount * light_level / 100;
-                    let yield_key = types::Token::persistent(types::TokenType::MiningYield);
-                    let yield_val = encounter_tokens.entry(yield_key).or_insert(0);
-                    *yield_val += yield_increase;
-                }
-                types::TokenType::MiningLightLevel => {
-                    let light_key = types::Token::persistent(types::TokenType::MiningLightLevel);
-                    let light_val = encounter_tokens.entry(light_key).or_insert(0);
-                    if let Some(cap) = gain.cap {
-                        let capped_gain = (cap - *light_val).max(0).min(gain.amount);
-                        *light_val += capped_gain;
-                    } else {
-                        *light_val += gain.amount;
-                    }
-                }
-                _ => {
-                    // Direct token addition to player balances (e.g., Stamina)
-                    let entry =
-                        types::token_entry_by_type(&mut self.token_balances, &gain.token_type);
-                    *entry += gain.amount;
-                }
+            let (token_type, amount) =
+                apply_mining_gain(gain, encounter_tokens, &mut self.token_balances);
+            *resolved.entry(token_type).or_insert(0) += amount;
+        }
+        for gain in &types::drain_gains(&mining_effect.drains, &resolved) {
+            apply_mining_gain(gain, encounter_tokens, &mut self.token_balances);
+        }
+        if mining_effect.ventilates {
+            if let Some(EncounterState::Mining(m)) = &mut self.current_encounter {
+                m.gas_per_turn = 0;
             }
         }
 
@@ -384,23 +529,41 @@ impl GameState {
         })
     }
 
-    /// Ore plays a random card from hand, applying token-based damages.
-    /// Then draws a card from deck to hand.
+    /// Ore plays a random card from hand, applying token-based damages and its hazard.
+    /// Ongoing gas and gem vein timers tick first. Then draws a card from deck to hand.
     fn resolve_ore_play(&mut self, rng: &mut rand_pcg::Lcg64Xsh32) {
-        let damages = {
+        let (damages, hazard, gas_per_turn) = {
             let mining = match &mut self.current_encounter {
                 Some(EncounterState::Mining(m)) => m,
                 _ => return,
             };
+            // The turn a vein is exposed counts as its first, so it closes as it reaches 0
+            mining.gem_vein = mining.gem_vein.take().and_then(|vein| {
+                vein.turns_left
+                    .checked_sub(1)
+                    .filter(|turns_left| *turns_left > 0)
+                    .map(|turns_left| types::GemVeinBonus { turns_left, ..vein })
+            });
             let played_idx =
                 match crate::library::game_state::deck_play_random(rng, &mut mining.ore_deck) {
                     Some(idx) => idx,
                     None => return,
                 };
             mining.round += 1;
-            mining.ore_deck[played_idx].damages.clone()
+            let ore_card = &mining.ore_deck[played_idx];
+            (
+                ore_card.damages.clone(),
+                ore_card.hazard.clone(),
+                mining.gas_per_turn,
+            )
         };
 
+        if gas_per_turn > 0 {
+            let stamina =
+                types::token_entry_by_type(&mut self.token_balances, &types::TokenType::Stamina);
+            *stamina = (*stamina - gas_per_turn).max(0);
+        }
+
         // Apply damages: encounter-scoped tokens go to encounter state, others to player balances
         for damage in &damages {
             let key = types::Token::persistent(damage.token_type.clone());
@@ -418,6 +581,33 @@ impl GameState {
             }
         }
 
+        match hazard {
+            Some(types::OreHazard::CaveIn { yield_percent }) => {
+                self.grant_mining_yield(yield_percent);
+                self.finish_mining_encounter(false);
+                return;
+            }
+            Some(types::OreHazard::Gas { stamina_per_turn }) => {
+                if let Some(EncounterState::Mining(m)) = &mut self.current_encounter {
+                    m.gas_per_turn = m.gas_per_turn.max(stamina_per_turn);
+                }
+            }
+            Some(types::OreHazard::GemVein {
+                token_type,
+                amount,
+                turns,
+            }) => {
+                if let Some(EncounterState::Mining(m)) = &mut self.current_encounter {
+                    m.gem_vein = Some(types::GemVeinBonus {
+                        token_type,
+                        amount,
+                        turns_left: turns,
+                    });
+                }
+            }
+            None => {}
+        }
+
         // Ore draws a card
         if let Some(EncounterState::Mining(mining)) = &mut self.current_encounter {
             crate::library::game_state::deck_draw_random(rng, &mut mining.ore_deck);
@@ -435,19 +625,38 @@ impl GameState {
         }
     }
 
-    /// Conclude a mining encounter voluntarily: reward = min(stamina, yield) ore tokens.
+    /// Conclude a mining encounter voluntarily: reward = min(stamina, yield) ore tokens,
+    /// plus the gem vein bonus if one is still open.
     pub fn conclude_mining_encounter(&mut self) -> Result<(), String> {
-        let mining_yield = match &self.current_encounter {
+        let gem_vein = match &self.current_encounter {
             Some(EncounterState::Mining(m)) if m.outcome == EncounterOutcome::Undecided => {
+                m.gem_vein.clone()
+            }
+            _ => return Err("No active mining encounter to conclude".to_string()),
+        };
+        self.grant_mining_yield(100);
+        if let Some(vein) = gem_vein {
+            let entry = types::token_entry_by_type(&mut self.token_balances, &vein.token_type);
+            *entry += vein.amount;
+        }
+        self.finish_mining_encounter(true);
+        Ok(())
+    }
+
+    /// Convert `percent`% of the accumulated yield into Ore, paying one Stamina per Ore
+    /// and capped by the Stamina available.
+    fn grant_mining_yield(&mut self, percent: u32) {
+        let mining_yield = match &self.current_encounter {
+            Some(EncounterState::Mining(m)) => {
                 let yield_key = types::Token::persistent(types::TokenType::MiningYield);
                 m.encounter_tokens.get(&yield_key).copied().unwrap_or(0)
             }
-            _ => return Err("No active mining encounter to conclude".to_string()),
+            _ => return,
         };
 
         let stamina_key = types::Token::persistent(types::TokenType::Stamina);
         let stamina = self.token_balances.get(&stamina_key).copied().unwrap_or(0);
-        let reward = stamina.min(mining_yield);
+        let reward = stamina.min(mining_yield * i64::from(percent) / 100);
 
         // Deduct stamina cost
         if let Some(s) = self.token_balances.get_mut(&stamina_key) {
@@ -458,9 +667,6 @@ impl GameState {
         let ore_key = types::Token::persistent(types::TokenType::Ore);
         let ore = self.token_balances.entry(ore_key).or_insert(0);
         *ore += reward;
-
-        self.finish_mining_encounter(true);
-        Ok(())
     }
 
     /// Finalize a mining encounter: record outcome. Encounter-scoped tokens are
@@ -4,4 +4,5 @@ pub(crate) mod fishing;
 pub(crate) mod herbalism;
 pub(crate) mod mining;
 pub(crate) mod rest;
+pub(crate) mod tools;
 pub(crate) mod woodcutting;
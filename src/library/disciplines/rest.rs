@@ -154,6 +154,7 @@ pub(crate) fn register_rest_cards(lib: &mut Library, rng: &mut rand_pcg::Lcg64Xs
                 rest_def: types::RestDef {
                     rest_token_min: 1,
                     rest_token_max: 2,
+                    entry_cost: vec![],
                 },
             },
         },
@@ -281,6 +282,7 @@ impl GameState {
                         *entry = cap;
                     }
                 }
+                types::clamp_to_max(&mut self.token_balances, &token_type);
             }
         }
 
@@ -0,0 +1,110 @@
+use crate::library::types::{self, CardCounts, CardKind, Discipline, EquippedTool};
+use crate::library::{GameState, Library};
+
+pub(crate) fn register_tool_cards(lib: &mut Library, _rng: &mut rand_pcg::Lcg64Xsh32) {
+    // Pickaxe: every mining card also gains mining power
+    lib.add_card(
+        CardKind::Tool {
+            discipline: Discipline::Mining,
+            modifiers: vec![types::TokenAmount {
+                token_type: types::TokenType::MiningPower,
+                amount: 50,
+                cap: None,
+            }],
+            durability: 20,
+        },
+        CardCounts {
+            library: 1,
+            deck: 0,
+            hand: 0,
+            discard: 0,
+        },
+    );
+}
+
+impl GameState {
+    /// Equip a tool from the library into its discipline's slot. Only allowed between
+    /// encounters. A previously unequipped copy resumes with the durability it had left.
+    /// Copies are counts, so the stored wear is that one copy's: it is taken back out first,
+    /// and other copies of the card equip at full durability.
+    pub fn equip_tool(&mut self, card_id: usize) -> Result<(), String> {
+        if self.current_encounter.is_some() {
+            return Err("Tools can only be equipped between encounters".to_string());
+        }
+        let card = self
+            .library
+            .get(card_id)
+            .ok_or_else(|| format!("Card {} not found in Library", card_id))?;
+        let (discipline, durability) = match &card.kind {
+            CardKind::Tool {
+                discipline,
+                durability,
+                ..
+            } => (*discipline, *durability),
+            _ => return Err(format!("Card {} is not a tool", card_id)),
+        };
+        if card.counts.library == 0 {
+            return Err(format!(
+                "Card {} has no copy in the library to equip",
+                card_id
+            ));
+        }
+        if let Some(equipped) = self.equipped_tools.get(&discipline) {
+            return Err(format!(
+                "Card {} is already equipped for {:?}; unequip it first",
+                equipped.card_id, discipline
+            ));
+        }
+        if let Some(card) = self.library.cards.get_mut(card_id) {
+            card.counts.library -= 1;
+        }
+        let durability_left = self
+            .stored_tool_durability
+            .remove(&card_id)
+            .unwrap_or(durability);
+        self.equipped_tools.insert(
+            discipline,
+            EquippedTool {
+                discipline,
+                card_id,
+                durability_left,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the tool in a discipline's slot to the library, remembering its wear.
+    pub fn unequip_tool(&mut self, discipline: &Discipline) -> Result<(), String> {
+        if self.current_encounter.is_some() {
+            return Err("Tools can only be unequipped between encounters".to_string());
+        }
+        let equipped = self
+            .equipped_tools
+            .remove(discipline)
+            .ok_or_else(|| format!("No tool equipped for {:?}", discipline))?;
+        if let Some(card) = self.library.cards.get_mut(equipped.card_id) {
+            card.counts.library += 1;
+        }
+        self.stored_tool_durability
+            .insert(equipped.card_id, equipped.durability_left);
+        Ok(())
+    }
+
+    /// Modifiers of the tool equipped for `discipline`, wearing it down by one use.
+    /// A tool whose durability runs out breaks and is removed.
+    pub(crate) fn use_tool(&mut self, discipline: &Discipline) -> Vec<types::TokenAmount> {
+        let equipped = match self.equipped_tools.get_mut(discipline) {
+            Some(equipped) => equipped,
+            None => return Vec::new(),
+        };
+        let modifiers = match self.library.get(equipped.card_id).map(|c| &c.kind) {
+            Some(CardKind::Tool { modifiers, .. }) => modifiers.clone(),
+            _ => Vec::new(),
+        };
+        equipped.durability_left = equipped.durability_left.saturating_sub(1);
+        if equipped.durability_left == 0 {
+            self.equipped_tools.remove(discipline);
+        }
+        modifiers
+    }
+}
@@ -1,5 +1,5 @@
 use crate::library::types::{
-    self, CardCounts, CardKind, EncounterKind, EncounterOutcome, EncounterState,
+    self, CardCounts, CardKind, EncounterKind, EncounterOutcome, EncounterState, WoodcuttingPattern,
 };
 use crate::library::{GameState, Library};
 use std::collections::HashMap;
@@ -17,6 +17,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -39,6 +40,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -61,6 +63,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -83,6 +86,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -103,6 +107,8 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                         types::Token::persistent(types::TokenType::Lumber),
                         1000,
                     )]),
+                    pattern_multipliers: WoodcuttingPattern::default_multipliers(),
+                    entry_cost: vec![],
                 },
             },
         },
@@ -133,6 +139,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     },
                 ],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -155,6 +162,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -177,6 +185,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     cap: None,
                 }],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -210,6 +219,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     },
                 ],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -244,6 +254,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     },
                 ],
                 gains: vec![],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -270,6 +281,7 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
                     amount: 200,
                     cap: None,
                 }],
+                drains: vec![],
             },
         },
         CardCounts {
@@ -281,106 +293,211 @@ pub(crate) fn register_woodcutting_cards(lib: &mut Library, _rng: &mut rand_pcg:
     );
 }
 
-/// Evaluate played woodcutting cards and return (pattern_name, reward_multiplier).
-/// Poker-inspired patterns adapted for 8 cards using ChopType counts.
-fn evaluate_best_pattern(played: &[types::PlayedWoodcuttingCard]) -> (String, f64) {
-    use std::collections::HashMap;
-    use types::ChopType;
-
-    // Count occurrences of each chop type
-    let mut type_counts: HashMap<&ChopType, usize> = HashMap::new();
-    for card in played {
-        for ct in &card.chop_types {
-            *type_counts.entry(ct).or_insert(0) += 1;
+/// Chop counts of a set of played woodcutting cards, used to match patterns.
+struct PatternStats {
+    max_type_count: usize,
+    second_type_count: usize,
+    distinct_types: usize,
+    max_value_count: usize,
+    longest_straight: usize,
+}
+
+impl PatternStats {
+    fn from_played(played: &[types::PlayedWoodcuttingCard]) -> Self {
+        let mut type_counts: HashMap<&types::ChopType, usize> = HashMap::new();
+        for card in played {
+            for ct in &card.chop_types {
+                *type_counts.entry(ct).or_insert(0) += 1;
+            }
+        }
+        let mut sorted_type_counts: Vec<usize> = type_counts.values().copied().collect();
+        sorted_type_counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut all_values: Vec<u32> = played
+            .iter()
+            .flat_map(|c| c.chop_values.iter().copied())
+            .collect();
+        all_values.sort();
+        let mut value_counts: HashMap<u32, usize> = HashMap::new();
+        for &v in &all_values {
+            *value_counts.entry(v).or_insert(0) += 1;
         }
-    }
 
-    // Collect all chop values (sorted) for straight detection
-    let mut all_values: Vec<u32> = played
-        .iter()
-        .flat_map(|c| c.chop_values.iter().copied())
-        .collect();
-    all_values.sort();
-
-    // Count value frequencies for value-based patterns
-    let mut value_counts: HashMap<u32, usize> = HashMap::new();
-    for &v in &all_values {
-        *value_counts.entry(v).or_insert(0) += 1;
+        PatternStats {
+            max_type_count: sorted_type_counts.first().copied().unwrap_or(0),
+            second_type_count: sorted_type_counts.get(1).copied().unwrap_or(0),
+            distinct_types: type_counts.len(),
+            max_value_count: value_counts.values().copied().max().unwrap_or(0),
+            longest_straight: longest_consecutive_run(&all_values),
+        }
     }
-    let mut freq_list: Vec<usize> = value_counts.values().copied().collect();
-    freq_list.sort_unstable_by(|a, b| b.cmp(a));
 
-    let max_type_count = type_counts.values().copied().max().unwrap_or(0);
-    let distinct_types = type_counts.len();
-    let longest_straight = longest_consecutive_run(&all_values);
+    fn matches(&self, pattern: WoodcuttingPattern) -> bool {
+        match pattern {
+            WoodcuttingPattern::EightOfAKind => self.max_type_count >= 8,
+            WoodcuttingPattern::SevenOfAKind => self.max_type_count >= 7,
+            WoodcuttingPattern::PerfectStraight => self.longest_straight >= 8,
+            WoodcuttingPattern::SixOfAKind => self.max_type_count >= 6,
+            WoodcuttingPattern::LongStraight => self.longest_straight >= 6,
+            WoodcuttingPattern::FullSet => self.distinct_types >= 5,
+            WoodcuttingPattern::FiveOfAKind => self.max_type_count >= 5,
+            WoodcuttingPattern::FullHouse => {
+                self.max_type_count >= 4 && self.second_type_count >= 2
+            }
+            WoodcuttingPattern::FourOfAKind => self.max_type_count >= 4,
+            WoodcuttingPattern::ShortStraight => self.longest_straight >= 4,
+            WoodcuttingPattern::TwoPairTypes => {
+                self.max_type_count >= 3 && self.second_type_count >= 3
+            }
+            WoodcuttingPattern::ValueQuads => self.max_value_count >= 4,
+            WoodcuttingPattern::Triple => self.max_type_count >= 3,
+            WoodcuttingPattern::ValueTriple => self.max_value_count >= 3,
+            WoodcuttingPattern::Pair => self.max_type_count >= 2,
+            WoodcuttingPattern::HighCard => true,
+        }
+    }
+}
 
-    // Sorted frequency list for value-based patterns
-    let mut sorted_type_counts: Vec<usize> = type_counts.values().copied().collect();
-    sorted_type_counts.sort_unstable_by(|a, b| b.cmp(a));
+/// Evaluate played woodcutting cards: the highest-ranked matching pattern present in the
+/// multiplier table wins. Falls back to HighCard (1.0 unless the table says otherwise).
+pub(crate) fn evaluate_best_pattern(
+    played: &[types::PlayedWoodcuttingCard],
+    multipliers: &HashMap<WoodcuttingPattern, f64>,
+) -> types::WoodcuttingPatternScore {
+    let stats = PatternStats::from_played(played);
+    WoodcuttingPattern::ALL
+        .iter()
+        .find_map(|&pattern| {
+            let multiplier = *multipliers.get(&pattern)?;
+            stats
+                .matches(pattern)
+                .then_some(types::WoodcuttingPatternScore {
+                    pattern,
+                    multiplier,
+                })
+        })
+        .unwrap_or(types::WoodcuttingPatternScore {
+            pattern: WoodcuttingPattern::HighCard,
+            multiplier: 1.0,
+        })
+}
 
-    // Evaluate patterns from best to worst
-    // Eight of a Kind: all 8 cards same type
-    if max_type_count >= 8 {
-        return ("Eight of a Kind".to_string(), 55.0);
-    }
-    // Seven of a Kind
-    if max_type_count >= 7 {
-        return ("Seven of a Kind".to_string(), 12.0);
-    }
-    // Perfect Straight: 8 sequential values
-    if longest_straight >= 8 {
-        return ("Perfect Straight".to_string(), 3.0);
-    }
-    // Six of a Kind
-    if max_type_count >= 6 {
-        return ("Six of a Kind".to_string(), 4.0);
-    }
-    // Long Straight: 6-7 sequential values
-    if longest_straight >= 6 {
-        return ("Long Straight".to_string(), 1.5);
-    }
-    // Full Set: all 5 chop types present
-    if distinct_types >= 5 {
-        return ("Full Set".to_string(), 1.0);
-    }
-    // Five of a Kind
-    if max_type_count >= 5 {
-        return ("Five of a Kind".to_string(), 2.0);
-    }
-    // Four of a Kind with Pair: 4+ of one type plus 2+ of another
-    if max_type_count >= 4 && sorted_type_counts.len() >= 2 && sorted_type_counts[1] >= 2 {
-        return ("Full House".to_string(), 1.5);
-    }
-    // Four of a Kind
-    if max_type_count >= 4 {
-        return ("Four of a Kind".to_string(), 10.0);
-    }
-    // Short Straight: 4-5 sequential values
-    if longest_straight >= 4 {
-        return ("Short Straight".to_string(), 2.5);
+/// Simulate `hands` full woodcutting hands of `max_plays` cards drawn without replacement
+/// from the player's woodcutting cards (deck, hand and discard), reporting how often each
+/// pattern is the best one and the expected Lumber reward. Seeded, so results are repeatable.
+pub fn simulate_woodcutting_patterns(
+    library: &Library,
+    woodcutting_def: &types::WoodcuttingDef,
+    hands: u32,
+    seed: u64,
+) -> types::WoodcuttingSimulation {
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    let mut pool: Vec<types::PlayedWoodcuttingCard> = Vec::new();
+    for (card_id, card) in library.cards.iter().enumerate() {
+        if let CardKind::Woodcutting { woodcutting_effect } = &card.kind {
+            let copies = card.counts.deck + card.counts.hand + card.counts.discard;
+            for _ in 0..copies {
+                pool.push(types::PlayedWoodcuttingCard {
+                    card_id,
+                    chop_types: woodcutting_effect.chop_types.clone(),
+                    chop_values: woodcutting_effect.chop_values.clone(),
+                });
+            }
+        }
     }
-    // Two Pair Types: 2 types with 3+ each
-    if sorted_type_counts.len() >= 2 && sorted_type_counts[0] >= 3 && sorted_type_counts[1] >= 3 {
-        return ("Two Pair Types".to_string(), 2.0);
+
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(seed);
+    let hand_size = (woodcutting_def.max_plays as usize).min(pool.len());
+    let mut counts: HashMap<WoodcuttingPattern, u32> = HashMap::new();
+    let mut total_multiplier = 0.0;
+    for _ in 0..hands {
+        let (hand, _) = pool.partial_shuffle(&mut rng, hand_size);
+        let score = evaluate_best_pattern(hand, &woodcutting_def.pattern_multipliers);
+        *counts.entry(score.pattern).or_insert(0) += 1;
+        total_multiplier += score.multiplier;
     }
-    // Value Quads: 4+ of same value
-    if freq_list.first().copied().unwrap_or(0) >= 4 {
-        return ("Value Quads".to_string(), 12.0);
+
+    let hands_f = f64::from(hands.max(1));
+    let expected_multiplier = total_multiplier / hands_f;
+    let base_lumber =
+        types::token_balance_by_type(&woodcutting_def.base_rewards, &types::TokenType::Lumber);
+    types::WoodcuttingSimulation {
+        hands,
+        frequencies: counts
+            .into_iter()
+            .map(|(pattern, count)| (pattern, f64::from(count) / hands_f))
+            .collect(),
+        expected_multiplier,
+        expected_lumber: base_lumber as f64 * expected_multiplier,
     }
-    // Triple of a Kind
-    if max_type_count >= 3 {
-        return ("Triple".to_string(), 2.0);
+}
+
+/// Patterns from the multiplier table that `played` plus `remaining` more plays could
+/// match, drawing each chop profile at most as many times as copies are available.
+/// Patterns only ever gain matches as cards are added, so only the longest extensions
+/// are searched.
+fn reachable_patterns(
+    played: &[types::PlayedWoodcuttingCard],
+    available: &mut [(types::PlayedWoodcuttingCard, u32)],
+    remaining: u32,
+    multipliers: &HashMap<WoodcuttingPattern, f64>,
+) -> Vec<types::WoodcuttingPatternScore> {
+    let mut found: Vec<WoodcuttingPattern> = Vec::new();
+    let wanted = WoodcuttingPattern::ALL
+        .iter()
+        .filter(|p| multipliers.contains_key(p))
+        .count();
+    let mut hand = played.to_vec();
+    collect_reachable(&mut hand, available, 0, remaining, &mut found, wanted);
+    WoodcuttingPattern::ALL
+        .iter()
+        .filter(|p| found.contains(p))
+        .filter_map(|&pattern| {
+            multipliers
+                .get(&pattern)
+                .map(|&multiplier| types::WoodcuttingPatternScore {
+                    pattern,
+                    multiplier,
+                })
+        })
+        .collect()
+}
+
+/// Depth-first search over multisets of the available copies, stopping once every wanted
+/// pattern is found. Each push takes one copy of a profile and each pop puts it back.
+fn collect_reachable(
+    hand: &mut Vec<types::PlayedWoodcuttingCard>,
+    available: &mut [(types::PlayedWoodcuttingCard, u32)],
+    first_profile: usize,
+    remaining: u32,
+    found: &mut Vec<WoodcuttingPattern>,
+    wanted: usize,
+) {
+    if found.len() >= wanted {
+        return;
     }
-    // Value Triples
-    if freq_list.first().copied().unwrap_or(0) >= 3 {
-        return ("Value Triple".to_string(), 19.0);
+    let can_extend = remaining > 0 && available[first_profile..].iter().any(|(_, n)| *n > 0);
+    if !can_extend {
+        let stats = PatternStats::from_played(hand);
+        for pattern in WoodcuttingPattern::ALL {
+            if stats.matches(pattern) && !found.contains(&pattern) {
+                found.push(pattern);
+            }
+        }
+        return;
     }
-    // Pair (2+ of a type)
-    if max_type_count >= 2 {
-        return ("Pair".to_string(), 4.5);
+    for index in first_profile..available.len() {
+        if available[index].1 == 0 {
+            continue;
+        }
+        available[index].1 -= 1;
+        hand.push(available[index].0.clone());
+        collect_reachable(hand, available, index, remaining - 1, found, wanted);
+        hand.pop();
+        available[index].1 += 1;
     }
-    // High Card (fallback)
-    ("High Card".to_string(), 1.0)
 }
 
 /// Find the longest run of consecutive values in a sorted slice.
@@ -438,9 +555,10 @@ impl GameState {
             outcome: EncounterOutcome::Undecided,
             played_cards: Vec::new(),
             max_plays: woodcutting_def.max_plays,
-            pattern_name: None,
+            pattern: None,
             pattern_multiplier: None,
             base_rewards: woodcutting_def.base_rewards,
+            pattern_multipliers: woodcutting_def.pattern_multipliers,
         };
         self.current_encounter = Some(EncounterState::Woodcutting(state));
         self.encounter_phase = types::EncounterPhase::InEncounter;
@@ -458,7 +576,7 @@ impl GameState {
             .get(card_id)
             .ok_or_else(|| format!("Card {} not found in Library", card_id))?
             .clone();
-        let woodcutting_effect = match &lib_card.kind {
+        let mut woodcutting_effect = match &lib_card.kind {
             CardKind::Woodcutting { woodcutting_effect } => woodcutting_effect.clone(),
             _ => {
                 return Err(
@@ -472,11 +590,17 @@ impl GameState {
             types::split_token_amounts(&woodcutting_effect.costs);
         Self::check_and_deduct_gathering_costs(&pre_play_costs, &mut self.token_balances)?;
 
-        // Apply gains
-        for gain in &woodcutting_effect.gains {
-            let entry = types::token_entry_by_type(&mut self.token_balances, &gain.token_type);
-            *entry += gain.amount;
-        }
+        // An equipped woodcutting tool adds its modifiers to the card's gains
+        woodcutting_effect
+            .gains
+            .extend(self.use_tool(&types::Discipline::Woodcutting));
+
+        // Apply gains and their drains
+        Self::apply_gathering_gains(
+            &woodcutting_effect.gains,
+            &woodcutting_effect.drains,
+            &mut self.token_balances,
+        );
 
         // Deduct durability costs (depletes encounter, doesn't reject card)
         let mut durability_depleted = false;
@@ -513,16 +637,10 @@ impl GameState {
 
         if all_played {
             // Evaluate pattern and finish as win
-            let (pattern_name, multiplier) = {
-                let woodcutting = match &self.current_encounter {
-                    Some(EncounterState::Woodcutting(w)) => w,
-                    _ => return Err("No active woodcutting encounter".to_string()),
-                };
-                evaluate_best_pattern(&woodcutting.played_cards)
-            };
             if let Some(EncounterState::Woodcutting(w)) = &mut self.current_encounter {
-                w.pattern_name = Some(pattern_name);
-                w.pattern_multiplier = Some(multiplier);
+                let score = evaluate_best_pattern(&w.played_cards, &w.pattern_multipliers);
+                w.pattern = Some(score.pattern);
+                w.pattern_multiplier = Some(score.multiplier);
             }
             self.finish_woodcutting_encounter(true);
         } else {
@@ -562,16 +680,36 @@ impl GameState {
 
     fn finish_woodcutting_encounter(&mut self, is_win: bool) {
         if is_win {
-            let (base_rewards, multiplier) = match &self.current_encounter {
-                Some(EncounterState::Woodcutting(w)) => {
-                    (w.base_rewards.clone(), w.pattern_multiplier.unwrap_or(1.0))
-                }
+            let (base_rewards, multiplier, played_ids) = match &self.current_encounter {
+                Some(EncounterState::Woodcutting(w)) => (
+                    w.base_rewards.clone(),
+                    w.pattern_multiplier.unwrap_or(1.0),
+                    w.played_cards.iter().map(|p| p.card_id).collect::<Vec<_>>(),
+                ),
                 _ => return,
             };
+            let mut resolved = HashMap::new();
             for (token, amount) in &base_rewards {
                 let scaled = (*amount as f64 * multiplier).round() as i64;
                 let entry = self.token_balances.entry(token.clone()).or_insert(0);
                 *entry += scaled;
+                *resolved.entry(token.token_type.clone()).or_insert(0) += scaled;
+            }
+            // Each played card's drains also convert part of the pattern reward
+            let drains: Vec<types::DrainEffect> = played_ids
+                .iter()
+                .filter_map(|id| match self.library.get(*id).map(|c| &c.kind) {
+                    Some(CardKind::Woodcutting { woodcutting_effect }) => {
+                        Some(woodcutting_effect.drains.clone())
+                    }
+                    _ => None,
+                })
+                .flatten()
+                .collect();
+            for gain in &types::drain_gains(&drains, &resolved) {
+                *types::token_entry_by_type(&mut self.token_balances, &gain.token_type) +=
+                    gain.amount;
+                types::clamp_to_max(&mut self.token_balances, &gain.token_type);
             }
         }
         let outcome = if is_win {
@@ -585,6 +723,57 @@ impl GameState {
         self.encounter_phase = types::EncounterPhase::Scouting;
     }
 
+    /// Preview the active woodcutting encounter: the best pattern so far and every pattern
+    /// still reachable with the remaining plays, drawing from the copies of woodcutting
+    /// cards in the player's deck, hand and discard.
+    pub fn woodcutting_pattern_preview(&self) -> Result<types::WoodcuttingPatternPreview, String> {
+        let woodcutting = match &self.current_encounter {
+            Some(EncounterState::Woodcutting(w)) => w,
+            _ => return Err("No active woodcutting encounter".to_string()),
+        };
+        let mut available: Vec<(types::PlayedWoodcuttingCard, u32)> = Vec::new();
+        for (card_id, card) in self.library.cards.iter().enumerate() {
+            let CardKind::Woodcutting { woodcutting_effect } = &card.kind else {
+                continue;
+            };
+            let copies = card.counts.deck + card.counts.hand + card.counts.discard;
+            if copies == 0 || woodcutting_effect.chop_types.is_empty() {
+                continue;
+            }
+            let same_profile = available.iter_mut().find(|(p, _)| {
+                p.chop_types == woodcutting_effect.chop_types
+                    && p.chop_values == woodcutting_effect.chop_values
+            });
+            match same_profile {
+                Some((_, count)) => *count += copies,
+                None => available.push((
+                    types::PlayedWoodcuttingCard {
+                        card_id,
+                        chop_types: woodcutting_effect.chop_types.clone(),
+                        chop_values: woodcutting_effect.chop_values.clone(),
+                    },
+                    copies,
+                )),
+            }
+        }
+        let remaining_plays = woodcutting
+            .max_plays
+            .saturating_sub(woodcutting.played_cards.len() as u32);
+        Ok(types::WoodcuttingPatternPreview {
+            remaining_plays,
+            best_so_far: evaluate_best_pattern(
+                &woodcutting.played_cards,
+                &woodcutting.pattern_multipliers,
+            ),
+            reachable: reachable_patterns(
+                &woodcutting.played_cards,
+                &mut available,
+                remaining_plays,
+                &woodcutting.pattern_multipliers,
+            ),
+        })
+    }
+
     /// Draw one player woodcutting card from deck to hand, recycling discard if needed.
     fn draw_player_woodcutting_card(&mut self, rng: &mut rand_pcg::Lcg64Xsh32) {
         self.draw_player_cards_of_kind(
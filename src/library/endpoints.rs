@@ -50,6 +50,7 @@ pub async fn list_library_cards(
             Some("PlayerCardEffect") => matches!(c.kind, CardKind::PlayerCardEffect { .. }),
             Some("EnemyCardEffect") => matches!(c.kind, CardKind::EnemyCardEffect { .. }),
             Some("Crafting") => matches!(c.kind, CardKind::Crafting { .. }),
+            Some("Tool") => matches!(c.kind, CardKind::Tool { .. }),
             _ => true,
         })
         .map(|(id, c)| LibraryCardWithId {
@@ -1,7 +1,7 @@
 use super::action_log::ActionLog;
 use super::types::{
     ActionEntry, ActionPayload, CardCounts, CardKind, ConcreteEffect, ConcreteEffectCost,
-    EncounterKind, EncounterOutcome, EncounterState, HasDeckCounts,
+    EncounterDiscipline, EncounterOutcome, EncounterState, HasDeckCounts,
 };
 use super::Library;
 use std::collections::HashMap;
@@ -67,6 +67,22 @@ pub(crate) fn roll_concrete_effect(
                 .collect();
             (value, costs, None, None)
         }
+        Some(super::types::CardEffectKind::ConditionalLoseTokens { min, max, .. })
+        | Some(super::types::CardEffectKind::ConditionalGainTokens { min, max, .. }) => {
+            (roll_range(rng, min, max), vec![], None, None)
+        }
+        Some(super::types::CardEffectKind::ReflectDamage {
+            min_percent,
+            max_percent,
+        }) => (
+            roll_range_u32(rng, min_percent, max_percent) as i64,
+            vec![],
+            None,
+            None,
+        ),
+        Some(super::types::CardEffectKind::Drain { percent, .. }) => {
+            (percent as i64, vec![], None, None)
+        }
         _ => (0, vec![], None, None),
     };
     ConcreteEffect {
@@ -255,6 +271,7 @@ fn initialize_library(rng: &mut rand_pcg::Lcg64Xsh32) -> Library {
     super::disciplines::fishing::register_fishing_cards(&mut lib, rng);
     super::disciplines::rest::register_rest_cards(&mut lib, rng);
     super::disciplines::crafting::register_crafting_cards(&mut lib, rng);
+    super::disciplines::tools::register_tool_cards(&mut lib, rng);
 
     if let Err(errors) = lib.validate_card_effects() {
         panic!("Library card effect validation failed: {:?}", errors);
@@ -273,6 +290,12 @@ pub struct GameState {
     pub encounter_phase: super::types::EncounterPhase,
     pub last_encounter_result: Option<EncounterOutcome>,
     pub encounter_results: Vec<EncounterOutcome>,
+    /// Reward breakdowns of won combats, keyed by index into `encounter_results`.
+    pub encounter_rewards: HashMap<usize, super::types::RewardBreakdown>,
+    pub equipped_tools: HashMap<super::types::Discipline, super::types::EquippedTool>,
+    /// Durability left on the used copy of each unequipped tool, keyed by card ID. Only one
+    /// copy of a tool card can be equipped at a time, so at most one copy per card is worn.
+    pub stored_tool_durability: HashMap<usize, u32>,
 }
 
 impl GameState {
@@ -285,7 +308,7 @@ impl GameState {
     pub fn new_with_rng(rng: &mut rand_pcg::Lcg64Xsh32) -> Self {
         let mut balances = HashMap::new();
         for id in super::types::TokenType::all() {
-            balances.insert(super::types::Token::persistent(id), 0i64);
+            balances.insert(super::types::Token::with_default_lifecycle(id), 0i64);
         }
         // Default Foresight controls area deck hand size
         balances.insert(
@@ -309,6 +332,12 @@ impl GameState {
             super::types::Token::persistent(super::types::TokenType::FishingDurability),
             10000,
         );
+        for discipline in super::types::Discipline::ALL {
+            balances.insert(
+                super::types::Token::persistent(discipline.max_durability_token()),
+                10000,
+            );
+        }
         // Starting stamina
         balances.insert(
             super::types::Token::persistent(super::types::TokenType::Stamina),
@@ -319,6 +348,10 @@ impl GameState {
             super::types::Token::persistent(super::types::TokenType::Health),
             1000,
         );
+        balances.insert(
+            super::types::Token::persistent(super::types::TokenType::MaxHealth),
+            1000,
+        );
         // Max handsize tokens (player decks)
         balances.insert(
             super::types::Token::persistent(super::types::TokenType::AttackMaxHand),
@@ -356,22 +389,6 @@ impl GameState {
             super::types::Token::persistent(super::types::TokenType::CraftingMaxHand),
             5,
         );
-        let _action_log = match std::env::var("ACTION_LOG_FILE") {
-            Ok(path) => {
-                #[allow(clippy::manual_unwrap_or_default)]
-                let mut log = match super::action_log::ActionLog::load_from_file(&path) {
-                    Ok(l) => l,
-                    Err(_) => ActionLog::new(),
-                };
-                if let Ok(writer) =
-                    crate::action::persistence::FileWriter::new(std::path::PathBuf::from(&path))
-                {
-                    log.set_writer(Some(writer));
-                }
-                log
-            }
-            Err(_) => ActionLog::new(),
-        };
         Self {
             action_log: std::sync::Arc::new(ActionLog::new()),
             token_balances: balances,
@@ -380,12 +397,22 @@ impl GameState {
             encounter_phase: super::types::EncounterPhase::NoEncounter,
             last_encounter_result: None,
             encounter_results: Vec::new(),
+            encounter_rewards: HashMap::new(),
+            equipped_tools: HashMap::new(),
+            stored_tool_durability: HashMap::new(),
         }
     }
 
-    /// Append an action to the action log with optional metadata; returns the appended entry.
-    pub fn append_action(&self, action_type: &str, payload: ActionPayload) -> ActionEntry {
-        self.action_log.append(action_type, payload)
+    /// Append an action to the action log together with the digest of the state it left
+    /// behind (`rng` being the game RNG after the action); returns the appended entry.
+    pub fn append_action(
+        &self,
+        action_type: &str,
+        payload: ActionPayload,
+        rng: &rand_pcg::Lcg64Xsh32,
+    ) -> ActionEntry {
+        self.action_log
+            .append_with_digest(action_type, payload, Some(self.digest(rng)))
     }
 
     /// Check if player can pay all costs on a card's effects. Deducts costs if affordable.
@@ -446,6 +473,26 @@ impl GameState {
         Ok(())
     }
 
+    /// Add gathering gains to player balances, then the drains of what those gains resolved.
+    pub(crate) fn apply_gathering_gains(
+        gains: &[super::types::TokenAmount],
+        drains: &[super::types::DrainEffect],
+        token_balances: &mut HashMap<super::types::Token, i64>,
+    ) {
+        let mut resolved = HashMap::new();
+        for gain in gains {
+            let entry = super::types::token_entry_by_type(token_balances, &gain.token_type);
+            *entry += gain.amount;
+            super::types::clamp_to_max(token_balances, &gain.token_type);
+            *resolved.entry(gain.token_type.clone()).or_insert(0) += gain.amount;
+        }
+        for gain in &super::types::drain_gains(drains, &resolved) {
+            let entry = super::types::token_entry_by_type(token_balances, &gain.token_type);
+            *entry += gain.amount;
+            super::types::clamp_to_max(token_balances, &gain.token_type);
+        }
+    }
+
     /// Check if player can afford gathering costs without deducting.
     pub fn preview_gathering_costs(
         costs: &[super::types::TokenAmount],
@@ -599,6 +646,134 @@ impl GameState {
         self.encounter_phase = super::types::EncounterPhase::Scouting;
     }
 
+    /// Material cost of repairing a discipline's durability back to its maximum:
+    /// one material per `REPAIR_DURABILITY_PER_MATERIAL` missing durability, rounded up.
+    pub fn repair_cost(&self, discipline: &super::types::Discipline) -> i64 {
+        let current = super::types::token_balance_by_type(
+            &self.token_balances,
+            &discipline.durability_token(),
+        );
+        let max = super::types::token_balance_by_type(
+            &self.token_balances,
+            &discipline.max_durability_token(),
+        );
+        let missing = (max - current).max(0);
+        (missing + super::types::Discipline::REPAIR_DURABILITY_PER_MATERIAL - 1)
+            / super::types::Discipline::REPAIR_DURABILITY_PER_MATERIAL
+    }
+
+    /// Repair a discipline's durability to its maximum during a rest or crafting encounter,
+    /// paying `repair_cost` in the discipline's repair material.
+    pub fn resolve_repair(&mut self, discipline: &super::types::Discipline) -> Result<(), String> {
+        match &self.current_encounter {
+            Some(EncounterState::Rest(r))
+                if r.outcome == super::types::EncounterOutcome::Undecided => {}
+            Some(EncounterState::Crafting(c))
+                if c.outcome == super::types::EncounterOutcome::Undecided => {}
+            _ => return Err("Repairs need an active rest or crafting encounter".to_string()),
+        }
+        let cost = self.repair_cost(discipline);
+        if cost == 0 {
+            return Err(format!(
+                "{:?} is already at its maximum",
+                discipline.durability_token()
+            ));
+        }
+        Self::check_and_deduct_gathering_costs(
+            &[super::types::TokenAmount {
+                token_type: discipline.repair_material(),
+                amount: cost,
+                cap: None,
+            }],
+            &mut self.token_balances,
+        )?;
+        let max = super::types::token_balance_by_type(
+            &self.token_balances,
+            &discipline.max_durability_token(),
+        );
+        *super::types::token_entry_by_type(
+            &mut self.token_balances,
+            &discipline.durability_token(),
+        ) = max;
+        Ok(())
+    }
+
+    /// Pay an encounter card's entry cost before it is picked. Nothing is deducted unless the
+    /// whole cost is affordable.
+    pub fn pay_entry_cost(&mut self, card_id: usize) -> Result<(), String> {
+        let entry_cost = match self.library.get(card_id).map(|c| &c.kind) {
+            Some(CardKind::Encounter { encounter_kind }) => encounter_kind.entry_cost().to_vec(),
+            _ => return Err(format!("Card {} is not an encounter", card_id)),
+        };
+        Self::check_and_deduct_gathering_costs(&entry_cost, &mut self.token_balances)
+    }
+
+    /// Pick an encounter card from the hand: pay its entry cost, move it to the discard and
+    /// start the encounter. Everything that could stop the encounter from starting is checked
+    /// before anything is paid, so a failed pick leaves the tokens, the cards and the rng as
+    /// they were.
+    pub fn start_picked_encounter(
+        &mut self,
+        card_id: usize,
+        rng: &mut rand_pcg::Lcg64Xsh32,
+    ) -> Result<(), String> {
+        let card = self
+            .library
+            .get(card_id)
+            .ok_or_else(|| format!("Card {} not found", card_id))?;
+        let CardKind::Encounter { encounter_kind } = &card.kind else {
+            return Err(format!("Card {} is not an encounter", card_id));
+        };
+        if card.counts.hand == 0 {
+            return Err(format!("Card {} has no copies in hand", card_id));
+        }
+        let discipline = encounter_kind.discipline();
+        if discipline == EncounterDiscipline::Crafting && self.current_encounter.is_some() {
+            return Err("Already in an encounter".to_string());
+        }
+        Self::preview_gathering_costs(encounter_kind.entry_cost(), &self.token_balances)?;
+
+        self.pay_entry_cost(card_id)?;
+        self.library.play(card_id)?;
+        match discipline {
+            EncounterDiscipline::Combat => {
+                // Initialize player health if not set
+                let health_key = super::types::Token::persistent(super::types::TokenType::Health);
+                if self.token_balances.get(&health_key).copied().unwrap_or(0) == 0 {
+                    self.token_balances.insert(health_key, 2000);
+                }
+                self.start_combat(card_id, rng)
+            }
+            EncounterDiscipline::Mining => self.start_mining_encounter(card_id, rng),
+            EncounterDiscipline::Herbalism => self.start_herbalism_encounter(card_id, rng),
+            EncounterDiscipline::Woodcutting => self.start_woodcutting_encounter(card_id, rng),
+            EncounterDiscipline::Fishing => self.start_fishing_encounter(card_id, rng),
+            EncounterDiscipline::Rest => self.start_rest_encounter(card_id, rng),
+            EncounterDiscipline::Crafting => self.start_crafting_encounter(card_id, rng),
+        }
+    }
+
+    /// Summaries of the encounters in the hand, one per card id, in library order.
+    pub fn encounter_hand_summaries(&self) -> Vec<super::types::EncounterSummary> {
+        self.library
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.counts.hand > 0)
+            .filter_map(|(card_id, c)| match &c.kind {
+                CardKind::Encounter { encounter_kind } => Some(super::types::EncounterSummary {
+                    card_id,
+                    hand_count: c.counts.hand,
+                    discipline: encounter_kind.discipline(),
+                    difficulty_score: encounter_kind.difficulty_score(),
+                    expected_rewards: encounter_kind.expected_rewards(),
+                    entry_cost: encounter_kind.entry_cost().to_vec(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Check if the player has died (Health <= 0) and apply death consequences:
     /// lose all gathering material tokens, reset health and stamina, increment deaths counter.
     pub(crate) fn check_player_death(&mut self) {
@@ -631,171 +806,186 @@ impl GameState {
     /// Reconstruct state from an existing action log.
     /// The RNG is initialized from the first `SetSeed` entry in the log.
     pub fn replay_from_log(log: &ActionLog) -> Self {
+        Self::replay_with_rng(log).0
+    }
+
+    /// Like `replay_from_log`, but also returns the RNG as it stands after the last entry.
+    pub fn replay_with_rng(log: &ActionLog) -> (Self, rand_pcg::Lcg64Xsh32) {
+        Self::resume_from_snapshot(None, log)
+    }
+
+    /// Apply one logged action to the state, mirroring what its handler did when it was
+    /// first played. Errors are ignored: only actions that succeeded were logged.
+    pub(crate) fn replay_entry(&mut self, payload: &ActionPayload, rng: &mut rand_pcg::Lcg64Xsh32) {
         use rand::SeedableRng;
 
-        let mut gs = GameState::new();
-        let mut rng = rand_pcg::Lcg64Xsh32::from_seed([0u8; 16]);
-
-        for e in log.entries() {
-            match &e.payload {
-                ActionPayload::SetSeed { seed } => {
-                    let mut seed_bytes = [0u8; 16];
-                    seed_bytes[0..8].copy_from_slice(&seed.to_le_bytes());
-                    seed_bytes[8..16].copy_from_slice(&seed.to_le_bytes());
-                    rng = rand_pcg::Lcg64Xsh32::from_seed(seed_bytes);
-                    let new_gs = GameState::new();
-                    gs.library = new_gs.library;
-                    gs.token_balances = new_gs.token_balances;
-                    gs.current_encounter = None;
-                    gs.encounter_phase = new_gs.encounter_phase;
-                    gs.last_encounter_result = None;
-                    gs.encounter_results.clear();
+        match payload {
+            ActionPayload::SetSeed { seed } => {
+                let mut seed_bytes = [0u8; 16];
+                seed_bytes[0..8].copy_from_slice(&seed.to_le_bytes());
+                seed_bytes[8..16].copy_from_slice(&seed.to_le_bytes());
+                *rng = rand_pcg::Lcg64Xsh32::from_seed(seed_bytes);
+                let new_gs = GameState::new_with_rng(rng);
+                self.library = new_gs.library;
+                self.token_balances = new_gs.token_balances;
+                self.current_encounter = None;
+                self.encounter_phase = new_gs.encounter_phase;
+                self.last_encounter_result = None;
+                self.encounter_results.clear();
+                self.encounter_rewards.clear();
+                self.equipped_tools.clear();
+                self.stored_tool_durability.clear();
+            }
+            ActionPayload::DrawEncounter { encounter_id } => {
+                if let Ok(card_id) = encounter_id.parse::<usize>() {
+                    let _ = self.start_picked_encounter(card_id, rng);
                 }
-                ActionPayload::DrawEncounter { encounter_id } => {
-                    if let Ok(card_id) = encounter_id.parse::<usize>() {
-                        let health_key =
-                            super::types::Token::persistent(super::types::TokenType::Health);
-                        if gs.token_balances.get(&health_key).copied().unwrap_or(0) == 0 {
-                            gs.token_balances.insert(health_key, 20);
-                        }
-                        let _ = gs.library.play(card_id);
-                        // Dispatch based on encounter kind
-                        if let Some(lib_card) = gs.library.get(card_id) {
-                            match &lib_card.kind {
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Mining { .. },
-                                } => {
-                                    let _ = gs.start_mining_encounter(card_id, &mut rng);
-                                }
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Herbalism { .. },
-                                } => {
-                                    let _ = gs.start_herbalism_encounter(card_id, &mut rng);
-                                }
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Woodcutting { .. },
-                                } => {
-                                    let _ = gs.start_woodcutting_encounter(card_id, &mut rng);
-                                }
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Fishing { .. },
-                                } => {
-                                    let _ = gs.start_fishing_encounter(card_id, &mut rng);
-                                }
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Rest { .. },
-                                } => {
-                                    let _ = gs.start_rest_encounter(card_id, &mut rng);
-                                }
-                                CardKind::Encounter {
-                                    encounter_kind: EncounterKind::Crafting { .. },
-                                } => {
-                                    let _ = gs.start_crafting_encounter(card_id, &mut rng);
-                                }
-                                _ => {
-                                    let _ = gs.start_combat(card_id, &mut rng);
-                                }
+            }
+            ActionPayload::PlayCard { card_id } => {
+                // Rest encounters handle library.play() internally
+                let is_rest = matches!(&self.current_encounter, Some(EncounterState::Rest(_)));
+                if !is_rest {
+                    let _ = self.library.play(*card_id);
+                }
+                match &self.current_encounter {
+                    Some(EncounterState::Combat(_)) => {
+                        let _ = self.resolve_player_card(*card_id, rng);
+                        if self.current_encounter.is_some() {
+                            let _ = self.resolve_enemy_play(rng);
+                            if self.current_encounter.is_some() {
+                                let _ = self.advance_combat_phase();
                             }
                         }
                     }
-                }
-                ActionPayload::PlayCard { card_id } => {
-                    // Rest encounters handle library.play() internally
-                    let is_rest = matches!(&gs.current_encounter, Some(EncounterState::Rest(_)));
-                    if !is_rest {
-                        let _ = gs.library.play(*card_id);
+                    Some(EncounterState::Mining(_)) => {
+                        let _ = self.resolve_player_mining_card(*card_id, rng);
                     }
-                    match &gs.current_encounter {
-                        Some(EncounterState::Combat(_)) => {
-                            let _ = gs.resolve_player_card(*card_id, &mut rng);
-                            if gs.current_encounter.is_some() {
-                                let _ = gs.resolve_enemy_play(&mut rng);
-                                if gs.current_encounter.is_some() {
-                                    let _ = gs.advance_combat_phase();
-                                }
-                            }
-                        }
-                        Some(EncounterState::Mining(_)) => {
-                            let _ = gs.resolve_player_mining_card(*card_id, &mut rng);
-                        }
-                        Some(EncounterState::Herbalism(_)) => {
-                            let _ = gs.resolve_player_herbalism_card(*card_id, &mut rng);
-                        }
-                        Some(EncounterState::Woodcutting(_)) => {
-                            let _ = gs.resolve_player_woodcutting_card(*card_id, &mut rng);
-                        }
-                        Some(EncounterState::Fishing(_)) => {
-                            let _ = gs.resolve_player_fishing_card(*card_id, &mut rng);
-                        }
-                        Some(EncounterState::Rest(_)) => {
-                            let _ = gs.resolve_rest_card_play(*card_id, &mut rng);
-                        }
-                        Some(EncounterState::Crafting(_)) => {
-                            let _ = gs.resolve_crafting_play_card(*card_id, &mut rng);
-                        }
-                        None => {}
+                    Some(EncounterState::Herbalism(_)) => {
+                        let _ = self.resolve_player_herbalism_card(*card_id, rng);
                     }
-                }
-                ActionPayload::ApplyScouting { .. } => {
-                    if let Some(ref enc) = gs.current_encounter {
-                        let enc_id = enc.encounter_card_id();
-                        let _ = gs.library.return_to_deck(enc_id);
+                    Some(EncounterState::Woodcutting(_)) => {
+                        let _ = self.resolve_player_woodcutting_card(*card_id, rng);
                     }
-                    let foresight = gs
-                        .token_balances
-                        .get(&super::types::Token::persistent(
-                            super::types::TokenType::Foresight,
-                        ))
-                        .copied()
-                        .unwrap_or(3) as usize;
-                    gs.library.encounter_draw_to_hand(foresight);
-                    gs.encounter_phase = super::types::EncounterPhase::NoEncounter;
-                }
-                ActionPayload::AbortEncounter => {
-                    if matches!(&gs.current_encounter, Some(EncounterState::Rest(_))) {
-                        gs.abort_rest_encounter();
-                    } else if matches!(&gs.current_encounter, Some(EncounterState::Crafting(_))) {
-                        gs.abort_crafting_encounter();
-                    } else {
-                        gs.abort_encounter();
+                    Some(EncounterState::Fishing(_)) => {
+                        let _ = self.resolve_player_fishing_card(*card_id, rng);
                     }
-                }
-                ActionPayload::ConcludeEncounter => match &gs.current_encounter {
-                    Some(EncounterState::Mining(_)) => {
-                        let _ = gs.conclude_mining_encounter();
+                    Some(EncounterState::Rest(_)) => {
+                        let _ = self.resolve_rest_card_play(*card_id, rng);
                     }
                     Some(EncounterState::Crafting(_)) => {
-                        let _ = gs.conclude_crafting_encounter();
+                        let _ = self.resolve_crafting_play_card(*card_id, rng);
                     }
-                    _ => {}
-                },
-                ActionPayload::CraftSwap { from_id, to_id } => {
-                    let _ = gs.resolve_crafting_swap(*from_id, *to_id);
+                    None => {}
                 }
-                ActionPayload::CraftCard { target_card_id } => {
-                    let _ = gs.resolve_crafting_start_craft(*target_card_id);
+            }
+            ActionPayload::ApplyScouting { .. } => {
+                if let Some(ref enc) = self.current_encounter {
+                    let enc_id = enc.encounter_card_id();
+                    let _ = self.library.return_to_deck(enc_id);
                 }
-                ActionPayload::CraftDurability { discipline } => {
-                    let _ = gs.resolve_crafting_add_durability(discipline);
+                let foresight = self
+                    .token_balances
+                    .get(&super::types::Token::persistent(
+                        super::types::TokenType::Foresight,
+                    ))
+                    .copied()
+                    .unwrap_or(3) as usize;
+                self.library.encounter_draw_to_hand(foresight);
+                self.encounter_phase = super::types::EncounterPhase::NoEncounter;
+            }
+            ActionPayload::RetreatEncounter => {
+                let _ = self.retreat_from_combat();
+            }
+            ActionPayload::AbortEncounter => {
+                if matches!(&self.current_encounter, Some(EncounterState::Rest(_))) {
+                    self.abort_rest_encounter();
+                } else if matches!(&self.current_encounter, Some(EncounterState::Crafting(_))) {
+                    self.abort_crafting_encounter();
+                } else {
+                    self.abort_encounter();
                 }
             }
-            match gs.action_log.entries.lock() {
-                Ok(mut g) => g.push(e.clone()),
-                Err(err) => err.into_inner().push(e.clone()),
-            };
-            let cur = gs.action_log.seq.load(Ordering::SeqCst);
-            if cur < e.seq {
-                gs.action_log.seq.store(e.seq, Ordering::SeqCst);
+            ActionPayload::ConcludeEncounter => match &self.current_encounter {
+                Some(EncounterState::Mining(_)) => {
+                    let _ = self.conclude_mining_encounter();
+                }
+                Some(EncounterState::Crafting(_)) => {
+                    let _ = self.conclude_crafting_encounter();
+                }
+                _ => {}
+            },
+            ActionPayload::CraftSwap { from_id, to_id } => {
+                let _ = self.resolve_crafting_swap(*from_id, *to_id);
             }
+            ActionPayload::CraftCard { target_card_id } => {
+                let _ = self.resolve_crafting_start_craft(*target_card_id);
+            }
+            ActionPayload::CraftDurability { discipline } => {
+                let _ = self.resolve_crafting_add_durability(discipline);
+            }
+            ActionPayload::FishingBait { bait } => {
+                let _ = self.resolve_fishing_bait(bait, rng);
+            }
+            ActionPayload::Repair { discipline } => {
+                let _ = self.resolve_repair(discipline);
+            }
+            ActionPayload::EquipTool { card_id } => {
+                let _ = self.equip_tool(*card_id);
+            }
+            ActionPayload::UnequipTool { discipline } => {
+                let _ = self.unequip_tool(discipline);
+            }
+            ActionPayload::Rewind { .. } => {}
         }
-        gs
     }
 
-    /// Graceful shutdown helper to flush and close any background writer.
-    pub fn shutdown(&self) {
-        if let Some(w) = &self.action_log.writer {
-            w.close();
+    /// A new in-memory game (log, state and RNG) rebuilt by replaying the logged actions up to
+    /// and including `to_seq`. `self` is left untouched.
+    pub fn replay_prefix(&self, to_seq: u64) -> Result<(Self, rand_pcg::Lcg64Xsh32), String> {
+        let last_seq = self.action_log.seq.load(Ordering::SeqCst);
+        if to_seq > last_seq {
+            return Err(format!(
+                "seq {} is beyond the last logged seq {}",
+                to_seq, last_seq
+            ));
+        }
+        let kept = ActionLog::new();
+        for e in self.action_log.entries() {
+            if e.seq <= to_seq {
+                kept.record(e);
+            }
+        }
+        Ok(Self::replay_with_rng(&kept))
+    }
+
+    /// Discard every logged action after `to_seq` and rebuild the state by replaying the rest.
+    /// The log's sinks get a `Rewind` marker entry and a persisted log loses the snapshots
+    /// taken after `to_seq`. Returns the RNG as it stands after `to_seq`.
+    pub fn rewind(&mut self, to_seq: u64) -> Result<rand_pcg::Lcg64Xsh32, String> {
+        let (mut rewound, rng) = self
+            .replay_prefix(to_seq)
+            .map_err(|e| format!("Cannot rewind: {}", e))?;
+        self.action_log.write_to_sinks(&ActionEntry::new(
+            to_seq,
+            &rewound.action_log.head_hash(),
+            ActionPayload::Rewind { to_seq },
+        ));
+        if let Some(path) = self.action_log.file_path() {
+            crate::action::persistence::discard_snapshots_after(path, to_seq);
         }
+        // A fresh log with the sinks attached, rather than mutating the possibly shared Arc
+        let mut log = ActionLog::clone(&rewound.action_log);
+        for sink in self.action_log.sinks() {
+            log.add_sink(sink.clone());
+        }
+        rewound.action_log = std::sync::Arc::new(log);
+        *self = rewound;
+        Ok(rng)
+    }
+
+    /// Graceful shutdown helper to flush and close the action log's sinks.
+    pub fn shutdown(&self) {
+        self.action_log.close_sinks();
     }
 }
 
@@ -848,6 +1038,16 @@ pub(crate) fn deck_shuffle_hand<T: HasDeckCounts>(rng: &mut rand_pcg::Lcg64Xsh32
 pub(crate) fn deck_play_random<T: HasDeckCounts>(
     rng: &mut rand_pcg::Lcg64Xsh32,
     cards: &mut [T],
+) -> Option<usize> {
+    deck_play_random_matching(rng, cards, |_| true)
+}
+
+/// Like `deck_play_random`, but only picks among hand cards accepted by `playable`.
+/// Returns None when no hand card is playable.
+pub(crate) fn deck_play_random_matching<T: HasDeckCounts>(
+    rng: &mut rand_pcg::Lcg64Xsh32,
+    cards: &mut [T],
+    playable: impl Fn(&T) -> bool,
 ) -> Option<usize> {
     use rand::RngCore;
     let total_hand: u32 = cards.iter().map(|c| c.hand_count()).sum();
@@ -861,12 +1061,19 @@ pub(crate) fn deck_play_random<T: HasDeckCounts>(
             *card.discard_count_mut() = 0;
         }
     }
-    let total_hand: u32 = cards.iter().map(|c| c.hand_count()).sum();
-    if total_hand == 0 {
+    let total_playable: u32 = cards
+        .iter()
+        .filter(|c| playable(c))
+        .map(|c| c.hand_count())
+        .sum();
+    if total_playable == 0 {
         return None;
     }
-    let mut pick = (rng.next_u64() as u32) % total_hand;
+    let mut pick = (rng.next_u64() as u32) % total_playable;
     for (i, card) in cards.iter_mut().enumerate() {
+        if !playable(card) {
+            continue;
+        }
         if pick < card.hand_count() {
             *card.hand_count_mut() -= 1;
             *card.discard_count_mut() += 1;
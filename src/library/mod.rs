@@ -3,11 +3,15 @@
 //! This file provides small, well-scoped domain primitives used by higher-level systems.
 
 pub mod action_log;
+pub mod compact_log;
 pub(crate) mod disciplines;
 mod endpoints;
 pub mod game_state;
+pub mod snapshot;
+pub mod state_digest;
 pub mod types;
 
+pub use disciplines::woodcutting::simulate_woodcutting_patterns;
 pub use endpoints::{
     add_test_library_card, list_card_effects, list_library_cards,
     okapi_add_operation_for_list_card_effects_, CardEffectEntry, CardEffectsResponse,
@@ -64,6 +68,17 @@ fn calculate_crafting_cost(kind: &CardKind) -> HashMap<types::TokenType, i64> {
                 total_power += r.amount.abs();
             }
         }
+        // A tool applies its modifiers on every use, so its power scales with durability
+        CardKind::Tool {
+            modifiers,
+            durability,
+            ..
+        } => {
+            num_effects = modifiers.len() as i64;
+            for m in modifiers {
+                total_power += m.amount.abs() * *durability as i64 / 4;
+            }
+        }
         // Non-player cards have no crafting cost
         CardKind::Encounter { .. }
         | CardKind::PlayerCardEffect { .. }
@@ -95,7 +110,8 @@ fn calculate_crafting_cost(kind: &CardKind) -> HashMap<types::TokenType, i64> {
 
 /// The Library: canonical collection of all player-owned cards.
 /// Index in the Vec = card ID. Per vision "card location model and counts".
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, rocket::serde::Serialize, rocket::serde::Deserialize)]
+#[serde(crate = "rocket::serde")]
 pub struct Library {
     pub cards: Vec<LibraryCard>,
 }
@@ -337,6 +353,101 @@ impl Library {
                             }
                         }
                     }
+                    for entry in combatant_def
+                        .loot_table
+                        .iter()
+                        .flat_map(|table| &table.entries)
+                    {
+                        if entry.min < 0 || entry.min > entry.max {
+                            errors.push(format!(
+                                "Card {} has invalid loot range {}..={} for {:?}",
+                                id, entry.min, entry.max, entry.token_type
+                            ));
+                        }
+                    }
+                    if let types::RetreatCost::MaterialFraction { percent } =
+                        combatant_def.retreat_cost
+                    {
+                        if !(1..=100).contains(&percent) {
+                            errors.push(format!(
+                                "Card {} has retreat material fraction {}%, expected 1..=100",
+                                id, percent
+                            ));
+                        }
+                    }
+                }
+                CardKind::Encounter {
+                    encounter_kind: EncounterKind::Mining { mining_def },
+                } => {
+                    for hazard in mining_def.ore_deck.iter().filter_map(|o| o.hazard.as_ref()) {
+                        let valid = match hazard {
+                            types::OreHazard::CaveIn { yield_percent } => *yield_percent <= 100,
+                            types::OreHazard::Gas { stamina_per_turn } => *stamina_per_turn > 0,
+                            types::OreHazard::GemVein { amount, turns, .. } => {
+                                *amount > 0 && *turns > 0
+                            }
+                        };
+                        if !valid {
+                            errors.push(format!("Card {} has invalid ore hazard {:?}", id, hazard));
+                        }
+                    }
+                }
+                CardKind::Encounter {
+                    encounter_kind: EncounterKind::Fishing { fishing_def },
+                } => {
+                    for species in &fishing_def.species {
+                        for behavior in &species.behaviors {
+                            let valid = match behavior {
+                                types::FishBehavior::Greedy { min_fish_amount } => {
+                                    *min_fish_amount >= 1
+                                }
+                                types::FishBehavior::Skittish { lost_turns } => *lost_turns > 0,
+                                types::FishBehavior::Telegraphed => true,
+                            };
+                            if !valid {
+                                errors.push(format!(
+                                    "Card {} has invalid behavior {:?} for fish species {}",
+                                    id, behavior, species.name
+                                ));
+                            }
+                        }
+                    }
+                }
+                CardKind::Tool {
+                    modifiers,
+                    durability,
+                    ..
+                } if modifiers.is_empty() || *durability == 0 => {
+                    errors.push(format!(
+                        "Card {} is a tool without modifiers or durability",
+                        id
+                    ));
+                }
+                CardKind::Mining {
+                    mining_effect: types::MiningCardEffect { drains, .. },
+                }
+                | CardKind::Herbalism {
+                    herbalism_effect: types::HerbalismCardEffect { drains, .. },
+                }
+                | CardKind::Woodcutting {
+                    woodcutting_effect: types::WoodcuttingCardEffect { drains, .. },
+                }
+                | CardKind::Fishing {
+                    fishing_effect: types::FishingCardEffect { drains, .. },
+                } => {
+                    for drain in drains {
+                        if !(1..=100).contains(&drain.percent) {
+                            errors.push(format!(
+                                "Card {} has invalid drain percent {}%",
+                                id, drain.percent
+                            ));
+                        }
+                    }
+                }
+                CardKind::PlayerCardEffect { kind } | CardKind::EnemyCardEffect { kind } => {
+                    if let Err(e) = validate_effect_template(kind) {
+                        errors.push(format!("Card effect {} {}", id, e));
+                    }
                 }
                 _ => {}
             }
@@ -348,3 +459,50 @@ impl Library {
         }
     }
 }
+
+/// Check the roll ranges of conditional, triggered and drain effect templates.
+fn validate_effect_template(kind: &types::CardEffectKind) -> Result<(), String> {
+    match kind {
+        types::CardEffectKind::ConditionalLoseTokens {
+            condition,
+            min,
+            max,
+            ..
+        }
+        | types::CardEffectKind::ConditionalGainTokens {
+            condition,
+            min,
+            max,
+            ..
+        } => {
+            if *min < 0 || min > max {
+                return Err(format!("has invalid range {}..={}", min, max));
+            }
+            if let types::EffectCondition::OwnHealthBelowPercent { percent } = condition {
+                if !(1..=100).contains(percent) {
+                    return Err(format!("has invalid health threshold {}%", percent));
+                }
+            }
+            Ok(())
+        }
+        types::CardEffectKind::ReflectDamage {
+            min_percent,
+            max_percent,
+        } => {
+            if min_percent > max_percent || *max_percent > 100 {
+                return Err(format!(
+                    "has invalid reflect range {}%..={}%",
+                    min_percent, max_percent
+                ));
+            }
+            Ok(())
+        }
+        types::CardEffectKind::Drain { percent, .. } => {
+            if !(1..=100).contains(percent) {
+                return Err(format!("has invalid drain percent {}%", percent));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
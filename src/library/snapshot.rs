@@ -0,0 +1,152 @@
+//! Game state snapshots, written periodically next to the action log so that resuming a game
+//! replays only the entries logged after the latest snapshot.
+
+use super::action_log::ActionLog;
+use super::state_digest::{ReplayDivergence, StateDigest};
+use super::types::{
+    token_map_serde, Discipline, EncounterOutcome, EncounterPhase, EncounterState, EquippedTool,
+    RewardBreakdown, Token,
+};
+use super::{GameState, Library};
+use crate::action::persistence;
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+/// Logged actions between two snapshots unless `ACTION_LOG_SNAPSHOT_INTERVAL` says otherwise.
+const DEFAULT_SNAPSHOT_INTERVAL: u64 = 100;
+
+/// Everything replay would rebuild from the log entries up to and including `last_seq`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct GameSnapshot {
+    pub last_seq: u64,
+    #[serde(with = "token_map_serde")]
+    pub token_balances: HashMap<Token, i64>,
+    pub library: Library,
+    pub current_encounter: Option<EncounterState>,
+    pub encounter_phase: EncounterPhase,
+    pub last_encounter_result: Option<EncounterOutcome>,
+    pub encounter_results: Vec<EncounterOutcome>,
+    pub encounter_rewards: HashMap<usize, RewardBreakdown>,
+    pub equipped_tools: HashMap<Discipline, EquippedTool>,
+    pub stored_tool_durability: HashMap<usize, u32>,
+    pub rng: rand_pcg::Lcg64Xsh32,
+}
+
+fn snapshot_interval() -> u64 {
+    std::env::var("ACTION_LOG_SNAPSHOT_INTERVAL")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .filter(|interval| *interval > 0)
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL)
+}
+
+impl GameState {
+    /// Capture the state after the latest logged action, together with the game RNG.
+    pub fn snapshot(&self, rng: &rand_pcg::Lcg64Xsh32) -> GameSnapshot {
+        GameSnapshot {
+            last_seq: self.action_log.seq.load(Ordering::SeqCst),
+            token_balances: self.token_balances.clone(),
+            library: self.library.clone(),
+            current_encounter: self.current_encounter.clone(),
+            encounter_phase: self.encounter_phase.clone(),
+            last_encounter_result: self.last_encounter_result.clone(),
+            encounter_results: self.encounter_results.clone(),
+            encounter_rewards: self.encounter_rewards.clone(),
+            equipped_tools: self.equipped_tools.clone(),
+            stored_tool_durability: self.stored_tool_durability.clone(),
+            rng: rng.clone(),
+        }
+    }
+
+    fn from_snapshot(snapshot: &GameSnapshot) -> (Self, rand_pcg::Lcg64Xsh32) {
+        let gs = GameState {
+            action_log: std::sync::Arc::new(ActionLog::new()),
+            token_balances: snapshot.token_balances.clone(),
+            library: snapshot.library.clone(),
+            current_encounter: snapshot.current_encounter.clone(),
+            encounter_phase: snapshot.encounter_phase.clone(),
+            last_encounter_result: snapshot.last_encounter_result.clone(),
+            encounter_results: snapshot.encounter_results.clone(),
+            encounter_rewards: snapshot.encounter_rewards.clone(),
+            equipped_tools: snapshot.equipped_tools.clone(),
+            stored_tool_durability: snapshot.stored_tool_durability.clone(),
+        };
+        (gs, snapshot.rng.clone())
+    }
+
+    /// Rebuild the state for `log`, starting from `snapshot` if given. Entries up to the
+    /// snapshot's `last_seq` are only recorded in the new log; later ones are replayed.
+    /// The first replayed entry whose recorded digest differs is reported on stderr.
+    pub fn resume_from_snapshot(
+        snapshot: Option<&GameSnapshot>,
+        log: &ActionLog,
+    ) -> (Self, rand_pcg::Lcg64Xsh32) {
+        let mut reported = false;
+        Self::resume_with(snapshot, log, |seq, recorded, replayed| {
+            let differences = recorded.diff(replayed);
+            if !reported && !differences.is_empty() {
+                eprintln!("{}", ReplayDivergence { seq, differences });
+                reported = true;
+            }
+            true
+        })
+    }
+
+    /// Replay loop behind `resume_from_snapshot`: `check(seq, recorded, replayed)` is called
+    /// for every replayed entry that has a digest, and returning false stops the replay.
+    pub(crate) fn resume_with(
+        snapshot: Option<&GameSnapshot>,
+        log: &ActionLog,
+        mut check: impl FnMut(u64, &StateDigest, &StateDigest) -> bool,
+    ) -> (Self, rand_pcg::Lcg64Xsh32) {
+        use rand::SeedableRng;
+
+        let (mut gs, mut rng) = match snapshot {
+            Some(snapshot) => Self::from_snapshot(snapshot),
+            None => (GameState::new(), rand_pcg::Lcg64Xsh32::from_seed([0u8; 16])),
+        };
+        let resume_after = snapshot.map_or(0, |s| s.last_seq);
+        for e in log.entries() {
+            if e.seq > resume_after {
+                gs.replay_entry(&e.payload, &mut rng);
+                if let Some(recorded) = &e.digest {
+                    if !check(e.seq, recorded, &gs.digest(&rng)) {
+                        gs.action_log.record(e);
+                        break;
+                    }
+                }
+            }
+            gs.action_log.record(e);
+        }
+        (gs, rng)
+    }
+
+    /// Load the NDJSON action log at `path` and resume from the newest snapshot next to it
+    /// whose `last_seq` is an entry of that log, replaying only the entries after it. Without
+    /// a valid snapshot the whole log is replayed.
+    pub fn resume_from_file(path: &str) -> Result<(Self, rand_pcg::Lcg64Xsh32), String> {
+        let log = ActionLog::load_from_file(path)?;
+        let entries = log.entries();
+        let snapshot = persistence::load_snapshots(std::path::Path::new(path))
+            .into_iter()
+            .find(|s| s.last_seq == 0 || entries.iter().any(|e| e.seq == s.last_seq));
+        Ok(Self::resume_from_snapshot(snapshot.as_ref(), &log))
+    }
+
+    /// Write a snapshot next to the action log file every `ACTION_LOG_SNAPSHOT_INTERVAL`
+    /// logged actions (default 100). Does nothing when the log isn't persisted.
+    pub fn write_snapshot_if_due(&self, rng: &rand_pcg::Lcg64Xsh32) {
+        let Some(path) = self.action_log.file_path() else {
+            return;
+        };
+        let seq = self.action_log.seq.load(Ordering::SeqCst);
+        if seq == 0 || !seq.is_multiple_of(snapshot_interval()) {
+            return;
+        }
+        if let Err(e) = persistence::write_snapshot(path, &self.snapshot(rng)) {
+            eprintln!("ActionLog snapshot at seq {} failed: {}", seq, e);
+        }
+    }
+}
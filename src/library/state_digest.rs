@@ -0,0 +1,173 @@
+//! Post-action state digests recorded with each logged action, so that replay can point at the
+//! first action whose outcome it doesn't reproduce.
+
+use super::action_log::ActionLog;
+use super::snapshot::GameSnapshot;
+use super::types::{token_map_serde, EncounterOutcome, Token};
+use super::GameState;
+use rocket::serde::{Deserialize, Serialize};
+use rocket_okapi::JsonSchema;
+use std::collections::HashMap;
+
+/// Hex digits kept from the SHA-256 behind `library_hash` and `rng_hash`.
+const SHORT_HASH_LEN: usize = 16;
+
+/// Compact summary of the game state right after an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct StateDigest {
+    #[serde(with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub token_balances: HashMap<Token, i64>,
+    pub last_encounter_result: Option<EncounterOutcome>,
+    /// Hash of every library card's deck/hand/discard/library counts, in card id order.
+    pub library_hash: String,
+    /// Hash of the RNG state, which pins how many draws were made.
+    pub rng_hash: String,
+}
+
+/// One digest field that replay reproduced differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct FieldDiff {
+    /// `token_balances.<token>` for a single balance, otherwise the digest field name.
+    pub field: String,
+    pub recorded: String,
+    pub replayed: String,
+}
+
+/// The first logged action whose replayed outcome differs from its recorded digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReplayDivergence {
+    pub seq: u64,
+    pub differences: Vec<FieldDiff>,
+}
+
+impl std::fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Replay diverges at seq {}:", self.seq)?;
+        for d in &self.differences {
+            write!(
+                f,
+                " {} recorded {} replayed {};",
+                d.field, d.recorded, d.replayed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn short_hash(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()[..SHORT_HASH_LEN]
+        .to_string()
+}
+
+impl StateDigest {
+    /// Field-level differences from `replayed`, `self` being the recorded digest.
+    pub fn diff(&self, replayed: &StateDigest) -> Vec<FieldDiff> {
+        let mut differences = Vec::new();
+        let mut tokens: Vec<&Token> = self
+            .token_balances
+            .keys()
+            .chain(replayed.token_balances.keys())
+            .collect();
+        tokens.sort_by_key(|t| format!("{:?}", t));
+        tokens.dedup();
+        for token in tokens {
+            let recorded = self.token_balances.get(token).copied().unwrap_or(0);
+            let replayed = replayed.token_balances.get(token).copied().unwrap_or(0);
+            if recorded != replayed {
+                differences.push(FieldDiff {
+                    field: format!(
+                        "token_balances.{:?}:{:?}",
+                        token.token_type, token.lifecycle
+                    ),
+                    recorded: recorded.to_string(),
+                    replayed: replayed.to_string(),
+                });
+            }
+        }
+        if self.last_encounter_result != replayed.last_encounter_result {
+            differences.push(FieldDiff {
+                field: "last_encounter_result".to_string(),
+                recorded: format!("{:?}", self.last_encounter_result),
+                replayed: format!("{:?}", replayed.last_encounter_result),
+            });
+        }
+        for (field, recorded, replayed) in [
+            ("library_hash", &self.library_hash, &replayed.library_hash),
+            ("rng_hash", &self.rng_hash, &replayed.rng_hash),
+        ] {
+            if recorded != replayed {
+                differences.push(FieldDiff {
+                    field: field.to_string(),
+                    recorded: recorded.clone(),
+                    replayed: replayed.clone(),
+                });
+            }
+        }
+        differences
+    }
+}
+
+impl GameState {
+    /// Digest of the current state and `rng`.
+    pub fn digest(&self, rng: &rand_pcg::Lcg64Xsh32) -> StateDigest {
+        let counts: String = self
+            .library
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(id, c)| {
+                format!(
+                    "{}:{}:{}:{}:{};",
+                    id, c.counts.library, c.counts.deck, c.counts.hand, c.counts.discard
+                )
+            })
+            .collect();
+        StateDigest {
+            token_balances: self
+                .token_balances
+                .iter()
+                .filter(|(_, v)| **v != 0)
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+            last_encounter_result: self.last_encounter_result.clone(),
+            library_hash: short_hash(&counts),
+            rng_hash: short_hash(&serde_json::to_string(rng).unwrap_or_default()),
+        }
+    }
+
+    /// Replay `log` like `replay_with_rng`, but stop at the first entry whose recorded digest
+    /// differs from the replayed state. Entries without a digest are not checked.
+    pub fn replay_checked(
+        log: &ActionLog,
+    ) -> Result<(Self, rand_pcg::Lcg64Xsh32), ReplayDivergence> {
+        Self::resume_checked(None, log)
+    }
+
+    /// `resume_from_snapshot` that fails on the first diverging digest.
+    pub(crate) fn resume_checked(
+        snapshot: Option<&GameSnapshot>,
+        log: &ActionLog,
+    ) -> Result<(Self, rand_pcg::Lcg64Xsh32), ReplayDivergence> {
+        let mut divergence = None;
+        let resumed = Self::resume_with(snapshot, log, |seq, recorded, replayed| {
+            let differences = recorded.diff(replayed);
+            if differences.is_empty() {
+                return true;
+            }
+            divergence = Some(ReplayDivergence { seq, differences });
+            false
+        });
+        match divergence {
+            Some(divergence) => Err(divergence),
+            None => Ok(resumed),
+        }
+    }
+}
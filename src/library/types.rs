@@ -31,6 +31,10 @@ pub enum TokenType {
     HerbalismDurability,
     WoodcuttingDurability,
     FishingDurability,
+    MaxMiningDurability,
+    MaxHerbalismDurability,
+    MaxWoodcuttingDurability,
+    MaxFishingDurability,
     // Material tokens (produced by gathering)
     Ore,
     Plant,
@@ -66,6 +70,8 @@ pub enum TokenType {
     CraftingMaxHand,
     // Death tracking
     PlayerDeaths,
+    // Herbalism guard: spares half the plants of the next herbalism play
+    HerbalismGuard,
 }
 
 /// All known token types.
@@ -90,6 +96,10 @@ impl TokenType {
             TokenType::HerbalismDurability,
             TokenType::WoodcuttingDurability,
             TokenType::FishingDurability,
+            TokenType::MaxMiningDurability,
+            TokenType::MaxHerbalismDurability,
+            TokenType::MaxWoodcuttingDurability,
+            TokenType::MaxFishingDurability,
             TokenType::Ore,
             TokenType::Plant,
             TokenType::Lumber,
@@ -115,9 +125,18 @@ impl TokenType {
             TokenType::CraftingToken,
             TokenType::CraftingMaxHand,
             TokenType::PlayerDeaths,
+            TokenType::HerbalismGuard,
         ]
     }
 
+    /// Lifecycle used when a token of this type is granted without an explicit one.
+    pub fn default_lifecycle(&self) -> TokenLifecycle {
+        match self {
+            TokenType::HerbalismGuard => TokenLifecycle::SingleUse,
+            _ => TokenLifecycle::PersistentCounter,
+        }
+    }
+
     pub fn is_gathering_material(&self) -> bool {
         matches!(
             self,
@@ -134,6 +153,118 @@ impl TokenType {
                 | TokenType::FishingDurability
         )
     }
+
+    /// The token holding this pool's maximum, for pools that are capped.
+    pub fn max_token(&self) -> Option<TokenType> {
+        if *self == TokenType::Health {
+            return Some(TokenType::MaxHealth);
+        }
+        Discipline::ALL
+            .iter()
+            .find(|d| d.durability_token() == *self)
+            .map(|d| d.max_durability_token())
+    }
+}
+
+/// Clamp a capped pool (see `TokenType::max_token`) to its maximum in `balances`.
+/// Balances without the maximum token (e.g. an enemy with no MaxHealth) are left uncapped.
+pub fn clamp_to_max(balances: &mut HashMap<Token, i64>, token_type: &TokenType) {
+    if let Some(max_token) = token_type.max_token() {
+        if !balances.keys().any(|t| t.token_type == max_token) {
+            return;
+        }
+        let max = token_balance_by_type(balances, &max_token);
+        let entry = token_entry_by_type(balances, token_type);
+        *entry = (*entry).min(max);
+    }
+}
+
+/// A tool equipped in its discipline's slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct EquippedTool {
+    pub discipline: Discipline,
+    pub card_id: usize,
+    pub durability_left: u32,
+}
+
+/// A gathering discipline with its own durability pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum Discipline {
+    Mining,
+    Herbalism,
+    Woodcutting,
+    Fishing,
+}
+
+impl Discipline {
+    pub const ALL: [Discipline; 4] = [
+        Discipline::Mining,
+        Discipline::Herbalism,
+        Discipline::Woodcutting,
+        Discipline::Fishing,
+    ];
+
+    /// Durability restored per material token spent on a repair.
+    pub const REPAIR_DURABILITY_PER_MATERIAL: i64 = 10;
+
+    pub fn durability_token(&self) -> TokenType {
+        match self {
+            Discipline::Mining => TokenType::MiningDurability,
+            Discipline::Herbalism => TokenType::HerbalismDurability,
+            Discipline::Woodcutting => TokenType::WoodcuttingDurability,
+            Discipline::Fishing => TokenType::FishingDurability,
+        }
+    }
+
+    pub fn max_durability_token(&self) -> TokenType {
+        match self {
+            Discipline::Mining => TokenType::MaxMiningDurability,
+            Discipline::Herbalism => TokenType::MaxHerbalismDurability,
+            Discipline::Woodcutting => TokenType::MaxWoodcuttingDurability,
+            Discipline::Fishing => TokenType::MaxFishingDurability,
+        }
+    }
+
+    /// Material spent to restore this discipline's durability.
+    pub fn repair_material(&self) -> TokenType {
+        match self {
+            Discipline::Mining | Discipline::Fishing => TokenType::Ore,
+            Discipline::Herbalism | Discipline::Woodcutting => TokenType::Lumber,
+        }
+    }
+}
+
+/// Life steal for gathering cards: after the card's gains resolve, `percent` of the amount
+/// resolved for `source_token` is granted as `target_token`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct DrainEffect {
+    pub source_token: TokenType,
+    pub target_token: TokenType,
+    pub percent: u32,
+}
+
+impl DrainEffect {
+    /// Amount of `target_token` recovered from the amounts resolved so far.
+    pub fn recovery(&self, resolved: &HashMap<TokenType, i64>) -> i64 {
+        let source = resolved.get(&self.source_token).copied().unwrap_or(0);
+        source.max(0) * self.percent as i64 / 100
+    }
+}
+
+/// Turn drains into gains so each resolver routes them like its regular gains.
+pub fn drain_gains(drains: &[DrainEffect], resolved: &HashMap<TokenType, i64>) -> Vec<TokenAmount> {
+    drains
+        .iter()
+        .map(|drain| TokenAmount {
+            token_type: drain.target_token.clone(),
+            amount: drain.recovery(resolved),
+            cap: None,
+        })
+        .filter(|gain| gain.amount > 0)
+        .collect()
 }
 
 /// Split gathering costs into pre-play costs (reject card if unaffordable)
@@ -161,6 +292,14 @@ impl Token {
         }
     }
 
+    /// Create a token with its type's default lifecycle.
+    pub fn with_default_lifecycle(token_type: TokenType) -> Self {
+        Token {
+            lifecycle: token_type.default_lifecycle(),
+            token_type,
+        }
+    }
+
     /// Create a dodge token with its unique lifecycle.
     pub fn dodge() -> Self {
         Token {
@@ -210,6 +349,43 @@ pub enum CardEffectKind {
         defence: u32,
         resource: u32,
     },
+    /// Lose tokens only when `condition` holds at resolution time.
+    /// Health losses run through the same Dodge → Shield → Health pipeline as LoseTokens.
+    ConditionalLoseTokens {
+        condition: EffectCondition,
+        target: EffectTarget,
+        token_type: TokenType,
+        min: i64,
+        max: i64,
+    },
+    /// Gain tokens only when `condition` holds at resolution time.
+    ConditionalGainTokens {
+        condition: EffectCondition,
+        target: EffectTarget,
+        token_type: TokenType,
+        min: i64,
+        max: i64,
+    },
+    /// Register an on-hit trigger on the card owner for the rest of the combat round.
+    /// Each Health hit taken reflects the rolled percentage of the incoming damage to the attacker.
+    ReflectDamage { min_percent: u32, max_percent: u32 },
+    /// Convert `percent` of the `source_token` amount resolved by the card's earlier effects
+    /// (damage dealt to the opponent, tokens gained by the owner) into `target_token` for the owner.
+    Drain {
+        source_token: TokenType,
+        target_token: TokenType,
+        percent: u32,
+    },
+}
+
+/// Condition checked from the card owner's point of view when a conditional effect resolves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum EffectCondition {
+    /// The opponent has no Shield left.
+    OpponentShieldZero,
+    /// The owner's Health is below `percent` of their MaxHealth.
+    OwnHealthBelowPercent { percent: u32 },
 }
 
 /// Cost definition on a CardEffect template: a percentage range of the effect value.
@@ -325,6 +501,13 @@ pub enum CardKind {
     Crafting {
         crafting_effect: CraftingCardEffect,
     },
+    /// Equipment: while equipped, every card played in `discipline` also gains `modifiers`.
+    /// The tool breaks after `durability` such plays.
+    Tool {
+        discipline: Discipline,
+        modifiers: Vec<TokenAmount>,
+        durability: u32,
+    },
     Encounter {
         encounter_kind: EncounterKind,
     },
@@ -347,6 +530,11 @@ pub struct MiningCardEffect {
     pub costs: Vec<TokenAmount>,
     #[serde(default)]
     pub gains: Vec<TokenAmount>,
+    #[serde(default)]
+    pub drains: Vec<DrainEffect>,
+    /// Clears gas from a `OreHazard::Gas` ore card.
+    #[serde(default)]
+    pub ventilates: bool,
 }
 
 /// Inline effect for Crafting discipline cards.
@@ -402,24 +590,41 @@ pub struct HerbalismCardEffect {
     pub match_mode: HerbalismMatchMode,
     #[serde(default)]
     pub gains: Vec<TokenAmount>,
+    #[serde(default)]
+    pub drains: Vec<DrainEffect>,
 }
 
 /// A card in the plant hand. Each card has characteristics that Herbalism cards can target.
+/// `potency` is a percentage scaling the Plant reward when this plant is harvested.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct PlantCard {
     pub characteristics: Vec<PlantCharacteristic>,
     pub counts: DeckCounts,
+    #[serde(default = "PlantCard::default_potency")]
+    pub potency: u32,
+}
+
+impl PlantCard {
+    pub fn default_potency() -> u32 {
+        100
+    }
 }
 
 /// Definition of a plant node for an herbalism gathering encounter.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// Over-harvesting (no plants left) still pays `consolation_percent`% of `rewards`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct HerbalismDef {
     pub plant_hand: Vec<PlantCard>,
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub rewards: HashMap<Token, i64>,
+    #[serde(default)]
+    pub consolation_percent: u32,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
 }
 
 /// Chop types for Woodcutting discipline cards.
@@ -444,6 +649,8 @@ pub struct WoodcuttingCardEffect {
     pub costs: Vec<TokenAmount>,
     #[serde(default)]
     pub gains: Vec<TokenAmount>,
+    #[serde(default)]
+    pub drains: Vec<DrainEffect>,
 }
 
 /// Snapshot of a played woodcutting card for pattern evaluation.
@@ -455,7 +662,90 @@ pub struct PlayedWoodcuttingCard {
     pub chop_values: Vec<u32>,
 }
 
+/// Poker-inspired woodcutting patterns, listed from highest to lowest rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum WoodcuttingPattern {
+    /// 8+ chops of one type.
+    EightOfAKind,
+    /// 7+ chops of one type.
+    SevenOfAKind,
+    /// 8+ consecutive chop values.
+    PerfectStraight,
+    /// 6+ chops of one type.
+    SixOfAKind,
+    /// 6+ consecutive chop values.
+    LongStraight,
+    /// All 5 chop types present.
+    FullSet,
+    /// 5+ chops of one type.
+    FiveOfAKind,
+    /// 4+ chops of one type and 2+ of another.
+    FullHouse,
+    /// 4+ chops of one type.
+    FourOfAKind,
+    /// 4+ consecutive chop values.
+    ShortStraight,
+    /// Two chop types with 3+ each.
+    TwoPairTypes,
+    /// 4+ chops with the same value.
+    ValueQuads,
+    /// 3+ chops of one type.
+    Triple,
+    /// 3+ chops with the same value.
+    ValueTriple,
+    /// 2+ chops of one type.
+    Pair,
+    /// Fallback when nothing else matches.
+    HighCard,
+}
+
+impl WoodcuttingPattern {
+    /// Every pattern in rank order; evaluation picks the first one that matches.
+    pub const ALL: [WoodcuttingPattern; 16] = [
+        WoodcuttingPattern::EightOfAKind,
+        WoodcuttingPattern::SevenOfAKind,
+        WoodcuttingPattern::PerfectStraight,
+        WoodcuttingPattern::SixOfAKind,
+        WoodcuttingPattern::LongStraight,
+        WoodcuttingPattern::FullSet,
+        WoodcuttingPattern::FiveOfAKind,
+        WoodcuttingPattern::FullHouse,
+        WoodcuttingPattern::FourOfAKind,
+        WoodcuttingPattern::ShortStraight,
+        WoodcuttingPattern::TwoPairTypes,
+        WoodcuttingPattern::ValueQuads,
+        WoodcuttingPattern::Triple,
+        WoodcuttingPattern::ValueTriple,
+        WoodcuttingPattern::Pair,
+        WoodcuttingPattern::HighCard,
+    ];
+
+    /// The standard multiplier table used by encounters that don't define their own.
+    pub fn default_multipliers() -> HashMap<WoodcuttingPattern, f64> {
+        HashMap::from([
+            (WoodcuttingPattern::EightOfAKind, 55.0),
+            (WoodcuttingPattern::SevenOfAKind, 12.0),
+            (WoodcuttingPattern::PerfectStraight, 3.0),
+            (WoodcuttingPattern::SixOfAKind, 4.0),
+            (WoodcuttingPattern::LongStraight, 1.5),
+            (WoodcuttingPattern::FullSet, 1.0),
+            (WoodcuttingPattern::FiveOfAKind, 2.0),
+            (WoodcuttingPattern::FullHouse, 1.5),
+            (WoodcuttingPattern::FourOfAKind, 10.0),
+            (WoodcuttingPattern::ShortStraight, 2.5),
+            (WoodcuttingPattern::TwoPairTypes, 2.0),
+            (WoodcuttingPattern::ValueQuads, 12.0),
+            (WoodcuttingPattern::Triple, 2.0),
+            (WoodcuttingPattern::ValueTriple, 19.0),
+            (WoodcuttingPattern::Pair, 4.5),
+            (WoodcuttingPattern::HighCard, 1.0),
+        ])
+    }
+}
+
 /// Definition of a woodcutting encounter (no enemy deck).
+/// Patterns missing from `pattern_multipliers` are never awarded (HighCard falls back to 1.0).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct WoodcuttingDef {
@@ -463,6 +753,52 @@ pub struct WoodcuttingDef {
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub base_rewards: HashMap<Token, i64>,
+    #[serde(default = "WoodcuttingPattern::default_multipliers")]
+    pub pattern_multipliers: HashMap<WoodcuttingPattern, f64>,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
+}
+
+impl Default for WoodcuttingDef {
+    /// Matches the serde defaults, so a built def pays the standard pattern table.
+    fn default() -> Self {
+        WoodcuttingDef {
+            max_plays: 0,
+            base_rewards: HashMap::new(),
+            pattern_multipliers: WoodcuttingPattern::default_multipliers(),
+            entry_cost: vec![],
+        }
+    }
+}
+
+/// A woodcutting pattern and the multiplier it pays out in the current encounter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct WoodcuttingPatternScore {
+    pub pattern: WoodcuttingPattern,
+    pub multiplier: f64,
+}
+
+/// Patterns still reachable in the active woodcutting encounter and the best one so far.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct WoodcuttingPatternPreview {
+    pub remaining_plays: u32,
+    pub best_so_far: WoodcuttingPatternScore,
+    pub reachable: Vec<WoodcuttingPatternScore>,
+}
+
+/// Monte Carlo estimate of woodcutting pattern odds for a deck and encounter definition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct WoodcuttingSimulation {
+    pub hands: u32,
+    /// Share of hands (0.0..=1.0) whose best pattern was each pattern.
+    pub frequencies: HashMap<WoodcuttingPattern, f64>,
+    pub expected_multiplier: f64,
+    /// Expected Lumber reward per hand: base Lumber reward × expected multiplier.
+    pub expected_lumber: f64,
 }
 
 /// Inline effect for Fishing discipline cards.
@@ -475,6 +811,8 @@ pub struct FishingCardEffect {
     pub costs: Vec<TokenAmount>,
     #[serde(default)]
     pub gains: Vec<TokenAmount>,
+    #[serde(default)]
+    pub drains: Vec<DrainEffect>,
 }
 
 /// A card in the fish (enemy) deck. Each card has a numeric value.
@@ -485,8 +823,82 @@ pub struct FishCard {
     pub counts: DeckCounts,
 }
 
+/// How a fish species plays its fish deck.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde", tag = "behavior_type")]
+pub enum FishBehavior {
+    /// Plays its highest hand card instead of a random one while `FishAmount` is at least `min_fish_amount`.
+    Greedy { min_fish_amount: i64 },
+    /// Escapes (the encounter is lost) once the player has lost `lost_turns` duel turns.
+    Skittish { lost_turns: u32 },
+    /// Picks its next fish card in advance and reveals it in `FishingEncounterState::revealed_fish`.
+    Telegraphed,
+}
+
+/// A fish species that can bite at a fishing spot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct FishSpecies {
+    pub name: String,
+    #[serde(default)]
+    pub behaviors: Vec<FishBehavior>,
+    /// Paid on a win in addition to the spot's own rewards.
+    #[serde(default, with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub rewards: HashMap<Token, i64>,
+}
+
+impl FishSpecies {
+    pub fn greedy_threshold(&self) -> Option<i64> {
+        self.behaviors.iter().find_map(|b| match b {
+            FishBehavior::Greedy { min_fish_amount } => Some(*min_fish_amount),
+            _ => None,
+        })
+    }
+
+    pub fn escape_after(&self) -> Option<u32> {
+        self.behaviors.iter().find_map(|b| match b {
+            FishBehavior::Skittish { lost_turns } => Some(*lost_turns),
+            _ => None,
+        })
+    }
+
+    pub fn is_telegraphed(&self) -> bool {
+        self.behaviors.contains(&FishBehavior::Telegraphed)
+    }
+}
+
+/// Bait the player can spend Plant on before the first cast of a fishing encounter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum FishingBait {
+    /// Moves the highest-value cards out of the fish hand, so the fish plays low until it reshuffles.
+    Chum,
+    /// Widens the valid range on both sides.
+    Glowworm,
+}
+
+impl FishingBait {
+    /// Number of highest-value fish cards `Chum` moves from hand to discard.
+    pub const CHUM_DISCARDS: u32 = 3;
+    /// How far `Glowworm` moves each end of the valid range.
+    pub const GLOWWORM_WIDEN: i64 = 50;
+
+    pub fn cost(&self) -> TokenAmount {
+        let amount = match self {
+            FishingBait::Chum => 100,
+            FishingBait::Glowworm => 150,
+        };
+        TokenAmount {
+            token_type: TokenType::Plant,
+            amount,
+            cap: None,
+        }
+    }
+}
+
 /// Definition of a fishing encounter.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct FishingDef {
     pub valid_range_min: i64,
@@ -497,6 +909,13 @@ pub struct FishingDef {
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub rewards: HashMap<Token, i64>,
+    /// Species that can bite here; one is picked at random when the encounter starts.
+    /// Empty means a plain fish that plays uniformly at random.
+    #[serde(default)]
+    pub species: Vec<FishSpecies>,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
 }
 
 /// Sub-type of encounter cards.
@@ -512,12 +931,166 @@ pub enum EncounterKind {
     Crafting { crafting_def: CraftingDef },
 }
 
-/// Definition of a mining node for a gathering encounter.
+/// Which kind of encounter a card starts, as shown in encounter summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum EncounterDiscipline {
+    Combat,
+    Mining,
+    Herbalism,
+    Woodcutting,
+    Fishing,
+    Rest,
+    Crafting,
+}
+
+impl EncounterKind {
+    pub fn discipline(&self) -> EncounterDiscipline {
+        match self {
+            EncounterKind::Combat { .. } => EncounterDiscipline::Combat,
+            EncounterKind::Mining { .. } => EncounterDiscipline::Mining,
+            EncounterKind::Herbalism { .. } => EncounterDiscipline::Herbalism,
+            EncounterKind::Woodcutting { .. } => EncounterDiscipline::Woodcutting,
+            EncounterKind::Fishing { .. } => EncounterDiscipline::Fishing,
+            EncounterKind::Rest { .. } => EncounterDiscipline::Rest,
+            EncounterKind::Crafting { .. } => EncounterDiscipline::Crafting,
+        }
+    }
+
+    pub fn entry_cost(&self) -> &[TokenAmount] {
+        match self {
+            EncounterKind::Combat { combatant_def } => &combatant_def.entry_cost,
+            EncounterKind::Mining { mining_def } => &mining_def.entry_cost,
+            EncounterKind::Herbalism { herbalism_def } => &herbalism_def.entry_cost,
+            EncounterKind::Woodcutting { woodcutting_def } => &woodcutting_def.entry_cost,
+            EncounterKind::Fishing { fishing_def } => &fishing_def.entry_cost,
+            EncounterKind::Rest { rest_def } => &rest_def.entry_cost,
+            EncounterKind::Crafting { crafting_def } => &crafting_def.entry_cost,
+        }
+    }
+
+    /// Rough measure of what the encounter throws at the player. Only comparable between
+    /// encounters of the same discipline:
+    /// - Combat: the enemy's starting Health.
+    /// - Mining: average total damage of one ore card.
+    /// - Herbalism: plant characteristics to match, summed over all plants.
+    /// - Woodcutting: 0 (there is no opposing deck).
+    /// - Fishing: percentage of the turns that must be won.
+    /// - Rest: 0.
+    /// - Crafting: copies in the enemy crafting deck.
+    pub fn difficulty_score(&self) -> i64 {
+        match self {
+            EncounterKind::Combat { combatant_def } => combatant_def
+                .initial_tokens
+                .get(&Token::persistent(TokenType::Health))
+                .copied()
+                .unwrap_or(0) as i64,
+            EncounterKind::Mining { mining_def } => {
+                let copies: i64 = mining_def
+                    .ore_deck
+                    .iter()
+                    .map(|ore| ore.counts.total() as i64)
+                    .sum();
+                let damage: i64 = mining_def
+                    .ore_deck
+                    .iter()
+                    .map(|ore| {
+                        ore.damages.iter().map(|d| d.amount).sum::<i64>()
+                            * ore.counts.total() as i64
+                    })
+                    .sum();
+                if copies == 0 {
+                    0
+                } else {
+                    damage / copies
+                }
+            }
+            EncounterKind::Herbalism { herbalism_def } => herbalism_def
+                .plant_hand
+                .iter()
+                .map(|plant| plant.characteristics.len() as i64 * plant.counts.total() as i64)
+                .sum(),
+            EncounterKind::Woodcutting { .. } | EncounterKind::Rest { .. } => 0,
+            EncounterKind::Fishing { fishing_def } => {
+                if fishing_def.max_turns == 0 {
+                    0
+                } else {
+                    fishing_def.win_turns_needed as i64 * 100 / fishing_def.max_turns as i64
+                }
+            }
+            EncounterKind::Crafting { crafting_def } => crafting_def
+                .enemy_crafting_deck
+                .iter()
+                .map(|card| card.counts.total() as i64)
+                .sum(),
+        }
+    }
+
+    /// Tokens a win is expected to pay before any play-dependent scaling: fixed rewards,
+    /// the average loot roll for combat and the average species bonus for fishing. Mining
+    /// (Ore comes from MiningYield), rest and crafting have no fixed rewards.
+    pub fn expected_rewards(&self) -> HashMap<Token, i64> {
+        match self {
+            EncounterKind::Combat { combatant_def } => {
+                let mut rewards = combatant_def.rewards.clone();
+                if let Some(loot) = &combatant_def.loot_table {
+                    let total_weight: i64 = loot.entries.iter().map(|e| e.weight as i64).sum();
+                    if total_weight > 0 {
+                        for entry in &loot.entries {
+                            let average =
+                                (entry.min + entry.max) * entry.weight as i64 * loot.rolls as i64
+                                    / (2 * total_weight);
+                            *rewards
+                                .entry(Token::persistent(entry.token_type.clone()))
+                                .or_insert(0) += average;
+                        }
+                    }
+                }
+                rewards
+            }
+            EncounterKind::Herbalism { herbalism_def } => herbalism_def.rewards.clone(),
+            EncounterKind::Woodcutting { woodcutting_def } => woodcutting_def.base_rewards.clone(),
+            EncounterKind::Fishing { fishing_def } => {
+                let mut rewards = fishing_def.rewards.clone();
+                let species_count = fishing_def.species.len() as i64;
+                for species in &fishing_def.species {
+                    for (token, amount) in &species.rewards {
+                        *rewards.entry(token.clone()).or_insert(0) += amount / species_count;
+                    }
+                }
+                rewards
+            }
+            EncounterKind::Mining { .. }
+            | EncounterKind::Rest { .. }
+            | EncounterKind::Crafting { .. } => HashMap::new(),
+        }
+    }
+}
+
+/// What a hand encounter asks and offers, computed from its definition before it is picked.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
+pub struct EncounterSummary {
+    pub card_id: usize,
+    /// Copies of this encounter currently in the hand.
+    pub hand_count: u32,
+    pub discipline: EncounterDiscipline,
+    pub difficulty_score: i64,
+    #[serde(with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub expected_rewards: HashMap<Token, i64>,
+    pub entry_cost: Vec<TokenAmount>,
+}
+
+/// Definition of a mining node for a gathering encounter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
 pub struct MiningDef {
     pub initial_light_level: i64,
     pub ore_deck: Vec<OreCard>,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
 }
 
 /// Definition of a rest encounter.
@@ -526,6 +1099,9 @@ pub struct MiningDef {
 pub struct RestDef {
     pub rest_token_min: i64,
     pub rest_token_max: i64,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
 }
 
 /// An enemy crafting card that increases material costs.
@@ -558,11 +1134,14 @@ impl HasDeckCounts for EnemyCraftingCard {
 }
 
 /// Definition of a crafting encounter.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct CraftingDef {
     pub initial_crafting_tokens: i64,
     pub enemy_crafting_deck: Vec<EnemyCraftingCard>,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
 }
 
 /// State of an active craft-a-card mini-game within a crafting encounter.
@@ -578,12 +1157,43 @@ pub struct CraftingCraftState {
     pub current_costs: HashMap<TokenType, i64>,
 }
 
-/// A card in the ore deck. Each card applies token-based damages to the player.
+/// A card in the ore deck. Each card applies token-based damages to the player,
+/// then fires its optional hazard.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct OreCard {
     pub damages: Vec<TokenAmount>,
     pub counts: DeckCounts,
+    #[serde(default)]
+    pub hazard: Option<OreHazard>,
+}
+
+/// Special trigger fired when the ore plays a card.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde", tag = "hazard_type")]
+pub enum OreHazard {
+    /// Force-ends the encounter as lost; the player still converts `yield_percent`% of
+    /// the accumulated MiningYield into Ore (bounded by Stamina, as when concluding).
+    CaveIn { yield_percent: u32 },
+    /// Drains `stamina_per_turn` Stamina every later turn until a ventilating card is played.
+    /// Gas pockets don't stack; the strongest one sets the drain.
+    Gas { stamina_per_turn: i64 },
+    /// Grants `amount` of `token_type` if the player concludes within `turns` turns, counting
+    /// the turn it is exposed on.
+    GemVein {
+        token_type: TokenType,
+        amount: i64,
+        turns: u32,
+    },
+}
+
+/// An exposed gem vein: concluding while it is open grants the bonus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct GemVeinBonus {
+    pub token_type: TokenType,
+    pub amount: i64,
+    pub turns_left: u32,
 }
 
 /// Copy counts for non-library cards (enemy, ore, etc.): deck, hand, discard.
@@ -595,6 +1205,12 @@ pub struct DeckCounts {
     pub discard: u32,
 }
 
+impl DeckCounts {
+    pub fn total(&self) -> u32 {
+        self.deck + self.hand + self.discard
+    }
+}
+
 /// Trait for card types that have deck/hand/discard tracking.
 /// Both `DeckCounts` and `CardCounts` implement this, enabling generic
 /// deck operations across encounter-internal and player-owned cards.
@@ -756,7 +1372,7 @@ impl HasDeckCounts for LibraryCard {
 
 /// Definition of an enemy combatant for a combat encounter card.
 /// Enemies are self-contained: their cards are inline, not Library references.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct CombatantDef {
     #[serde(with = "token_map_serde_u64")]
@@ -765,6 +1381,61 @@ pub struct CombatantDef {
     pub attack_deck: Vec<EnemyCardDef>,
     pub defence_deck: Vec<EnemyCardDef>,
     pub resource_deck: Vec<EnemyCardDef>,
+    #[serde(default)]
+    pub retreat_cost: RetreatCost,
+    /// Tokens granted when the enemy is defeated.
+    #[serde(default, with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub rewards: HashMap<Token, i64>,
+    /// Extra loot rolled from the game RNG when the enemy is defeated.
+    #[serde(default)]
+    pub loot_table: Option<LootTable>,
+    /// Tokens the player pays to pick this encounter.
+    #[serde(default)]
+    pub entry_cost: Vec<TokenAmount>,
+}
+
+/// Weighted loot rolled on victory; each of the `rolls` picks one entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct LootTable {
+    pub rolls: u32,
+    pub entries: Vec<LootEntry>,
+}
+
+/// A loot table entry: `min..=max` of `token_type`, picked with relative `weight`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct LootEntry {
+    pub token_type: TokenType,
+    pub min: i64,
+    pub max: i64,
+    pub weight: u32,
+}
+
+/// Tokens granted by a won combat, split into fixed rewards and rolled loot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct RewardBreakdown {
+    #[serde(with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub rewards: HashMap<Token, i64>,
+    #[serde(with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub loot: HashMap<Token, i64>,
+}
+
+/// What the player pays to flee a combat encounter (recorded as PlayerLost).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde", tag = "retreat_type")]
+pub enum RetreatCost {
+    /// The enemy cannot be fled; the combat must be fought to the end.
+    #[default]
+    Impossible,
+    /// Spend a fixed amount of Stamina.
+    Stamina { amount: i64 },
+    /// Lose `percent` of every gathering material balance (Ore, Plant, Lumber, Fish).
+    MaterialFraction { percent: u32 },
 }
 
 /// A simple inline card definition for enemy decks.
@@ -834,7 +1505,10 @@ pub mod token_map_serde {
 
         let lifecycle = match lifecycle_str {
             None | Some("PersistentCounter") => TokenLifecycle::PersistentCounter,
-            Some(s) => serde_json::from_str(s).unwrap_or(TokenLifecycle::PersistentCounter),
+            // Keys carry unit lifecycles unquoted, e.g. "HerbalismGuard:SingleUse"
+            Some(s) => serde_json::from_str(&format!("\"{}\"", s))
+                .or_else(|_| serde_json::from_str(s))
+                .unwrap_or(TokenLifecycle::PersistentCounter),
         };
 
         Ok(Token {
@@ -909,7 +1583,10 @@ pub mod token_map_serde_u64 {
 
         let lifecycle = match lifecycle_str {
             None | Some("PersistentCounter") => TokenLifecycle::PersistentCounter,
-            Some(s) => serde_json::from_str(s).unwrap_or(TokenLifecycle::PersistentCounter),
+            // Keys carry unit lifecycles unquoted, e.g. "HerbalismGuard:SingleUse"
+            Some(s) => serde_json::from_str(&format!("\"{}\"", s))
+                .or_else(|_| serde_json::from_str(s))
+                .unwrap_or(TokenLifecycle::PersistentCounter),
         };
 
         Ok(Token {
@@ -994,7 +1671,7 @@ pub fn token_balance_by_type(map: &HashMap<Token, i64>, tt: &TokenType) -> i64 {
 /// Get a mutable reference to the first token entry matching a type,
 /// or insert a new entry with the type's default lifecycle.
 pub fn token_entry_by_type<'a>(map: &'a mut HashMap<Token, i64>, tt: &TokenType) -> &'a mut i64 {
-    let key = Token::persistent(tt.clone());
+    let key = Token::with_default_lifecycle(tt.clone());
     map.entry(key).or_insert(0)
 }
 
@@ -1016,26 +1693,121 @@ pub enum TokenLifecycle {
 }
 
 /// Action payloads for the append-only log — only player-initiated actions.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde", tag = "type")]
 pub enum ActionPayload {
-    SetSeed { seed: u64 },
-    DrawEncounter { encounter_id: String },
-    PlayCard { card_id: usize },
-    ApplyScouting { card_ids: Vec<usize> },
+    SetSeed {
+        seed: u64,
+    },
+    DrawEncounter {
+        encounter_id: String,
+    },
+    PlayCard {
+        card_id: usize,
+    },
+    ApplyScouting {
+        card_ids: Vec<usize>,
+    },
     AbortEncounter,
+    RetreatEncounter,
     ConcludeEncounter,
-    CraftSwap { from_id: usize, to_id: usize },
-    CraftCard { target_card_id: usize },
-    CraftDurability { discipline: String },
+    CraftSwap {
+        from_id: usize,
+        to_id: usize,
+    },
+    CraftCard {
+        target_card_id: usize,
+    },
+    CraftDurability {
+        discipline: Discipline,
+    },
+    FishingBait {
+        bait: FishingBait,
+    },
+    Repair {
+        discipline: Discipline,
+    },
+    EquipTool {
+        card_id: usize,
+    },
+    UnequipTool {
+        discipline: Discipline,
+    },
+    /// Branch marker, only ever written to the persisted log file: the entries after
+    /// `to_seq` were discarded by a rewind and the following lines continue from there.
+    Rewind {
+        to_seq: u64,
+    },
+}
+
+impl ActionPayload {
+    /// The variant name, as serialized in the `type` field.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ActionPayload::SetSeed { .. } => "SetSeed",
+            ActionPayload::DrawEncounter { .. } => "DrawEncounter",
+            ActionPayload::PlayCard { .. } => "PlayCard",
+            ActionPayload::ApplyScouting { .. } => "ApplyScouting",
+            ActionPayload::AbortEncounter => "AbortEncounter",
+            ActionPayload::RetreatEncounter => "RetreatEncounter",
+            ActionPayload::ConcludeEncounter => "ConcludeEncounter",
+            ActionPayload::CraftSwap { .. } => "CraftSwap",
+            ActionPayload::CraftCard { .. } => "CraftCard",
+            ActionPayload::CraftDurability { .. } => "CraftDurability",
+            ActionPayload::FishingBait { .. } => "FishingBait",
+            ActionPayload::Repair { .. } => "Repair",
+            ActionPayload::EquipTool { .. } => "EquipTool",
+            ActionPayload::UnequipTool { .. } => "UnequipTool",
+            ActionPayload::Rewind { .. } => "Rewind",
+        }
+    }
 }
 
 /// Stored action entry in the append-only action log.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct ActionEntry {
     pub seq: u64,
     pub payload: ActionPayload,
+    /// `hash` of the previous entry, or `GENESIS_HASH` for the first one.
+    #[serde(default)]
+    pub prev_hash: String,
+    /// Hex SHA-256 over `seq`, `prev_hash` and the JSON of `payload`.
+    #[serde(default)]
+    pub hash: String,
+    /// State right after the action, checked by replay. Not covered by `hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<super::state_digest::StateDigest>,
+}
+
+impl ActionEntry {
+    /// The `prev_hash` of the first entry in a log.
+    pub const GENESIS_HASH: &'static str =
+        "0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// An entry chained onto `prev_hash`, with its own hash filled in.
+    pub fn new(seq: u64, prev_hash: &str, payload: ActionPayload) -> Self {
+        let hash = Self::content_hash(seq, prev_hash, &payload);
+        ActionEntry {
+            seq,
+            payload,
+            prev_hash: prev_hash.to_string(),
+            hash,
+            digest: None,
+        }
+    }
+
+    pub fn content_hash(seq: u64, prev_hash: &str, payload: &ActionPayload) -> String {
+        use sha2::{Digest, Sha256};
+        let payload_json = serde_json::to_string(payload).unwrap_or_default();
+        let digest = Sha256::digest(format!("{}:{}:{}", seq, prev_hash, payload_json));
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Whether `hash` matches the entry's content.
+    pub fn hash_is_valid(&self) -> bool {
+        self.hash == Self::content_hash(self.seq, &self.prev_hash, &self.payload)
+    }
 }
 
 // ====== Combat types for deterministic, logged combat resolution (Step 6) ======
@@ -1097,6 +1869,33 @@ pub struct CombatEncounterState {
     pub enemy_attack_deck: Vec<EnemyCardDef>,
     pub enemy_defence_deck: Vec<EnemyCardDef>,
     pub enemy_resource_deck: Vec<EnemyCardDef>,
+    /// Triggers hooked into the damage pipeline; cleared when a new round starts.
+    #[serde(default)]
+    pub triggers: Vec<CombatTrigger>,
+}
+
+/// One side of a combat encounter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum Combatant {
+    Player,
+    Enemy,
+}
+
+/// A reactive effect registered by a played card, owned by one combatant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct CombatTrigger {
+    pub owner: Combatant,
+    pub kind: CombatTriggerKind,
+}
+
+/// What a combat trigger does when it fires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(crate = "rocket::serde")]
+pub enum CombatTriggerKind {
+    /// When the owner is hit by a Health loss, deal `percent` of the incoming damage back.
+    ReflectOnHit { percent: u32 },
 }
 
 /// Runtime state for a mining gathering encounter.
@@ -1110,6 +1909,11 @@ pub struct MiningEncounterState {
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub encounter_tokens: HashMap<Token, i64>,
+    /// Stamina drained each turn by unventilated gas.
+    #[serde(default)]
+    pub gas_per_turn: i64,
+    #[serde(default)]
+    pub gem_vein: Option<GemVeinBonus>,
 }
 
 /// Runtime state for an herbalism gathering encounter.
@@ -1123,6 +1927,16 @@ pub struct HerbalismEncounterState {
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub rewards: HashMap<Token, i64>,
+    #[serde(default)]
+    pub consolation_percent: u32,
+    /// Indices into `plant_hand` of plants the last play spared thanks to a HerbalismGuard.
+    #[serde(default)]
+    pub guarded_plants: Vec<usize>,
+    /// What concluding now would pay: `rewards` with Plant scaled by the surviving plants'
+    /// average potency.
+    #[serde(default, with = "token_map_serde")]
+    #[schemars(with = "token_map_serde::SchemaHelper")]
+    pub projected_reward: HashMap<Token, i64>,
 }
 
 /// Runtime state for a woodcutting gathering encounter (pattern-matching).
@@ -1134,11 +1948,13 @@ pub struct WoodcuttingEncounterState {
     pub outcome: EncounterOutcome,
     pub played_cards: Vec<PlayedWoodcuttingCard>,
     pub max_plays: u32,
-    pub pattern_name: Option<String>,
+    pub pattern: Option<WoodcuttingPattern>,
     pub pattern_multiplier: Option<f64>,
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub base_rewards: HashMap<Token, i64>,
+    #[serde(default = "WoodcuttingPattern::default_multipliers")]
+    pub pattern_multipliers: HashMap<WoodcuttingPattern, f64>,
 }
 
 /// Runtime state for a fishing gathering encounter (card-subtraction).
@@ -1160,6 +1976,16 @@ pub struct FishingEncounterState {
     #[serde(with = "token_map_serde")]
     #[schemars(with = "token_map_serde::SchemaHelper")]
     pub encounter_tokens: HashMap<Token, i64>,
+    #[serde(default)]
+    pub species: Option<FishSpecies>,
+    #[serde(default)]
+    pub turns_lost: u32,
+    /// Index into `fish_deck` of the card a telegraphed fish will play next.
+    #[serde(default)]
+    pub revealed_fish: Option<usize>,
+    /// Bait used in this encounter; at most one, and only on round 1.
+    #[serde(default)]
+    pub bait: Option<FishingBait>,
 }
 
 /// Runtime state for a rest encounter (play rest cards using rest tokens).
@@ -4,6 +4,8 @@ use rand::{RngCore, SeedableRng};
 use rand_pcg::Lcg64Xsh32;
 use rocket::futures::lock::Mutex;
 
+/// Clones share the seed and the RNG.
+#[derive(Clone)]
 pub struct RandomGeneratorWrapper {
     pub(crate) seed: Arc<Mutex<[u8; 16]>>,
     pub(crate) random_generator_state: Arc<Mutex<Lcg64Xsh32>>,
@@ -19,3 +21,12 @@ pub fn new() -> RandomGeneratorWrapper {
         random_generator_state: Arc::new(Mutex::new(random_generator)),
     }
 }
+
+/// Wrap an RNG restored from a snapshot or replay. The seed is only known to the action
+/// log's `SetSeed` entry and is left zeroed.
+pub fn resumed(random_generator: Lcg64Xsh32) -> RandomGeneratorWrapper {
+    RandomGeneratorWrapper {
+        seed: Arc::new(Mutex::new([0; 16])),
+        random_generator_state: Arc::new(Mutex::new(random_generator)),
+    }
+}
@@ -9,6 +9,9 @@ use schemars::JsonSchema;
 pub struct TokenBalance {
     pub token: crate::library::types::Token,
     pub value: i64,
+    /// Maximum of a capped pool (e.g. `MaxMiningDurability` for `MiningDurability`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
 }
 
 #[openapi]
@@ -17,13 +20,32 @@ pub async fn get_player_tokens(
     game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
 ) -> Json<Vec<TokenBalance>> {
     let gs = game_state.lock().await;
-    let balances: Vec<TokenBalance> = gs
-        .token_balances
+    Json(token_balances(&gs))
+}
+
+pub(crate) fn token_balances(gs: &crate::library::GameState) -> Vec<TokenBalance> {
+    gs.token_balances
         .iter()
         .map(|(k, v)| TokenBalance {
             token: k.clone(),
             value: *v,
+            max: k.token_type.max_token().map(|max_token| {
+                crate::library::types::token_balance_by_type(&gs.token_balances, &max_token)
+            }),
         })
+        .collect()
+}
+
+/// Tools currently equipped, one per discipline slot.
+#[openapi]
+#[get("/player/tools")]
+pub async fn get_player_tools(
+    game_state: &State<std::sync::Arc<rocket::futures::lock::Mutex<crate::library::GameState>>>,
+) -> Json<Vec<crate::library::types::EquippedTool>> {
+    let gs = game_state.lock().await;
+    let tools = crate::library::types::Discipline::ALL
+        .iter()
+        .filter_map(|discipline| gs.equipped_tools.get(discipline).cloned())
         .collect();
-    Json(balances)
+    Json(tools)
 }
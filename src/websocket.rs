@@ -0,0 +1,157 @@
+//! WebSocket channel for playing many actions over one connection. Every frame goes through
+//! the same action handling as `POST /action`.
+
+use either::{Left, Right};
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::http::Status as HttpStatus;
+use rocket::response::status::Custom;
+use rocket::serde::json::{serde_json, Json};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::State;
+use rocket_okapi::JsonSchema;
+use rocket_ws::{Channel, Message, WebSocket};
+use std::sync::Arc;
+
+use crate::action::{PlayResult, PlayerActions};
+use crate::events::{play_published, EventBus, GameEvent};
+use crate::library::types::ActionEntry;
+use crate::library::GameState;
+use crate::player_data::RandomGeneratorWrapper;
+use crate::status_messages::{new_status, Status};
+
+/// One action sent over the socket: a `PlayerActions` object as posted to `/action`, plus
+/// an optional `id` echoed in the reply and `deltas` to get the events the action caused.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct ActionFrame {
+    #[serde(default)]
+    pub id: Option<serde_json::Value>,
+    #[serde(default)]
+    pub deltas: bool,
+    #[serde(flatten)]
+    pub action: PlayerActions,
+}
+
+/// Reply to one `ActionFrame`, sent in the order the frames arrived. `status` is the HTTP
+/// status `POST /action` would answer with, alongside its `entry` or `error` body.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct ActionReply {
+    pub id: Option<serde_json::Value>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<ActionEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Status>,
+    /// The events published for the action, when the frame asked for `deltas`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deltas: Option<Vec<GameEvent>>,
+}
+
+impl ActionReply {
+    fn error(id: Option<serde_json::Value>, status: HttpStatus, message: String) -> Self {
+        Self {
+            id,
+            status: status.code,
+            entry: None,
+            error: Some(new_status(message).0),
+            deltas: None,
+        }
+    }
+
+    fn from_result(id: Option<serde_json::Value>, result: PlayResult) -> Self {
+        match result {
+            Ok((status, entry)) => Self {
+                id,
+                status: status.code,
+                entry: Some(entry.0),
+                error: None,
+                deltas: None,
+            },
+            Err(Left(not_found)) => Self {
+                id,
+                status: HttpStatus::NotFound.code,
+                entry: None,
+                error: Some(not_found.0 .0),
+                deltas: None,
+            },
+            Err(Right(bad_request)) => Self {
+                id,
+                status: HttpStatus::BadRequest.code,
+                entry: None,
+                error: Some(bad_request.0 .0),
+                deltas: None,
+            },
+        }
+    }
+}
+
+/// Parse one text frame and play its action, answering with the reply to send back.
+async fn reply_to(
+    player_data: &RandomGeneratorWrapper,
+    game_state: &Arc<rocket::futures::lock::Mutex<GameState>>,
+    bus: &EventBus,
+    text: &str,
+) -> ActionReply {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => return ActionReply::error(None, HttpStatus::UnprocessableEntity, e.to_string()),
+    };
+    let id = value.get("id").cloned();
+    let frame: ActionFrame = match serde_json::from_value(value) {
+        Ok(frame) => frame,
+        Err(e) => return ActionReply::error(id, HttpStatus::UnprocessableEntity, e.to_string()),
+    };
+    let (result, events) = play_published(player_data, game_state, bus, frame.action).await;
+    ActionReply {
+        deltas: frame.deltas.then_some(events),
+        ..ActionReply::from_result(frame.id, result)
+    }
+}
+
+/// Upgrade to a WebSocket that takes `ActionFrame`s and answers each with an `ActionReply`.
+#[get("/action/ws")]
+pub fn action_socket(
+    ws: WebSocket,
+    player_data: &State<RandomGeneratorWrapper>,
+    game_state: &State<Arc<rocket::futures::lock::Mutex<GameState>>>,
+    bus: &State<EventBus>,
+) -> Channel<'static> {
+    let player_data = player_data.inner().clone();
+    let game_state = game_state.inner().clone();
+    let bus = bus.inner().clone();
+    ws.channel(move |mut stream| {
+        Box::pin(async move {
+            while let Some(message) = stream.next().await {
+                let reply = match message {
+                    Ok(Message::Text(text)) => {
+                        reply_to(&player_data, &game_state, &bus, &text).await
+                    }
+                    Ok(Message::Binary(_)) => ActionReply::error(
+                        None,
+                        HttpStatus::UnprocessableEntity,
+                        "Expected a JSON text frame".to_string(),
+                    ),
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    // Pings are answered by the socket itself
+                    Ok(_) => continue,
+                };
+                let text = serde_json::to_string(&reply)
+                    .map_err(|e| rocket_ws::result::Error::Io(std::io::Error::other(e)))?;
+                if stream.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    })
+}
+
+/// `GET /action/ws` without a WebSocket upgrade.
+#[get("/action/ws", rank = 2)]
+pub fn action_socket_upgrade_required() -> Custom<Json<Status>> {
+    Custom(
+        HttpStatus::UpgradeRequired,
+        new_status("Expected a WebSocket upgrade".to_string()),
+    )
+}
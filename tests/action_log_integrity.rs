@@ -0,0 +1,152 @@
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::types::{ActionEntry, ActionPayload};
+use my_little_cardgame::rocket_initialize;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+
+fn chained_log() -> ActionLog {
+    let log = ActionLog::new();
+    log.append("NewGame", ActionPayload::SetSeed { seed: 7 });
+    log.append("EquipTool", ActionPayload::EquipTool { card_id: 3 });
+    log.append("Abort", ActionPayload::AbortEncounter);
+    log.append("Retreat", ActionPayload::RetreatEncounter);
+    log
+}
+
+/// Write `entries` as NDJSON and load them back.
+fn load(name: &str, entries: &[ActionEntry]) -> Result<ActionLog, String> {
+    let path = std::env::temp_dir().join(format!(
+        "mlcg_integrity_{}_{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let lines: Vec<String> = entries
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect();
+    std::fs::write(&path, lines.join("\n")).unwrap();
+    let loaded = ActionLog::load_from_file(path.to_str().unwrap());
+    let _ = std::fs::remove_file(&path);
+    loaded
+}
+
+#[test]
+fn appended_entries_form_a_hash_chain() {
+    let log = chained_log();
+    let entries = log.entries();
+    assert_eq!(entries[0].prev_hash, ActionEntry::GENESIS_HASH);
+    for pair in entries.windows(2) {
+        assert_eq!(pair[1].prev_hash, pair[0].hash);
+    }
+    assert!(entries.iter().all(|e| e.hash_is_valid()));
+    assert_eq!(log.verify(), Ok(()));
+    assert_eq!(log.head_hash(), entries[3].hash);
+
+    let loaded = load("valid", &entries).expect("an intact log loads");
+    assert_eq!(loaded.entries().len(), 4);
+    assert_eq!(loaded.head_hash(), log.head_hash());
+}
+
+#[test]
+fn loading_reports_the_first_bad_line() {
+    let entries = chained_log().entries();
+
+    let mut tampered = entries.clone();
+    tampered[1].payload = ActionPayload::EquipTool { card_id: 4 };
+    let err = load("tampered", &tampered).expect_err("tampering is caught");
+    assert!(err.starts_with("Line 2:"), "{}", err);
+
+    let gap = [entries[0].clone(), entries[2].clone(), entries[3].clone()];
+    let err = load("gap", &gap).expect_err("a gap is caught");
+    assert_eq!(err, "Line 2: expected seq 2, found 3");
+
+    let duplicate = [entries[0].clone(), entries[1].clone(), entries[1].clone()];
+    let err = load("duplicate", &duplicate).expect_err("a duplicate is caught");
+    assert_eq!(err, "Line 3: expected seq 3, found 2");
+
+    let reordered = [
+        entries[0].clone(),
+        entries[2].clone(),
+        entries[1].clone(),
+        entries[3].clone(),
+    ];
+    let err = load("reordered", &reordered).expect_err("reordering is caught");
+    assert!(err.starts_with("Line 2:"), "{}", err);
+
+    // A resealed entry still breaks the chain of the one after it
+    let mut resealed = entries.clone();
+    resealed[1] = ActionEntry::new(2, &entries[0].hash, ActionPayload::EquipTool { card_id: 4 });
+    let err = load("resealed", &resealed).expect_err("resealing is caught");
+    assert_eq!(err, "Line 3: seq 3 does not chain onto the previous entry");
+
+    // A rewind marker must chain onto the entry it rewinds to
+    let mut forged_rewind = entries.clone();
+    forged_rewind.push(ActionEntry::new(
+        2,
+        &entries[3].hash,
+        ActionPayload::Rewind { to_seq: 2 },
+    ));
+    let err = load("forged_rewind", &forged_rewind).expect_err("a forged rewind is caught");
+    assert!(err.starts_with("Line 5:"), "{}", err);
+}
+
+#[test]
+fn verify_endpoint_checks_the_live_log() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let resp = client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(r#"{"action_type":"NewGame","seed":42}"#)
+        .dispatch();
+    assert_eq!(resp.status(), Status::Created);
+    let entry: serde_json::Value =
+        serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
+
+    let resp = client.get("/actions/log/verify").dispatch();
+    assert_eq!(resp.status(), Status::Ok);
+    let report: serde_json::Value =
+        serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
+    assert_eq!(report["valid"], true);
+    assert_eq!(report["entries"], 1);
+    assert_eq!(report["head_hash"], entry["hash"]);
+    assert!(report.get("error").is_none());
+}
+
+#[test]
+fn logs_written_before_hashing_load_and_keep_growing() {
+    // Baseline format: seq and payload only
+    let legacy = [
+        r#"{"seq":1,"payload":{"type":"SetSeed","seed":7}}"#,
+        r#"{"seq":2,"payload":{"type":"EquipTool","card_id":3}}"#,
+        r#"{"seq":3,"payload":{"type":"AbortEncounter"}}"#,
+    ];
+    let path = std::env::temp_dir().join(format!(
+        "mlcg_integrity_legacy_{}.jsonl",
+        std::process::id()
+    ));
+    std::fs::write(&path, legacy.join("\n") + "\n").unwrap();
+    let loaded = ActionLog::load_from_file(path.to_str().unwrap()).expect("a legacy log loads");
+    assert_eq!(loaded.entries(), chained_log().entries()[..3].to_vec());
+    assert_eq!(loaded.verify(), Ok(()));
+
+    // New entries chain onto the re-chained prefix, and the mixed file loads again
+    let next = loaded.append("Retreat", ActionPayload::RetreatEncounter);
+    let mut lines = legacy.join("\n") + "\n";
+    lines.push_str(&serde_json::to_string(&next).unwrap());
+    std::fs::write(&path, lines).unwrap();
+    let reloaded = ActionLog::load_from_file(path.to_str().unwrap()).expect("the mixed log loads");
+    assert_eq!(reloaded.head_hash(), chained_log().head_hash());
+
+    // Unhashed entries are only accepted before the first hashed one
+    let mut entries: Vec<String> = chained_log()
+        .entries()
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect();
+    entries[3] = r#"{"seq":4,"payload":{"type":"RetreatEncounter"}}"#.to_string();
+    std::fs::write(&path, entries.join("\n")).unwrap();
+    let err = ActionLog::load_from_file(path.to_str().unwrap()).expect_err("unhashed tail");
+    assert!(err.starts_with("Line 4:"), "{}", err);
+    let _ = std::fs::remove_file(&path);
+}
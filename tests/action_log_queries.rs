@@ -0,0 +1,196 @@
+use my_little_cardgame::library::action_log::{ActionLog, LogQuery};
+use my_little_cardgame::library::types::ActionPayload;
+use my_little_cardgame::rocket_initialize;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+
+/// Two encounters: the first concluded and scouted (seqs 2-6), the second still running (8-10).
+fn two_encounter_log() -> ActionLog {
+    let log = ActionLog::new();
+    for payload in [
+        ActionPayload::SetSeed { seed: 1 },
+        ActionPayload::DrawEncounter {
+            encounter_id: "5".to_string(),
+        },
+        ActionPayload::PlayCard { card_id: 1 },
+        ActionPayload::PlayCard { card_id: 2 },
+        ActionPayload::ConcludeEncounter,
+        ActionPayload::ApplyScouting { card_ids: vec![] },
+        ActionPayload::EquipTool { card_id: 9 },
+        ActionPayload::DrawEncounter {
+            encounter_id: "6".to_string(),
+        },
+        ActionPayload::PlayCard { card_id: 3 },
+        ActionPayload::RetreatEncounter,
+    ] {
+        log.append("Test", payload);
+    }
+    log
+}
+
+fn seqs(log: &ActionLog, query: LogQuery) -> (Vec<u64>, Option<u64>) {
+    let page = log.query(&query);
+    (page.entries.iter().map(|e| e.seq).collect(), page.next_seq)
+}
+
+fn all() -> LogQuery {
+    LogQuery {
+        limit: 100,
+        ..LogQuery::default()
+    }
+}
+
+#[test]
+fn query_filters_by_type_range_and_encounter() {
+    let log = two_encounter_log();
+    let play_cards = LogQuery {
+        payload_type: Some("PlayCard".to_string()),
+        ..all()
+    };
+    assert_eq!(seqs(&log, play_cards.clone()), (vec![3, 4, 9], None));
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                from_seq: Some(3),
+                to_seq: Some(7),
+                ..all()
+            }
+        ),
+        (vec![3, 4, 5, 6, 7], None)
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                encounter: Some(2),
+                ..all()
+            }
+        ),
+        (vec![2, 3, 4, 5, 6], None)
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                encounter: Some(8),
+                ..play_cards
+            }
+        ),
+        (vec![9], None)
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                encounter: Some(8),
+                ..all()
+            }
+        ),
+        (vec![8, 9, 10], None),
+        "an encounter in progress runs to the end of the log"
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                encounter: Some(3),
+                ..all()
+            }
+        ),
+        (vec![], None),
+        "seq 3 does not start an encounter"
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                payload_type: Some("Bogus".to_string()),
+                ..all()
+            }
+        ),
+        (vec![], None)
+    );
+}
+
+#[test]
+fn query_pages_in_both_directions() {
+    let log = two_encounter_log();
+    let newest = LogQuery {
+        descending: true,
+        limit: 3,
+        ..LogQuery::default()
+    };
+    assert_eq!(seqs(&log, newest.clone()), (vec![10, 9, 8], Some(7)));
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                to_seq: Some(7),
+                ..newest.clone()
+            }
+        ),
+        (vec![7, 6, 5], Some(4))
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                payload_type: Some("PlayCard".to_string()),
+                limit: 2,
+                ..newest
+            }
+        ),
+        (vec![9, 4], Some(3))
+    );
+    assert_eq!(
+        seqs(
+            &log,
+            LogQuery {
+                payload_type: Some("PlayCard".to_string()),
+                limit: 2,
+                ..LogQuery::default()
+            }
+        ),
+        (vec![3, 4], Some(9))
+    );
+}
+
+#[test]
+fn actions_log_endpoint_accepts_the_filters() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    for seed in [1, 2, 3] {
+        let status = client
+            .post("/action")
+            .header(ContentType::JSON)
+            .body(format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed))
+            .dispatch()
+            .status();
+        assert_eq!(status, Status::Created);
+    }
+    let log: serde_json::Value = serde_json::from_str(
+        &client
+            .get("/actions/log?payload_type=SetSeed&descending=true&limit=2")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    let entries = log["entries"].as_array().expect("entries");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["payload"]["seed"], 3);
+    assert_eq!(entries[1]["payload"]["seed"], 2);
+    assert_eq!(log["next_seq"], 1);
+
+    let log: serde_json::Value = serde_json::from_str(
+        &client
+            .get("/actions/log?from_seq=2&to_seq=2")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    assert_eq!(log["entries"].as_array().map(|e| e.len()), Some(1));
+    assert_eq!(log["entries"][0]["seq"], 2);
+}
@@ -0,0 +1,102 @@
+use my_little_cardgame::action::persistence::{ActionSink, FileWriter, MemorySink, NoopSink};
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::types::{ActionEntry, ActionPayload};
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct FailingSink;
+
+impl ActionSink for FailingSink {
+    fn write(&self, _entry: &ActionEntry) -> Result<(), String> {
+        Err("disk full".to_string())
+    }
+}
+
+fn seqs(entries: &[ActionEntry]) -> Vec<u64> {
+    entries.iter().map(|e| e.seq).collect()
+}
+
+#[test]
+fn append_fans_out_to_every_sink() {
+    let memory = Arc::new(MemorySink::default());
+    let mut log = ActionLog::new();
+    log.add_sink(memory.clone());
+    log.add_sink(Arc::new(NoopSink));
+    log.append("NewGame", ActionPayload::SetSeed { seed: 1 });
+    log.append("Abort", ActionPayload::AbortEncounter);
+
+    assert_eq!(seqs(&memory.entries()), vec![1, 2]);
+    assert_eq!(memory.entries()[1].hash, log.head_hash());
+    assert!(log.sink_errors().is_empty());
+}
+
+#[test]
+fn clones_start_without_sinks() {
+    let memory = Arc::new(MemorySink::default());
+    let mut log = ActionLog::new();
+    log.add_sink(memory.clone());
+    log.append("NewGame", ActionPayload::SetSeed { seed: 1 });
+
+    let mut clone = log.clone();
+    assert!(clone.sinks().is_empty());
+    clone.append("Retreat", ActionPayload::RetreatEncounter);
+    log.append("Abort", ActionPayload::AbortEncounter);
+    assert_eq!(
+        seqs(&memory.entries()),
+        vec![1, 2],
+        "the diverged clone wrote nothing"
+    );
+    assert_eq!(memory.entries()[1].payload, ActionPayload::AbortEncounter);
+
+    let copy = Arc::new(MemorySink::default());
+    clone.add_sink(copy.clone());
+    clone.append("Abort", ActionPayload::AbortEncounter);
+    assert_eq!(seqs(&copy.entries()), vec![3]);
+    assert_eq!(seqs(&memory.entries()), vec![1, 2]);
+}
+
+#[test]
+fn sink_failures_are_reported_and_do_not_stop_the_log() {
+    let memory = Arc::new(MemorySink::default());
+    let mut log = ActionLog::new();
+    log.add_sink(Arc::new(FailingSink));
+    log.add_sink(memory.clone());
+    log.append("NewGame", ActionPayload::SetSeed { seed: 1 });
+
+    assert_eq!(log.sink_errors(), vec!["seq 1: disk full".to_string()]);
+    assert_eq!(seqs(&log.entries()), vec![1]);
+    assert_eq!(seqs(&memory.entries()), vec![1], "later sinks still get it");
+}
+
+#[test]
+fn file_sink_writes_a_log_that_loads_back() {
+    let dir = std::env::temp_dir().join(format!(
+        "my_little_cardgame_sinks_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("actions.jsonl");
+
+    let mut log = ActionLog::new();
+    log.add_sink(Arc::new(
+        FileWriter::new(path.clone()).expect("create writer"),
+    ));
+    assert_eq!(log.file_path(), Some(path.as_path()));
+    log.append("NewGame", ActionPayload::SetSeed { seed: 1 });
+    log.append("Equip", ActionPayload::EquipTool { card_id: 4 });
+    log.close_sinks();
+
+    let loaded = ActionLog::load_from_file(path.to_str().unwrap()).expect("load");
+    assert_eq!(seqs(&loaded.entries()), vec![1, 2]);
+    assert_eq!(loaded.head_hash(), log.head_hash());
+
+    // Writing after close is a reported failure
+    log.append("Abort", ActionPayload::AbortEncounter);
+    assert_eq!(log.sink_errors().len(), 1);
+    assert!(log.sink_errors()[0].starts_with("seq 3:"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
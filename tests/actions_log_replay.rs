@@ -1,4 +1,7 @@
-use my_little_cardgame::library::types::{token_balance_by_type, Token, TokenType};
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::types::{
+    token_balance_by_type, ActionPayload, EncounterDiscipline, Token, TokenType,
+};
 use my_little_cardgame::library::GameState;
 
 #[test]
@@ -18,3 +21,61 @@ fn direct_balance_updates() {
         5
     );
 }
+
+fn seeded(seed: u64) -> rand_pcg::Lcg64Xsh32 {
+    use rand::SeedableRng;
+    let mut seed_bytes = [0u8; 16];
+    seed_bytes[0..8].copy_from_slice(&seed.to_le_bytes());
+    seed_bytes[8..16].copy_from_slice(&seed.to_le_bytes());
+    rand_pcg::Lcg64Xsh32::from_seed(seed_bytes)
+}
+
+#[test]
+fn replayed_encounter_picks_start_like_the_handler() {
+    let mut rng = seeded(5);
+    let mut live = GameState::new_with_rng(&mut rng);
+    let combat_card = live
+        .encounter_hand_summaries()
+        .iter()
+        .find(|s| s.discipline == EncounterDiscipline::Combat)
+        .map(|s| s.card_id)
+        .expect("a combat encounter in the starting hand");
+    live.start_picked_encounter(combat_card, &mut rng)
+        .expect("combat starts");
+
+    let log = ActionLog::new();
+    log.append("NewGame", ActionPayload::SetSeed { seed: 5 });
+    log.append(
+        "EncounterPickEncounter",
+        ActionPayload::DrawEncounter {
+            encounter_id: combat_card.to_string(),
+        },
+    );
+    let replayed = GameState::replay_from_log(&log);
+    assert_eq!(replayed.current_encounter, live.current_encounter);
+    assert_eq!(replayed.token_balances, live.token_balances);
+    assert_eq!(
+        replayed.library.encounter_hand(),
+        live.library.encounter_hand()
+    );
+}
+
+#[test]
+fn picking_combat_without_health_starts_at_the_handler_default() {
+    let mut rng = seeded(5);
+    let mut gs = GameState::new_with_rng(&mut rng);
+    let combat_card = gs
+        .encounter_hand_summaries()
+        .iter()
+        .find(|s| s.discipline == EncounterDiscipline::Combat)
+        .map(|s| s.card_id)
+        .expect("a combat encounter in the starting hand");
+    gs.token_balances
+        .remove(&Token::persistent(TokenType::Health));
+    gs.start_picked_encounter(combat_card, &mut rng)
+        .expect("combat starts");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Health),
+        2000
+    );
+}
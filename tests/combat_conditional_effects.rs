@@ -0,0 +1,262 @@
+mod common;
+
+use common::{deck_in_hand, effect, game_with, library_only, template};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardEffectKind, CardKind, CombatPhase, CombatantDef, EffectCondition,
+    EffectTarget, EncounterKind, EncounterState, EnemyCardDef, RetreatCost, Token, TokenLifecycle,
+    TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const ATTACK_CARD: usize = 5;
+const RESOURCE_CARD: usize = 6;
+const DEFENCE_CARD: usize = 7;
+const ENCOUNTER_CARD: usize = 8;
+
+/// Library with fixed-value templates: a 100 damage strike followed by a 300 damage
+/// finishing blow, a 200 heal below 50% Health, a 50% reflect and a 400 damage enemy attack.
+fn conditional_library(enemy_shield: u64) -> Library {
+    let mut lib = Library::new();
+    lib.add_card(
+        template(
+            CardEffectKind::LoseTokens {
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 100,
+                max: 100,
+                costs: vec![],
+                duration: TokenLifecycle::PersistentCounter,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::ConditionalLoseTokens {
+                condition: EffectCondition::OpponentShieldZero,
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 300,
+                max: 300,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::ConditionalGainTokens {
+                condition: EffectCondition::OwnHealthBelowPercent { percent: 50 },
+                target: EffectTarget::OnSelf,
+                token_type: TokenType::Health,
+                min: 200,
+                max: 200,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::ReflectDamage {
+                min_percent: 50,
+                max_percent: 50,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::LoseTokens {
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 400,
+                max: 400,
+                costs: vec![],
+                duration: TokenLifecycle::PersistentCounter,
+            },
+            true,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        CardKind::Attack {
+            effects: vec![effect(0, 100), effect(1, 300)],
+        },
+        library_only(),
+    );
+    lib.add_card(
+        CardKind::Resource {
+            effects: vec![effect(2, 200)],
+        },
+        library_only(),
+    );
+    lib.add_card(
+        CardKind::Defence {
+            effects: vec![effect(3, 50)],
+        },
+        library_only(),
+    );
+    let enemy_attack = vec![EnemyCardDef {
+        effects: vec![effect(4, 400)],
+        counts: deck_in_hand(3),
+    }];
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Combat {
+                combatant_def: CombatantDef {
+                    initial_tokens: HashMap::from([
+                        (Token::persistent(TokenType::Health), 1000),
+                        (Token::persistent(TokenType::Shield), enemy_shield),
+                    ]),
+                    attack_deck: enemy_attack,
+                    retreat_cost: RetreatCost::Impossible,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    lib
+}
+
+fn start(enemy_shield: u64) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let (mut gs, mut rng) = game_with(conditional_library(enemy_shield), 26);
+    assert!(gs.library.validate_card_effects().is_ok());
+    gs.start_combat(ENCOUNTER_CARD, &mut rng)
+        .expect("start combat");
+    (gs, rng)
+}
+
+fn enemy_tokens(gs: &GameState) -> HashMap<Token, i64> {
+    match &gs.current_encounter {
+        Some(EncounterState::Combat(c)) => c.enemy_tokens.clone(),
+        _ => panic!("expected active combat"),
+    }
+}
+
+#[test]
+fn finishing_blow_applies_after_earlier_effect_breaks_shield() {
+    let (mut gs, mut rng) = start(100);
+    gs.resolve_player_card(ATTACK_CARD, &mut rng)
+        .expect("play attack");
+    let enemy = enemy_tokens(&gs);
+    assert_eq!(token_balance_by_type(&enemy, &TokenType::Shield), 0);
+    assert_eq!(token_balance_by_type(&enemy, &TokenType::Health), 700);
+}
+
+#[test]
+fn finishing_blow_skipped_while_shield_remains() {
+    let (mut gs, mut rng) = start(500);
+    gs.resolve_player_card(ATTACK_CARD, &mut rng)
+        .expect("play attack");
+    let enemy = enemy_tokens(&gs);
+    assert_eq!(token_balance_by_type(&enemy, &TokenType::Shield), 400);
+    assert_eq!(token_balance_by_type(&enemy, &TokenType::Health), 1000);
+}
+
+#[test]
+fn heal_only_applies_below_health_threshold() {
+    let (mut gs, mut rng) = start(0);
+    gs.resolve_player_card(RESOURCE_CARD, &mut rng)
+        .expect("play resource");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Health),
+        1000
+    );
+
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Health), 400);
+    gs.resolve_player_card(RESOURCE_CARD, &mut rng)
+        .expect("play resource");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Health),
+        600
+    );
+}
+
+#[test]
+fn reflect_trigger_returns_damage_until_round_ends() {
+    let (mut gs, mut rng) = start(0);
+    gs.resolve_player_card(DEFENCE_CARD, &mut rng)
+        .expect("play defence");
+    gs.advance_combat_phase().expect("advance to attacking");
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Health),
+        600
+    );
+    assert_eq!(
+        token_balance_by_type(&enemy_tokens(&gs), &TokenType::Health),
+        800
+    );
+
+    gs.advance_combat_phase().expect("advance to resourcing");
+    gs.advance_combat_phase().expect("advance to defending");
+    match &gs.current_encounter {
+        Some(EncounterState::Combat(c)) => {
+            assert_eq!(c.phase, CombatPhase::Defending);
+            assert_eq!(c.round, 2);
+            assert!(c.triggers.is_empty());
+        }
+        _ => panic!("expected active combat"),
+    }
+
+    gs.advance_combat_phase().expect("advance to attacking");
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Health),
+        200
+    );
+    assert_eq!(
+        token_balance_by_type(&enemy_tokens(&gs), &TokenType::Health),
+        800
+    );
+}
+
+#[test]
+fn validate_rejects_out_of_range_conditional_templates() {
+    let mut lib = Library::new();
+    lib.add_card(
+        template(
+            CardEffectKind::ReflectDamage {
+                min_percent: 50,
+                max_percent: 150,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::ConditionalGainTokens {
+                condition: EffectCondition::OwnHealthBelowPercent { percent: 0 },
+                target: EffectTarget::OnSelf,
+                token_type: TokenType::Health,
+                min: 10,
+                max: 20,
+            },
+            true,
+        ),
+        library_only(),
+    );
+    lib.add_card(
+        template(
+            CardEffectKind::ConditionalLoseTokens {
+                condition: EffectCondition::OpponentShieldZero,
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 300,
+                max: 100,
+            },
+            false,
+        ),
+        library_only(),
+    );
+    let errors = lib.validate_card_effects().expect_err("invalid templates");
+    assert_eq!(errors.len(), 3);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{deck_in_hand, game_with, library_only};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardEffectKind, CardKind, CombatPhase, CombatantDef, ConcreteEffect,
+    ConcreteEffectCost, EffectTarget, EncounterKind, EncounterOutcome, EncounterState,
+    EnemyCardDef, RetreatCost, Token, TokenLifecycle, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const ENCOUNTER_CARD: usize = 1;
+
+/// Enemy attack dealing `damage`, optionally costing a percentage of the damage in one token.
+fn enemy_attack(damage: i64, cost: Option<(TokenType, u32)>, hand: u32) -> EnemyCardDef {
+    EnemyCardDef {
+        effects: vec![ConcreteEffect {
+            effect_id: 0,
+            rolled_value: damage,
+            rolled_costs: cost
+                .map(|(token_type, rolled_percent)| {
+                    vec![ConcreteEffectCost {
+                        token_type,
+                        rolled_percent,
+                    }]
+                })
+                .unwrap_or_default(),
+            rolled_cap: None,
+            rolled_gain_percent: None,
+        }],
+        counts: deck_in_hand(hand),
+    }
+}
+
+/// Start a combat in the Attacking phase against an enemy with the given decks and tokens.
+/// A costless zero-damage resource card keeps the enemy from losing to unpayable hands.
+fn start(
+    attack_deck: Vec<EnemyCardDef>,
+    resource_deck: Vec<EnemyCardDef>,
+    initial_tokens: &[(TokenType, u64)],
+) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::EnemyCardEffect {
+            kind: CardEffectKind::LoseTokens {
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 100,
+                max: 500,
+                costs: vec![],
+                duration: TokenLifecycle::PersistentCounter,
+            },
+        },
+        library_only(),
+    );
+    let mut tokens: HashMap<Token, u64> =
+        HashMap::from([(Token::persistent(TokenType::Health), 1000)]);
+    for (token_type, amount) in initial_tokens {
+        tokens.insert(Token::persistent(token_type.clone()), *amount);
+    }
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Combat {
+                combatant_def: CombatantDef {
+                    initial_tokens: tokens,
+                    attack_deck,
+                    resource_deck,
+                    retreat_cost: RetreatCost::Impossible,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+
+    let (mut gs, mut rng) = game_with(lib, 29);
+    gs.start_combat(ENCOUNTER_CARD, &mut rng)
+        .expect("start combat");
+    gs.advance_combat_phase().expect("advance to attacking");
+    (gs, rng)
+}
+
+fn enemy_balance(gs: &GameState, token_type: &TokenType) -> i64 {
+    match &gs.current_encounter {
+        Some(EncounterState::Combat(c)) => token_balance_by_type(&c.enemy_tokens, token_type),
+        _ => panic!("expected active combat"),
+    }
+}
+
+fn player_health(gs: &GameState) -> i64 {
+    token_balance_by_type(&gs.token_balances, &TokenType::Health)
+}
+
+#[test]
+fn enemy_pays_stamina_cost_from_initial_tokens() {
+    let (mut gs, mut rng) = start(
+        vec![enemy_attack(500, Some((TokenType::Stamina, 50)), 2)],
+        vec![enemy_attack(0, None, 1)],
+        &[(TokenType::Stamina, 300)],
+    );
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+    assert_eq!(player_health(&gs), 500);
+    assert_eq!(enemy_balance(&gs, &TokenType::Stamina), 50);
+}
+
+#[test]
+fn enemy_pays_mana_cost_from_initial_tokens() {
+    let (mut gs, mut rng) = start(
+        vec![enemy_attack(400, Some((TokenType::Mana, 25)), 2)],
+        vec![enemy_attack(0, None, 1)],
+        &[(TokenType::Mana, 150)],
+    );
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+    assert_eq!(player_health(&gs), 600);
+    assert_eq!(enemy_balance(&gs, &TokenType::Mana), 50);
+}
+
+#[test]
+fn enemy_skips_unaffordable_cards() {
+    let (mut gs, mut rng) = start(
+        vec![
+            enemy_attack(500, Some((TokenType::Stamina, 50)), 3),
+            enemy_attack(100, None, 1),
+        ],
+        vec![enemy_attack(0, None, 1)],
+        &[(TokenType::Stamina, 100)],
+    );
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+    assert_eq!(player_health(&gs), 900);
+    assert_eq!(enemy_balance(&gs, &TokenType::Stamina), 100);
+    match &gs.current_encounter {
+        Some(EncounterState::Combat(c)) => {
+            assert_eq!(c.phase, CombatPhase::Attacking);
+            assert_eq!(c.enemy_attack_deck[0].counts.hand, 3);
+            assert_eq!(c.enemy_attack_deck[1].counts.discard, 1);
+        }
+        _ => panic!("expected active combat"),
+    }
+}
+
+#[test]
+fn enemy_loses_when_every_hand_card_is_unpayable() {
+    let (mut gs, mut rng) = start(
+        vec![enemy_attack(500, Some((TokenType::Stamina, 50)), 2)],
+        vec![enemy_attack(300, Some((TokenType::Mana, 50)), 1)],
+        &[(TokenType::Stamina, 250)],
+    );
+    // First attack is affordable and spends all Stamina; nothing left is payable
+    gs.resolve_enemy_play(&mut rng).expect("enemy attack");
+    assert_eq!(player_health(&gs), 500);
+    assert!(gs.current_encounter.is_none());
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerWon]);
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::MilestoneInsight),
+        100
+    );
+}
@@ -0,0 +1,99 @@
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, EncounterKind, EncounterOutcome, EncounterPhase, RetreatCost,
+    Token, TokenType,
+};
+use my_little_cardgame::library::GameState;
+use rand::SeedableRng;
+
+const GNOME_ENCOUNTER: usize = 11;
+
+fn start_gnome_with(retreat_cost: RetreatCost) -> GameState {
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(28);
+    let mut gs = GameState::new_with_rng(&mut rng);
+    match &mut gs.library.cards[GNOME_ENCOUNTER].kind {
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Combat { combatant_def },
+        } => combatant_def.retreat_cost = retreat_cost,
+        _ => panic!("card {} should be the Gnome encounter", GNOME_ENCOUNTER),
+    }
+    gs.start_combat(GNOME_ENCOUNTER, &mut rng)
+        .expect("start combat");
+    gs
+}
+
+#[test]
+fn retreat_impossible_keeps_combat_running() {
+    let mut gs = start_gnome_with(RetreatCost::Impossible);
+    assert!(gs.retreat_from_combat().is_err());
+    assert!(gs.current_encounter.is_some());
+    assert!(gs.encounter_results.is_empty());
+}
+
+#[test]
+fn retreat_spends_stamina_and_records_loss() {
+    let mut gs = start_gnome_with(RetreatCost::Stamina { amount: 300 });
+    let stamina_before = token_balance_by_type(&gs.token_balances, &TokenType::Stamina);
+    gs.retreat_from_combat().expect("retreat");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Stamina),
+        stamina_before - 300
+    );
+    assert!(gs.current_encounter.is_none());
+    assert_eq!(gs.encounter_phase, EncounterPhase::Scouting);
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerLost]);
+}
+
+#[test]
+fn retreat_rejected_without_enough_stamina() {
+    let mut gs = start_gnome_with(RetreatCost::Stamina { amount: 300 });
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Stamina), 299);
+    assert!(gs.retreat_from_combat().is_err());
+    assert!(gs.current_encounter.is_some());
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Stamina),
+        299
+    );
+}
+
+#[test]
+fn retreat_costs_fraction_of_materials() {
+    let mut gs = start_gnome_with(RetreatCost::MaterialFraction { percent: 25 });
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Ore), 100);
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Lumber), 41);
+    let stamina_before = token_balance_by_type(&gs.token_balances, &TokenType::Stamina);
+    gs.retreat_from_combat().expect("retreat");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Ore),
+        75
+    );
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Lumber),
+        31
+    );
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Stamina),
+        stamina_before
+    );
+    assert_eq!(gs.last_encounter_result, Some(EncounterOutcome::PlayerLost));
+}
+
+#[test]
+fn validate_rejects_material_fractions_outside_1_to_100() {
+    for (percent, valid) in [(0, false), (1, true), (100, true), (101, false)] {
+        let gs = start_gnome_with(RetreatCost::MaterialFraction { percent });
+        let result = gs.library.validate_card_effects();
+        assert_eq!(result.is_ok(), valid, "{}%: {:?}", percent, result);
+        if let Err(errors) = result {
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.contains("retreat material fraction")),
+                "{:?}",
+                errors
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::{deck_in_hand, effect, game_with, library_only, template};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardEffectKind, CardKind, CombatantDef, ConcreteEffect,
+    ConcreteEffectCost, EffectTarget, EncounterKind, EncounterOutcome, EnemyCardDef, LootEntry,
+    LootTable, RetreatCost, Token, TokenLifecycle, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const ENCOUNTER_CARD: usize = 1;
+
+/// Library whose enemy only holds an attack it cannot pay for, so it loses on its first play.
+fn rewards_library(loot_table: Option<LootTable>) -> Library {
+    let mut lib = Library::new();
+    lib.add_card(
+        template(
+            CardEffectKind::LoseTokens {
+                target: EffectTarget::OnOpponent,
+                token_type: TokenType::Health,
+                min: 100,
+                max: 500,
+                costs: vec![],
+                duration: TokenLifecycle::PersistentCounter,
+            },
+            true,
+        ),
+        library_only(),
+    );
+    let unpayable_attack = EnemyCardDef {
+        effects: vec![ConcreteEffect {
+            rolled_costs: vec![ConcreteEffectCost {
+                token_type: TokenType::Stamina,
+                rolled_percent: 100,
+            }],
+            ..effect(0, 100)
+        }],
+        counts: deck_in_hand(1),
+    };
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Combat {
+                combatant_def: CombatantDef {
+                    initial_tokens: HashMap::from([(Token::persistent(TokenType::Health), 100)]),
+                    attack_deck: vec![unpayable_attack],
+                    retreat_cost: RetreatCost::Impossible,
+                    rewards: HashMap::from([(Token::persistent(TokenType::Renown), 5)]),
+                    loot_table,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    lib
+}
+
+fn ore_and_insight_loot() -> LootTable {
+    LootTable {
+        rolls: 3,
+        entries: vec![
+            LootEntry {
+                token_type: TokenType::Ore,
+                min: 10,
+                max: 20,
+                weight: 1,
+            },
+            LootEntry {
+                token_type: TokenType::Insight,
+                min: 1,
+                max: 5,
+                weight: 1,
+            },
+        ],
+    }
+}
+
+fn win_combat(seed: u64, loot_table: Option<LootTable>) -> GameState {
+    let (mut gs, mut rng) = game_with(rewards_library(loot_table), seed);
+    assert!(gs.library.validate_card_effects().is_ok());
+    gs.start_combat(ENCOUNTER_CARD, &mut rng)
+        .expect("start combat");
+    gs.advance_combat_phase().expect("advance to attacking");
+    gs.resolve_enemy_play(&mut rng).expect("enemy play");
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerWon]);
+    gs
+}
+
+#[test]
+fn victory_grants_fixed_rewards_and_records_breakdown() {
+    let gs = win_combat(30, None);
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Renown),
+        5
+    );
+    let breakdown = gs.encounter_rewards.get(&0).expect("reward breakdown");
+    assert_eq!(
+        breakdown.rewards,
+        HashMap::from([(Token::persistent(TokenType::Renown), 5)])
+    );
+    assert!(breakdown.loot.is_empty());
+}
+
+#[test]
+fn loot_rolls_are_granted_and_seed_deterministic() {
+    let gs = win_combat(30, Some(ore_and_insight_loot()));
+    let breakdown = gs.encounter_rewards.get(&0).expect("reward breakdown");
+    let ore = token_balance_by_type(&breakdown.loot, &TokenType::Ore);
+    let insight = token_balance_by_type(&breakdown.loot, &TokenType::Insight);
+    assert!(ore > 0 || insight > 0);
+    assert!((0..=60).contains(&ore));
+    assert!((0..=15).contains(&insight));
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Ore),
+        ore
+    );
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Insight),
+        insight
+    );
+
+    let replayed = win_combat(30, Some(ore_and_insight_loot()));
+    assert_eq!(replayed.encounter_rewards, gs.encounter_rewards);
+}
+
+#[test]
+fn validate_rejects_invalid_loot_ranges() {
+    let mut loot = ore_and_insight_loot();
+    loot.entries[0].min = 30;
+    loot.entries[1].min = -1;
+    let errors = rewards_library(Some(loot))
+        .validate_card_effects()
+        .expect_err("invalid loot");
+    assert_eq!(errors.len(), 2);
+}
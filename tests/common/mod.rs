@@ -0,0 +1,122 @@
+//! Builders shared by the tests that set up a game with a `Library` of their own.
+#![allow(dead_code)]
+
+use my_little_cardgame::library::types::{
+    CardCounts, CardEffectKind, CardKind, ConcreteEffect, DeckCounts, EncounterState, FishCard,
+    FishingCardEffect, FishingEncounterState, HerbalismCardEffect, HerbalismMatchMode, PlantCard,
+    PlantCharacteristic, TokenAmount, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use rand::SeedableRng;
+
+/// One copy, kept in the library.
+pub fn library_only() -> CardCounts {
+    CardCounts {
+        library: 1,
+        deck: 0,
+        hand: 0,
+        discard: 0,
+    }
+}
+
+/// `count` copies in hand.
+pub fn in_hand(count: u32) -> CardCounts {
+    CardCounts {
+        library: 0,
+        deck: 0,
+        hand: count,
+        discard: 0,
+    }
+}
+
+/// `count` copies in an encounter deck's hand.
+pub fn deck_in_hand(count: u32) -> DeckCounts {
+    DeckCounts {
+        deck: 0,
+        hand: count,
+        discard: 0,
+    }
+}
+
+pub fn amount(token_type: TokenType, amount: i64) -> TokenAmount {
+    TokenAmount {
+        token_type,
+        amount,
+        cap: None,
+    }
+}
+
+/// A concrete effect rolled at `rolled_value`, without costs.
+pub fn effect(effect_id: usize, rolled_value: i64) -> ConcreteEffect {
+    ConcreteEffect {
+        effect_id,
+        rolled_value,
+        rolled_costs: vec![],
+        rolled_cap: None,
+        rolled_gain_percent: None,
+    }
+}
+
+/// An effect template for the enemy's cards or the player's.
+pub fn template(kind: CardEffectKind, enemy: bool) -> CardKind {
+    if enemy {
+        CardKind::EnemyCardEffect { kind }
+    } else {
+        CardKind::PlayerCardEffect { kind }
+    }
+}
+
+/// A costless herbalism card removing plants with any of `types`.
+pub fn herbalism_card(types: Vec<PlantCharacteristic>, gains: Vec<TokenAmount>) -> CardKind {
+    CardKind::Herbalism {
+        herbalism_effect: HerbalismCardEffect {
+            costs: vec![],
+            match_mode: HerbalismMatchMode::Or { types },
+            gains,
+            drains: vec![],
+        },
+    }
+}
+
+/// A plant in hand with one characteristic.
+pub fn plant(characteristic: PlantCharacteristic, potency: u32) -> PlantCard {
+    PlantCard {
+        characteristics: vec![characteristic],
+        counts: deck_in_hand(1),
+        potency,
+    }
+}
+
+/// A costless fishing card casting `value`.
+pub fn fishing_card(value: i64) -> CardKind {
+    CardKind::Fishing {
+        fishing_effect: FishingCardEffect {
+            values: vec![value],
+            costs: vec![],
+            gains: vec![],
+            drains: vec![],
+        },
+    }
+}
+
+pub fn fish(value: i64, hand: u32) -> FishCard {
+    FishCard {
+        value,
+        counts: deck_in_hand(hand),
+    }
+}
+
+pub fn fishing(gs: &GameState) -> &FishingEncounterState {
+    match &gs.current_encounter {
+        Some(EncounterState::Fishing(f)) => f,
+        _ => panic!("expected active fishing encounter"),
+    }
+}
+
+/// A new game seeded with `seed` that plays with `library` instead of the starting one.
+pub fn game_with(library: Library, seed: u64) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(seed);
+    let mut gs = GameState::new_with_rng(&mut rng);
+    gs.library = library;
+    (gs, rng)
+}
@@ -0,0 +1,72 @@
+//! Runs alone in its own binary: it sets `ACTION_LOG_FILE`, which every `rocket_initialize`
+//! in the process would pick up.
+
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::compact_log;
+use my_little_cardgame::library::types::ActionPayload;
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::lock::Mutex;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use std::sync::Arc;
+
+#[test]
+fn server_converts_a_compact_log_file_before_appending_to_it() {
+    let dir = std::env::temp_dir().join(format!(
+        "my_little_cardgame_compact_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let log_path = dir.join("actions.log");
+    let saved = ActionLog::new();
+    saved.append("NewGame", ActionPayload::SetSeed { seed: 4 });
+    saved.append("NewGame", ActionPayload::SetSeed { seed: 5 });
+    std::fs::write(&log_path, saved.export_compact(true)).unwrap();
+    std::env::set_var("ACTION_LOG_FILE", &log_path);
+
+    for seed in [6, 7] {
+        let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+        let status = client
+            .post("/action")
+            .header(ContentType::JSON)
+            .body(format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed))
+            .dispatch()
+            .status();
+        assert_eq!(status, Status::Created);
+        client
+            .rocket()
+            .state::<Arc<Mutex<GameState>>>()
+            .expect("managed game state")
+            .try_lock()
+            .expect("no request in flight")
+            .shutdown();
+    }
+
+    assert!(!compact_log::is_compact_file(&log_path));
+    let log = ActionLog::load_from_file(log_path.to_str().unwrap()).expect("an NDJSON log");
+    let seeds: Vec<ActionPayload> = log.entries().into_iter().map(|e| e.payload).collect();
+    assert_eq!(
+        seeds,
+        [4, 5, 6, 7]
+            .map(|seed| ActionPayload::SetSeed { seed })
+            .to_vec()
+    );
+    let originals = std::fs::read_dir(&dir)
+        .unwrap()
+        .filter(|e| {
+            e.as_ref()
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with("actions.log.compact.")
+        })
+        .count();
+    assert_eq!(originals, 1, "the compact original is kept");
+
+    std::env::remove_var("ACTION_LOG_FILE");
+    let _ = std::fs::remove_dir_all(&dir);
+}
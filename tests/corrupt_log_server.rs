@@ -0,0 +1,89 @@
+//! Runs alone in its own binary: it sets `ACTION_LOG_FILE`, which every `rocket_initialize`
+//! in the process would pick up.
+
+use my_little_cardgame::action::persistence::snapshot_path;
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::lock::Mutex;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use std::sync::Arc;
+
+fn play_new_game(client: &Client, seed: u64) {
+    let status = client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed))
+        .dispatch()
+        .status();
+    assert_eq!(status, Status::Created);
+}
+
+fn stop(client: Client) {
+    client
+        .rocket()
+        .state::<Arc<Mutex<GameState>>>()
+        .expect("managed game state")
+        .try_lock()
+        .expect("no request in flight")
+        .shutdown();
+}
+
+#[test]
+fn server_moves_an_unreadable_log_aside_instead_of_appending_to_it() {
+    let dir = std::env::temp_dir().join(format!(
+        "my_little_cardgame_corrupt_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let log_path = dir.join("actions.jsonl");
+    let corrupt = "{\"seq\":1,\"payload\":{\"type\":\"SetSeed\",\"seed\":1}}\nnot json\n";
+    std::fs::write(&log_path, corrupt).unwrap();
+    std::fs::write(snapshot_path(&log_path, 1), "{}").unwrap();
+    std::env::set_var("ACTION_LOG_FILE", &log_path);
+
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    play_new_game(&client, 9);
+    stop(client);
+
+    let moved: Vec<std::path::PathBuf> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            p.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("actions.jsonl.unreadable.")
+        })
+        .collect();
+    let moved_log = moved
+        .iter()
+        .find(|p| !p.to_string_lossy().contains(".snapshot."))
+        .expect("the unreadable log is kept");
+    assert_eq!(std::fs::read_to_string(moved_log).unwrap(), corrupt);
+    assert!(
+        !snapshot_path(&log_path, 1).exists(),
+        "its snapshots move along with it"
+    );
+    assert!(snapshot_path(moved_log, 1).exists());
+
+    let fresh = ActionLog::load_from_file(log_path.to_str().unwrap()).expect("a loadable log");
+    assert_eq!(fresh.entries().len(), 1);
+
+    // The next start resumes the new game instead of discarding it again
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    play_new_game(&client, 10);
+    stop(client);
+    let resumed = ActionLog::load_from_file(log_path.to_str().unwrap()).expect("a loadable log");
+    assert_eq!(
+        resumed.entries().iter().map(|e| e.seq).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    std::env::remove_var("ACTION_LOG_FILE");
+    let _ = std::fs::remove_dir_all(&dir);
+}
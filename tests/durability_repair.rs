@@ -0,0 +1,103 @@
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CraftingEncounterState, Discipline, EncounterOutcome, EncounterState,
+    RestEncounterState, Token, TokenType,
+};
+use my_little_cardgame::library::GameState;
+
+fn in_rest() -> GameState {
+    let mut gs = GameState::new();
+    gs.current_encounter = Some(EncounterState::Rest(RestEncounterState {
+        encounter_card_id: 0,
+        outcome: EncounterOutcome::Undecided,
+        rest_tokens: 1,
+    }));
+    gs
+}
+
+fn set(gs: &mut GameState, token_type: TokenType, value: i64) {
+    gs.token_balances
+        .insert(Token::persistent(token_type), value);
+}
+
+fn balance(gs: &GameState, token_type: TokenType) -> i64 {
+    token_balance_by_type(&gs.token_balances, &token_type)
+}
+
+#[test]
+fn every_discipline_starts_at_full_durability() {
+    let gs = GameState::new();
+    for discipline in Discipline::ALL {
+        let max = balance(&gs, discipline.max_durability_token());
+        assert_eq!(max, 10000);
+        assert_eq!(balance(&gs, discipline.durability_token()), max);
+        assert_eq!(gs.repair_cost(&discipline), 0);
+        assert_eq!(
+            discipline.durability_token().max_token(),
+            Some(discipline.max_durability_token())
+        );
+    }
+}
+
+#[test]
+fn repair_cost_scales_with_missing_durability() {
+    let mut gs = in_rest();
+    set(&mut gs, TokenType::MiningDurability, 9955);
+    set(&mut gs, TokenType::Ore, 10);
+    assert_eq!(gs.repair_cost(&Discipline::Mining), 5);
+
+    gs.resolve_repair(&Discipline::Mining).expect("repair");
+    assert_eq!(balance(&gs, TokenType::MiningDurability), 10000);
+    assert_eq!(balance(&gs, TokenType::Ore), 5);
+
+    set(&mut gs, TokenType::HerbalismDurability, 9000);
+    assert_eq!(gs.repair_cost(&Discipline::Herbalism), 100);
+}
+
+#[test]
+fn repair_needs_enough_material() {
+    let mut gs = in_rest();
+    set(&mut gs, TokenType::WoodcuttingDurability, 9000);
+    set(&mut gs, TokenType::Lumber, 99);
+    let err = gs
+        .resolve_repair(&Discipline::Woodcutting)
+        .expect_err("not enough Lumber");
+    assert!(err.contains("Lumber"), "{}", err);
+    assert_eq!(balance(&gs, TokenType::WoodcuttingDurability), 9000);
+    assert_eq!(balance(&gs, TokenType::Lumber), 99);
+}
+
+#[test]
+fn repair_is_rejected_at_full_durability_and_outside_rest_or_crafting() {
+    let mut gs = in_rest();
+    assert!(gs.resolve_repair(&Discipline::Fishing).is_err());
+
+    let mut gs = GameState::new();
+    set(&mut gs, TokenType::FishingDurability, 5000);
+    set(&mut gs, TokenType::Ore, 1000);
+    assert!(gs.resolve_repair(&Discipline::Fishing).is_err());
+    assert_eq!(balance(&gs, TokenType::Ore), 1000);
+}
+
+#[test]
+fn crafted_durability_is_capped_at_the_maximum() {
+    let mut gs = GameState::new();
+    gs.current_encounter = Some(EncounterState::Crafting(CraftingEncounterState {
+        round: 1,
+        encounter_card_id: 0,
+        outcome: EncounterOutcome::Undecided,
+        crafting_tokens: 3,
+        enemy_crafting_deck: vec![],
+        active_craft: None,
+    }));
+    set(&mut gs, TokenType::FishingDurability, 9800);
+    set(&mut gs, TokenType::Ore, 100);
+    gs.resolve_crafting_add_durability(&Discipline::Fishing)
+        .expect("craft durability");
+    assert_eq!(balance(&gs, TokenType::FishingDurability), 10000);
+    assert_eq!(balance(&gs, TokenType::Ore), 50);
+
+    assert!(gs
+        .resolve_crafting_add_durability(&Discipline::Fishing)
+        .is_err());
+    assert_eq!(balance(&gs, TokenType::Ore), 50);
+}
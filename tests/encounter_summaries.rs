@@ -0,0 +1,207 @@
+mod common;
+
+use common::{amount, deck_in_hand, game_with, in_hand};
+use my_little_cardgame::library::types::{
+    CardCounts, CardKind, CombatantDef, CraftingDef, EncounterDiscipline, EncounterKind, FishCard,
+    FishSpecies, FishingDef, LootEntry, LootTable, RetreatCost, Token, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+const COMBAT_CARD: usize = 0;
+const FISHING_CARD: usize = 1;
+
+fn setup() -> GameState {
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Combat {
+                combatant_def: CombatantDef {
+                    initial_tokens: HashMap::from([(Token::persistent(TokenType::Health), 700)]),
+                    retreat_cost: RetreatCost::Impossible,
+                    rewards: HashMap::from([(Token::persistent(TokenType::Ore), 100)]),
+                    loot_table: Some(LootTable {
+                        rolls: 2,
+                        entries: vec![
+                            LootEntry {
+                                token_type: TokenType::Ore,
+                                min: 10,
+                                max: 30,
+                                weight: 1,
+                            },
+                            LootEntry {
+                                token_type: TokenType::Plant,
+                                min: 40,
+                                max: 40,
+                                weight: 3,
+                            },
+                        ],
+                    }),
+                    entry_cost: vec![amount(TokenType::Stamina, 50), amount(TokenType::Ore, 20)],
+                    ..Default::default()
+                },
+            },
+        },
+        in_hand(2),
+    );
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Fishing {
+                fishing_def: FishingDef {
+                    valid_range_min: 0,
+                    valid_range_max: 100,
+                    max_turns: 8,
+                    win_turns_needed: 6,
+                    fish_deck: vec![FishCard {
+                        value: 50,
+                        counts: deck_in_hand(4),
+                    }],
+                    rewards: HashMap::from([(Token::persistent(TokenType::Fish), 200)]),
+                    species: vec![
+                        FishSpecies {
+                            name: "Pike".to_string(),
+                            behaviors: vec![],
+                            rewards: HashMap::from([(Token::persistent(TokenType::Fish), 300)]),
+                        },
+                        FishSpecies {
+                            name: "Carp".to_string(),
+                            behaviors: vec![],
+                            rewards: HashMap::new(),
+                        },
+                    ],
+                    ..Default::default()
+                },
+            },
+        },
+        CardCounts {
+            library: 0,
+            deck: 1,
+            hand: 0,
+            discard: 0,
+        },
+    );
+    game_with(lib, 40).0
+}
+
+fn balance(gs: &GameState, token_type: TokenType) -> i64 {
+    gs.token_balances
+        .get(&Token::persistent(token_type))
+        .copied()
+        .unwrap_or(0)
+}
+
+#[test]
+fn entry_cost_is_paid_only_when_fully_affordable() {
+    let mut gs = setup();
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Stamina), 60);
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Ore), 10);
+
+    assert!(gs.pay_entry_cost(COMBAT_CARD).is_err());
+    assert_eq!(balance(&gs, TokenType::Stamina), 60, "nothing was deducted");
+    assert_eq!(balance(&gs, TokenType::Ore), 10);
+
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Ore), 25);
+    gs.pay_entry_cost(COMBAT_CARD).expect("affordable now");
+    assert_eq!(balance(&gs, TokenType::Stamina), 10);
+    assert_eq!(balance(&gs, TokenType::Ore), 5);
+
+    gs.pay_entry_cost(FISHING_CARD).expect("free encounter");
+}
+
+#[test]
+fn nothing_is_paid_when_the_encounter_cannot_start() {
+    let mut gs = setup();
+    let crafting_card = gs.library.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Crafting {
+                crafting_def: CraftingDef {
+                    initial_crafting_tokens: 10,
+                    entry_cost: vec![amount(TokenType::Stamina, 30)],
+                    ..Default::default()
+                },
+            },
+        },
+        in_hand(1),
+    );
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Stamina), 100);
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Ore), 20);
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(41);
+    gs.start_picked_encounter(COMBAT_CARD, &mut rng)
+        .expect("combat starts");
+    assert_eq!(balance(&gs, TokenType::Stamina), 50);
+
+    // A crafting encounter can't start in the middle of combat
+    let rng_before = rng.clone();
+    let err = gs
+        .start_picked_encounter(crafting_card, &mut rng)
+        .expect_err("already in an encounter");
+    assert!(err.contains("Already in an encounter"), "{}", err);
+    assert_eq!(
+        balance(&gs, TokenType::Stamina),
+        50,
+        "the cost was not paid"
+    );
+    assert!(rng == rng_before, "no randomness was consumed");
+    assert!(
+        gs.library.encounter_contains(crafting_card),
+        "the card is still in hand"
+    );
+}
+
+#[test]
+fn hand_summaries_describe_only_hand_encounters() {
+    let mut gs = setup();
+    let summaries = gs.encounter_hand_summaries();
+    assert_eq!(summaries.len(), 1, "the fishing spot is still in the deck");
+
+    let combat = &summaries[0];
+    assert_eq!(combat.card_id, COMBAT_CARD);
+    assert_eq!(combat.hand_count, 2);
+    assert_eq!(combat.discipline, EncounterDiscipline::Combat);
+    assert_eq!(combat.difficulty_score, 700);
+    assert_eq!(
+        combat.entry_cost,
+        vec![amount(TokenType::Stamina, 50), amount(TokenType::Ore, 20)]
+    );
+    // 100 fixed Ore, plus 2 rolls at 1/4 chance of 20 Ore and 3/4 chance of 40 Plant
+    assert_eq!(
+        combat.expected_rewards,
+        HashMap::from([
+            (Token::persistent(TokenType::Ore), 110),
+            (Token::persistent(TokenType::Plant), 60),
+        ])
+    );
+
+    gs.library.encounter_draw_to_hand(3);
+    let summaries = gs.encounter_hand_summaries();
+    let fishing = summaries
+        .iter()
+        .find(|s| s.card_id == FISHING_CARD)
+        .expect("fishing spot drawn");
+    assert_eq!(fishing.discipline, EncounterDiscipline::Fishing);
+    assert_eq!(fishing.difficulty_score, 75, "6 of 8 turns must be won");
+    assert_eq!(
+        fishing.expected_rewards,
+        HashMap::from([(Token::persistent(TokenType::Fish), 350)]),
+        "species rewards are averaged over the species"
+    );
+    assert!(fishing.entry_cost.is_empty());
+}
+
+#[test]
+fn starting_hand_summaries_match_the_encounter_hand() {
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(7);
+    let gs = GameState::new_with_rng(&mut rng);
+    let summaries = gs.encounter_hand_summaries();
+    let summarized: usize = summaries.iter().map(|s| s.hand_count as usize).sum();
+    assert_eq!(summarized, gs.library.encounter_hand().len());
+    for summary in &summaries {
+        assert!(gs.library.encounter_contains(summary.card_id));
+    }
+}
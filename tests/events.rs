@@ -0,0 +1,311 @@
+use my_little_cardgame::rocket_initialize;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::serde::json::serde_json;
+
+fn post_action(client: &Client, json: &str) -> Status {
+    client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch()
+        .status()
+}
+
+fn first_encounter_card(client: &Client) -> u64 {
+    serde_json::from_str::<serde_json::Value>(
+        &client
+            .get("/library/cards?location=Hand")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default()
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter(|c| c["kind"]["card_kind"] == "Encounter")
+    .find_map(|c| c["id"].as_u64())
+    .expect("an encounter card in hand")
+}
+
+/// Stop the event stream and parse what it sent as (event, id, data) triples.
+fn received_ids(
+    client: &Client,
+    stream: LocalResponse,
+) -> Vec<(String, String, serde_json::Value)> {
+    client.rocket().shutdown().notify();
+    let body = stream.into_string().unwrap_or_default();
+    body.split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let field = |name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(|v| v.trim().to_string())
+                    .unwrap_or_default()
+            };
+            (
+                field("event:"),
+                field("id:"),
+                serde_json::from_str(&field("data:")).expect("json data"),
+            )
+        })
+        .collect()
+}
+
+/// Like `received_ids`, with each id parsed as a plain seq.
+fn received(client: &Client, stream: LocalResponse) -> Vec<(String, u64, serde_json::Value)> {
+    received_ids(client, stream)
+        .into_iter()
+        .map(|(name, id, data)| (name, id.parse().expect("numeric id"), data))
+        .collect()
+}
+
+fn new_game(client: &Client, seed: u64) {
+    assert_eq!(
+        post_action(
+            client,
+            &format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed)
+        ),
+        Status::Created
+    );
+}
+
+fn rewind(client: &Client, to_seq: u64) {
+    let status = client
+        .post(format!("/game/rewind?to_seq={}", to_seq))
+        .dispatch()
+        .status();
+    assert_eq!(status, Status::Ok);
+}
+
+/// (event, id, seed or to_seq) for each received event.
+fn summary(events: &[(String, String, serde_json::Value)]) -> Vec<(&str, &str, serde_json::Value)> {
+    events
+        .iter()
+        .map(|(name, id, data)| {
+            let value = match name.as_str() {
+                "rewound" => data["to_seq"].clone(),
+                _ => data["entry"]["payload"]["seed"].clone(),
+            };
+            (name.as_str(), id.as_str(), value)
+        })
+        .collect()
+}
+
+#[test]
+fn events_stream_reports_what_each_action_changed() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let stream = client.get("/events").dispatch();
+    assert_eq!(stream.status(), Status::Ok);
+    assert_eq!(stream.content_type(), Some(ContentType::EventStream));
+
+    assert_eq!(
+        post_action(&client, r#"{"action_type":"NewGame","seed":7}"#),
+        Status::Created
+    );
+    let card_id = first_encounter_card(&client);
+    assert_eq!(
+        post_action(
+            &client,
+            &format!(
+                r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+                card_id
+            )
+        ),
+        Status::Created
+    );
+    // Combat can only be retreated from, the other encounters aborted
+    assert!([
+        r#"{"action_type":"EncounterAbort"}"#,
+        r#"{"action_type":"EncounterRetreat"}"#
+    ]
+    .iter()
+    .any(|action| post_action(&client, action) == Status::Created));
+    // Rejected actions publish nothing
+    assert_eq!(
+        post_action(&client, r#"{"action_type":"EncounterAbort"}"#),
+        Status::BadRequest
+    );
+    let balances: Vec<serde_json::Value> = serde_json::from_str(
+        &client
+            .get("/player/tokens")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+
+    let events = received(&client, stream);
+    let names_at = |seq: u64| -> Vec<&str> {
+        events
+            .iter()
+            .filter(|(_, id, _)| *id == seq)
+            .map(|(name, _, _)| name.as_str())
+            .filter(|name| *name != "token-changed")
+            .collect()
+    };
+    assert_eq!(names_at(1), vec!["action-appended"]);
+    assert_eq!(
+        names_at(2),
+        vec!["action-appended", "encounter-started", "phase-changed"]
+    );
+    assert_eq!(
+        names_at(3),
+        vec!["action-appended", "encounter-finished", "phase-changed"]
+    );
+    assert!(events.iter().all(|(_, id, _)| *id <= 3));
+
+    for (name, id, data) in &events {
+        assert_eq!(data["event"], name.as_str());
+        assert_eq!(data["seq"], *id);
+    }
+    let data_of = |name: &str, seq: u64| {
+        events
+            .iter()
+            .find(|(n, id, _)| n == name && *id == seq)
+            .map(|(_, _, data)| data.clone())
+            .unwrap_or_default()
+    };
+    assert_eq!(data_of("action-appended", 1)["entry"]["payload"]["seed"], 7);
+    assert_eq!(
+        data_of("encounter-started", 2)["encounter_card_id"],
+        card_id
+    );
+    assert_eq!(data_of("phase-changed", 2)["from"], "NoEncounter");
+    assert_eq!(data_of("phase-changed", 2)["to"], "InEncounter");
+    // Each token's last change lands on its current balance
+    let changes: Vec<&serde_json::Value> = events
+        .iter()
+        .filter(|(name, _, _)| name == "token-changed")
+        .map(|(_, _, data)| data)
+        .collect();
+    assert!(!changes.is_empty());
+    for change in &changes {
+        assert_ne!(change["from"], change["to"]);
+        let last = changes
+            .iter()
+            .rfind(|c| c["token"] == change["token"])
+            .expect("itself");
+        let balance = balances
+            .iter()
+            .find(|b| b["token"] == change["token"])
+            .map_or(0.into(), |b| b["value"].clone());
+        assert_eq!(last["to"], balance, "{}", change["token"]);
+    }
+}
+
+#[test]
+fn reconnecting_with_last_event_id_resumes_from_the_action_log() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    for seed in [1, 2, 3, 4] {
+        assert_eq!(
+            post_action(
+                &client,
+                &format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed)
+            ),
+            Status::Created
+        );
+    }
+    let stream = client
+        .get("/events")
+        .header(Header::new("Last-Event-ID", "2"))
+        .dispatch();
+    assert_eq!(
+        post_action(&client, r#"{"action_type":"NewGame","seed":5}"#),
+        Status::Created
+    );
+
+    let events = received(&client, stream);
+    let appended: Vec<(u64, serde_json::Value)> = events
+        .iter()
+        .filter(|(name, _, _)| name == "action-appended")
+        .map(|(_, id, data)| (*id, data["entry"]["payload"]["seed"].clone()))
+        .collect();
+    assert_eq!(
+        appended,
+        vec![(3, 3.into()), (4, 4.into()), (5, 5.into())],
+        "missed actions come from the log, then live ones follow"
+    );
+    assert!(
+        events.iter().all(|(_, id, _)| *id >= 3),
+        "nothing before Last-Event-ID is resent"
+    );
+}
+
+#[test]
+fn rewinds_are_published_and_change_the_event_ids() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let stream = client.get("/events").dispatch();
+    for seed in [1, 2, 3] {
+        new_game(&client, seed);
+    }
+    rewind(&client, 1);
+    new_game(&client, 9);
+
+    let events = received_ids(&client, stream);
+    let events: Vec<_> = events
+        .into_iter()
+        .filter(|(name, _, _)| name == "action-appended" || name == "rewound")
+        .collect();
+    assert_eq!(
+        summary(&events),
+        vec![
+            ("action-appended", "1", 1.into()),
+            ("action-appended", "2", 2.into()),
+            ("action-appended", "3", 3.into()),
+            ("rewound", "1-1", 1.into()),
+            ("action-appended", "2-1", 9.into()),
+        ]
+    );
+}
+
+#[test]
+fn resuming_past_a_rewind_starts_with_the_rewind() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    for seed in [1, 2, 3, 4] {
+        new_game(&client, seed);
+    }
+    rewind(&client, 2);
+    new_game(&client, 9);
+
+    // Last saw seq 4 before the rewind: seq 3 and 4 are not the actions it saw any more
+    let stream = client
+        .get("/events")
+        .header(Header::new("Last-Event-ID", "4"))
+        .dispatch();
+    let events = received_ids(&client, stream);
+    assert_eq!(
+        summary(&events),
+        vec![
+            ("rewound", "2-1", 2.into()),
+            ("action-appended", "3-1", 9.into()),
+        ]
+    );
+
+    // Already saw the rewind: only what came after it
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    for seed in [1, 2, 3] {
+        new_game(&client, seed);
+    }
+    rewind(&client, 1);
+    new_game(&client, 9);
+    let stream = client
+        .get("/events")
+        .header(Header::new("Last-Event-ID", "1-1"))
+        .dispatch();
+    new_game(&client, 10);
+    let events: Vec<_> = received_ids(&client, stream)
+        .into_iter()
+        .filter(|(name, _, _)| name == "action-appended" || name == "rewound")
+        .collect();
+    assert_eq!(
+        summary(&events),
+        vec![
+            ("action-appended", "2-1", 9.into()),
+            ("action-appended", "3-1", 10.into()),
+        ]
+    );
+}
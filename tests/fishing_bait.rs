@@ -0,0 +1,101 @@
+mod common;
+
+use common::{fish, fishing, fishing_card, game_with, in_hand, library_only};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, EncounterKind, FishingBait, FishingDef, Token, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const CAST_CARD: usize = 0;
+const ENCOUNTER_CARD: usize = 1;
+
+fn start(plant: i64) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(fishing_card(500), in_hand(10));
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Fishing {
+                fishing_def: FishingDef {
+                    valid_range_min: 100,
+                    valid_range_max: 300,
+                    max_turns: 8,
+                    win_turns_needed: 4,
+                    fish_deck: vec![fish(100, 3), fish(300, 2), fish(700, 2)],
+                    rewards: HashMap::from([(Token::persistent(TokenType::Fish), 1000)]),
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, 37);
+    gs.token_balances
+        .insert(Token::persistent(TokenType::Plant), plant);
+    gs.start_fishing_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start fishing");
+    (gs, rng)
+}
+
+fn plant(gs: &GameState) -> i64 {
+    token_balance_by_type(&gs.token_balances, &TokenType::Plant)
+}
+
+#[test]
+fn chum_moves_the_highest_fish_out_of_hand() {
+    let (mut gs, mut rng) = start(500);
+    gs.resolve_fishing_bait(&FishingBait::Chum, &mut rng)
+        .expect("chum");
+    let hand: Vec<u32> = fishing(&gs)
+        .fish_deck
+        .iter()
+        .map(|c| c.counts.hand)
+        .collect();
+    assert_eq!(hand, vec![3, 1, 0]);
+    assert_eq!(fishing(&gs).bait, Some(FishingBait::Chum));
+    assert_eq!(plant(&gs), 400);
+}
+
+#[test]
+fn glowworm_widens_the_valid_range() {
+    let (mut gs, mut rng) = start(500);
+    gs.resolve_fishing_bait(&FishingBait::Glowworm, &mut rng)
+        .expect("glowworm");
+    let f = fishing(&gs);
+    assert_eq!((f.valid_range_min, f.valid_range_max), (50, 350));
+    assert_eq!(
+        f.encounter_tokens
+            .get(&Token::persistent(TokenType::FishingRangeMax)),
+        Some(&350)
+    );
+    assert_eq!(plant(&gs), 350);
+}
+
+#[test]
+fn bait_requires_plant() {
+    let (mut gs, mut rng) = start(50);
+    let err = gs
+        .resolve_fishing_bait(&FishingBait::Chum, &mut rng)
+        .expect_err("not enough Plant");
+    assert!(err.contains("Plant"), "{}", err);
+    assert_eq!(fishing(&gs).bait, None);
+    assert_eq!(plant(&gs), 50);
+}
+
+#[test]
+fn bait_is_only_valid_once_and_on_round_one() {
+    let (mut gs, mut rng) = start(500);
+    gs.resolve_fishing_bait(&FishingBait::Chum, &mut rng)
+        .expect("chum");
+    assert!(gs
+        .resolve_fishing_bait(&FishingBait::Glowworm, &mut rng)
+        .is_err());
+
+    let (mut gs, mut rng) = start(500);
+    gs.resolve_player_fishing_card(CAST_CARD, &mut rng)
+        .expect("cast");
+    assert!(gs
+        .resolve_fishing_bait(&FishingBait::Chum, &mut rng)
+        .is_err());
+    assert_eq!(plant(&gs), 500);
+}
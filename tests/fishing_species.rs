@@ -0,0 +1,149 @@
+mod common;
+
+use common::{fish, fishing, fishing_card, game_with, in_hand, library_only};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, EncounterKind, EncounterOutcome, EncounterState, FishBehavior,
+    FishSpecies, FishingDef, Token, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const CAST_CARD: usize = 0;
+const ENCOUNTER_CARD: usize = 1;
+
+fn species(behaviors: Vec<FishBehavior>, fish_reward: i64) -> FishSpecies {
+    FishSpecies {
+        name: "Test fish".to_string(),
+        behaviors,
+        rewards: HashMap::from([(Token::persistent(TokenType::Fish), fish_reward)]),
+    }
+}
+
+/// A 1000-value cast against a 100/900 fish deck: only the 100 fish lands in the 800..=900 range.
+fn start(species: Vec<FishSpecies>) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(fishing_card(1000), in_hand(10));
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Fishing {
+                fishing_def: FishingDef {
+                    valid_range_min: 800,
+                    valid_range_max: 900,
+                    max_turns: 8,
+                    win_turns_needed: 8,
+                    fish_deck: vec![fish(100, 3), fish(900, 1)],
+                    rewards: HashMap::from([(Token::persistent(TokenType::Fish), 1000)]),
+                    species,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, 36);
+    assert!(gs.library.validate_card_effects().is_ok());
+    gs.start_fishing_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start fishing");
+    (gs, rng)
+}
+
+fn set_fish_amount(gs: &mut GameState, amount: i64) {
+    if let Some(EncounterState::Fishing(f)) = &mut gs.current_encounter {
+        f.encounter_tokens
+            .insert(Token::persistent(TokenType::FishAmount), amount);
+    }
+}
+
+#[test]
+fn greedy_fish_plays_its_highest_card_when_fish_amount_is_high() {
+    let (mut gs, mut rng) = start(vec![species(
+        vec![FishBehavior::Greedy { min_fish_amount: 2 }],
+        0,
+    )]);
+    set_fish_amount(&mut gs, 2);
+    gs.resolve_player_fishing_card(CAST_CARD, &mut rng)
+        .expect("cast");
+    let f = fishing(&gs);
+    assert_eq!(f.fish_deck[1].counts.discard, 1);
+    assert_eq!(f.turns_won, 0);
+    assert_eq!(f.turns_lost, 1);
+}
+
+#[test]
+fn skittish_fish_escapes_after_lost_turns() {
+    let (mut gs, mut rng) = start(vec![species(
+        vec![
+            FishBehavior::Greedy { min_fish_amount: 1 },
+            FishBehavior::Skittish { lost_turns: 1 },
+        ],
+        0,
+    )]);
+    gs.resolve_player_fishing_card(CAST_CARD, &mut rng)
+        .expect("cast");
+    assert!(gs.current_encounter.is_none());
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerLost]);
+}
+
+#[test]
+fn telegraphed_fish_plays_the_revealed_card() {
+    let (mut gs, mut rng) = start(vec![species(vec![FishBehavior::Telegraphed], 0)]);
+    for _ in 0..3 {
+        let revealed = fishing(&gs).revealed_fish.expect("next fish is revealed");
+        let before = fishing(&gs).fish_deck[revealed].counts.discard;
+        gs.resolve_player_fishing_card(CAST_CARD, &mut rng)
+            .expect("cast");
+        assert_eq!(fishing(&gs).fish_deck[revealed].counts.discard, before + 1);
+    }
+}
+
+#[test]
+fn species_rewards_are_paid_on_top_of_spot_rewards() {
+    let (mut gs, _) = start(vec![species(vec![], 250)]);
+    assert_eq!(
+        fishing(&gs).species.as_ref().map(|s| s.behaviors.len()),
+        Some(0)
+    );
+    let before = token_balance_by_type(&gs.token_balances, &TokenType::Fish);
+    gs.conclude_fishing_encounter().expect("conclude");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Fish) - before,
+        1250
+    );
+}
+
+#[test]
+fn plain_spot_has_no_species() {
+    let (gs, _) = start(vec![]);
+    let f = fishing(&gs);
+    assert!(f.species.is_none());
+    assert!(f.revealed_fish.is_none());
+}
+
+#[test]
+fn validate_rejects_invalid_behaviors() {
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Fishing {
+                fishing_def: FishingDef {
+                    valid_range_min: 0,
+                    valid_range_max: 100,
+                    max_turns: 4,
+                    win_turns_needed: 2,
+                    fish_deck: vec![fish(100, 1)],
+                    species: vec![species(
+                        vec![
+                            FishBehavior::Greedy { min_fish_amount: 0 },
+                            FishBehavior::Skittish { lost_turns: 0 },
+                        ],
+                        0,
+                    )],
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let errors = lib.validate_card_effects().expect_err("invalid behaviors");
+    assert_eq!(errors.len(), 2);
+}
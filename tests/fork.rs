@@ -0,0 +1,197 @@
+use my_little_cardgame::game::MAX_FORKED_GAMES;
+use my_little_cardgame::rocket_initialize;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+
+fn post_json(client: &Client, uri: &str, json: &str) -> (Status, serde_json::Value) {
+    let resp = client
+        .post(uri.to_string())
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch();
+    let status = resp.status();
+    let body = serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
+    (status, body)
+}
+
+fn get_json(client: &Client, uri: &str) -> serde_json::Value {
+    serde_json::from_str(&client.get(uri).dispatch().into_string().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+/// Token balances sorted, so HashMap order doesn't matter.
+fn tokens(client: &Client, uri: &str) -> Vec<String> {
+    let mut balances: Vec<String> = get_json(client, uri)
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    balances.sort();
+    balances
+}
+
+fn log_len(client: &Client) -> usize {
+    get_json(client, "/actions/log")["entries"]
+        .as_array()
+        .map_or(0, |e| e.len())
+}
+
+fn fork(client: &Client, from_seq: u64) -> (Status, serde_json::Value) {
+    post_json(client, &format!("/game/fork?from_seq={}", from_seq), "")
+}
+
+fn mining_encounter(client: &Client) -> u64 {
+    get_json(client, "/encounters/hand")
+        .as_array()
+        .and_then(|s| s.iter().find(|s| s["discipline"] == "Mining").cloned())
+        .and_then(|s| s["card_id"].as_u64())
+        .expect("a mining encounter in hand")
+}
+
+#[test]
+fn fork_replays_the_prefix_into_an_independent_game() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_json(&client, "/action", r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    let tokens_at_start = tokens(&client, "/player/tokens");
+    let pick = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        mining_encounter(&client)
+    );
+    let (status, _) = post_json(&client, "/action", &pick);
+    assert_eq!(status, Status::Created);
+    let original_tokens = tokens(&client, "/player/tokens");
+
+    let (status, body) = fork(&client, 1);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["from_seq"], 1);
+    let game_id = body["game_id"].as_u64().expect("a game id");
+    let fork_tokens = format!("/game/{}/player/tokens", game_id);
+    assert_eq!(tokens(&client, &fork_tokens), tokens_at_start);
+
+    // The fork has its own log and RNG: picking the same encounter there continues at seq 2
+    // and ends in the same state as the original did
+    let (status, entry) = post_json(&client, &format!("/game/{}/action", game_id), &pick);
+    assert_eq!(status, Status::Created);
+    assert_eq!(entry["seq"], 2);
+    assert_eq!(tokens(&client, &fork_tokens), original_tokens);
+    let (status, _) = post_json(
+        &client,
+        &format!("/game/{}/action", game_id),
+        r#"{"action_type":"EncounterAbort"}"#,
+    );
+    assert_eq!(status, Status::Created);
+
+    // The original is untouched
+    assert_eq!(log_len(&client), 2);
+    assert_eq!(tokens(&client, "/player/tokens"), original_tokens);
+
+    let (status, second) = fork(&client, 2);
+    assert_eq!(status, Status::Ok);
+    assert_ne!(second["game_id"], game_id);
+}
+
+#[test]
+fn fork_rejects_unknown_seqs_and_games() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_json(&client, "/action", r#"{"action_type":"NewGame","seed":42}"#);
+    let (status, _) = fork(&client, 2);
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, _) = post_json(
+        &client,
+        "/game/99/action",
+        r#"{"action_type":"EncounterAbort"}"#,
+    );
+    assert_eq!(status, Status::NotFound);
+    assert_eq!(
+        client.get("/game/99/player/tokens").dispatch().status(),
+        Status::NotFound
+    );
+}
+
+#[test]
+fn fork_encounter_and_log_are_readable_until_deleted() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_json(&client, "/action", r#"{"action_type":"NewGame","seed":42}"#);
+    let (_, body) = fork(&client, 1);
+    let game_id = body["game_id"].as_u64().expect("a game id");
+    let encounter_uri = format!("/game/{}/encounter", game_id);
+    assert_eq!(
+        client.get(encounter_uri.as_str()).dispatch().status(),
+        Status::NotFound,
+        "no encounter picked in the fork yet"
+    );
+
+    let pick = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        mining_encounter(&client)
+    );
+    let (status, _) = post_json(&client, &format!("/game/{}/action", game_id), &pick);
+    assert_eq!(status, Status::Created);
+    assert_eq!(
+        get_json(&client, &encounter_uri)["encounter_state_type"],
+        "Mining"
+    );
+    assert_eq!(
+        client.get("/encounter").dispatch().status(),
+        Status::NotFound,
+        "the served game has no encounter"
+    );
+
+    let log = get_json(&client, &format!("/game/{}/actions/log", game_id));
+    let types: Vec<&str> = log["entries"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .filter_map(|e| e["payload"]["type"].as_str())
+        .collect();
+    assert_eq!(types, vec!["SetSeed", "DrawEncounter"]);
+    let page = get_json(
+        &client,
+        &format!("/game/{}/actions/log?from_seq=2&limit=5", game_id),
+    );
+    assert_eq!(page["entries"].as_array().map(|e| e.len()), Some(1));
+    assert_eq!(page["limit"], 5);
+    assert_eq!(log_len(&client), 1);
+
+    let delete_uri = format!("/game/{}", game_id);
+    assert_eq!(
+        client.delete(delete_uri.as_str()).dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get(encounter_uri.as_str()).dispatch().status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        client
+            .get(format!("/game/{}/actions/log", game_id))
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        client.delete(delete_uri.as_str()).dispatch().status(),
+        Status::NotFound
+    );
+}
+
+#[test]
+fn forking_past_the_cap_drops_the_oldest_fork() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_json(&client, "/action", r#"{"action_type":"NewGame","seed":42}"#);
+    let game_ids: Vec<u64> = (0..=MAX_FORKED_GAMES)
+        .map(|_| fork(&client, 1).1["game_id"].as_u64().expect("a game id"))
+        .collect();
+    let status_of = |game_id: &u64| {
+        client
+            .get(format!("/game/{}/player/tokens", game_id))
+            .dispatch()
+            .status()
+    };
+    assert_eq!(status_of(&game_ids[0]), Status::NotFound);
+    assert!(game_ids[1..].iter().all(|id| status_of(id) == Status::Ok));
+}
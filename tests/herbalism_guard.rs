@@ -0,0 +1,123 @@
+mod common;
+
+use common::{amount, game_with, herbalism_card, in_hand, library_only, plant};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, EncounterKind, EncounterState, HerbalismDef,
+    PlantCharacteristic, Token, TokenLifecycle, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const GUARD_CARD: usize = 0;
+const REMOVE_FRAGILE: usize = 1;
+const ENCOUNTER_CARD: usize = 2;
+
+/// Four Fragile plants followed by two Thorny ones.
+fn start(seed: u64) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(
+        herbalism_card(vec![], vec![amount(TokenType::HerbalismGuard, 1)]),
+        in_hand(2),
+    );
+    lib.add_card(
+        herbalism_card(vec![PlantCharacteristic::Fragile], vec![]),
+        in_hand(2),
+    );
+    let mut plant_hand = vec![plant(PlantCharacteristic::Fragile, 100); 4];
+    plant_hand.extend(vec![plant(PlantCharacteristic::Thorny, 100); 2]);
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Herbalism {
+                herbalism_def: HerbalismDef {
+                    plant_hand,
+                    rewards: HashMap::from([(Token::persistent(TokenType::Plant), 100)]),
+                    consolation_percent: 0,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, seed);
+    gs.start_herbalism_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start herbalism");
+    (gs, rng)
+}
+
+fn remaining_fragile(gs: &GameState) -> usize {
+    match &gs.current_encounter {
+        Some(EncounterState::Herbalism(h)) => h
+            .plant_hand
+            .iter()
+            .filter(|p| p.counts.hand > 0)
+            .filter(|p| p.characteristics == vec![PlantCharacteristic::Fragile])
+            .count(),
+        _ => panic!("expected active herbalism encounter"),
+    }
+}
+
+fn guarded_plants(gs: &GameState) -> Vec<usize> {
+    match &gs.current_encounter {
+        Some(EncounterState::Herbalism(h)) => h.guarded_plants.clone(),
+        _ => panic!("expected active herbalism encounter"),
+    }
+}
+
+fn guard_balance(gs: &GameState) -> i64 {
+    token_balance_by_type(&gs.token_balances, &TokenType::HerbalismGuard)
+}
+
+#[test]
+fn guard_is_a_single_use_token() {
+    let (mut gs, mut rng) = start(35);
+    gs.resolve_player_herbalism_card(GUARD_CARD, &mut rng)
+        .expect("play guard card");
+    let guard = Token {
+        token_type: TokenType::HerbalismGuard,
+        lifecycle: TokenLifecycle::SingleUse,
+    };
+    assert_eq!(gs.token_balances.get(&guard), Some(&1));
+}
+
+#[test]
+fn guard_spares_half_of_removed_plants_once() {
+    let (mut gs, mut rng) = start(35);
+    gs.resolve_player_herbalism_card(GUARD_CARD, &mut rng)
+        .expect("play guard card");
+    assert!(guarded_plants(&gs).is_empty());
+
+    gs.resolve_player_herbalism_card(REMOVE_FRAGILE, &mut rng)
+        .expect("play guarded card");
+    assert_eq!(remaining_fragile(&gs), 2);
+    let guarded = guarded_plants(&gs);
+    assert_eq!(guarded.len(), 2);
+    assert!(guarded.iter().all(|&idx| idx < 4));
+    assert_eq!(guard_balance(&gs), 0);
+
+    gs.resolve_player_herbalism_card(REMOVE_FRAGILE, &mut rng)
+        .expect("play unguarded card");
+    assert_eq!(remaining_fragile(&gs), 0);
+    assert!(guarded_plants(&gs).is_empty());
+}
+
+#[test]
+fn guarded_plants_are_seed_deterministic() {
+    let play = |seed| {
+        let (mut gs, mut rng) = start(seed);
+        gs.resolve_player_herbalism_card(GUARD_CARD, &mut rng)
+            .expect("play guard card");
+        gs.resolve_player_herbalism_card(REMOVE_FRAGILE, &mut rng)
+            .expect("play guarded card");
+        guarded_plants(&gs)
+    };
+    assert_eq!(play(35), play(35));
+}
+
+#[test]
+fn unused_guard_expires_with_the_encounter() {
+    let (mut gs, mut rng) = start(35);
+    gs.resolve_player_herbalism_card(GUARD_CARD, &mut rng)
+        .expect("play guard card");
+    gs.conclude_herbalism_encounter().expect("conclude");
+    assert_eq!(guard_balance(&gs), 0);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{game_with, herbalism_card, in_hand, library_only, plant};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, EncounterKind, EncounterOutcome, EncounterState, HerbalismDef,
+    PlantCharacteristic, Token, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+use std::collections::HashMap;
+
+const REMOVE_FRAGILE: usize = 0;
+const REMOVE_ALL: usize = 1;
+const ENCOUNTER_CARD: usize = 2;
+
+/// Plants: Fragile (150), Thorny (50), Fragile (80); 400 Plant reward, 25% consolation.
+fn start() -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(
+        herbalism_card(vec![PlantCharacteristic::Fragile], vec![]),
+        in_hand(2),
+    );
+    lib.add_card(
+        herbalism_card(
+            vec![PlantCharacteristic::Fragile, PlantCharacteristic::Thorny],
+            vec![],
+        ),
+        in_hand(2),
+    );
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Herbalism {
+                herbalism_def: HerbalismDef {
+                    plant_hand: vec![
+                        plant(PlantCharacteristic::Fragile, 150),
+                        plant(PlantCharacteristic::Thorny, 50),
+                        plant(PlantCharacteristic::Fragile, 80),
+                    ],
+                    rewards: HashMap::from([(Token::persistent(TokenType::Plant), 400)]),
+                    consolation_percent: 25,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, 34);
+    gs.start_herbalism_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start herbalism");
+    (gs, rng)
+}
+
+fn plant_balance(gs: &GameState) -> i64 {
+    token_balance_by_type(&gs.token_balances, &TokenType::Plant)
+}
+
+#[test]
+fn projected_reward_uses_average_potency_of_remaining_plants() {
+    let (gs, _) = start();
+    match &gs.current_encounter {
+        Some(EncounterState::Herbalism(h)) => {
+            // (150 + 50 + 80) / 3 = 93%
+            assert_eq!(
+                token_balance_by_type(&h.projected_reward, &TokenType::Plant),
+                372
+            );
+        }
+        _ => panic!("expected active herbalism encounter"),
+    }
+}
+
+#[test]
+fn surviving_plant_potency_scales_plant_reward() {
+    let (mut gs, mut rng) = start();
+    let before = plant_balance(&gs);
+    gs.resolve_player_herbalism_card(REMOVE_FRAGILE, &mut rng)
+        .expect("play herbalism card");
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerWon]);
+    assert_eq!(plant_balance(&gs) - before, 200);
+}
+
+#[test]
+fn over_harvest_pays_consolation_yield() {
+    let (mut gs, mut rng) = start();
+    let before = plant_balance(&gs);
+    gs.resolve_player_herbalism_card(REMOVE_ALL, &mut rng)
+        .expect("play herbalism card");
+    assert_eq!(gs.encounter_results, vec![EncounterOutcome::PlayerLost]);
+    assert_eq!(plant_balance(&gs) - before, 100);
+}
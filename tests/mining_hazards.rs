@@ -0,0 +1,155 @@
+mod common;
+
+use common::{amount, game_with, in_hand, library_only};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardKind, DeckCounts, EncounterKind, EncounterState, MiningCardEffect,
+    MiningDef, OreCard, OreHazard, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+
+const PICK_CARD: usize = 0;
+const ENCOUNTER_CARD: usize = 1;
+
+fn ore_card(hazard: Option<OreHazard>, hand: u32, deck: u32) -> OreCard {
+    OreCard {
+        damages: vec![],
+        counts: DeckCounts {
+            deck,
+            hand,
+            discard: 0,
+        },
+        hazard,
+    }
+}
+
+fn start(ore_deck: Vec<OreCard>) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::Mining {
+            mining_effect: MiningCardEffect {
+                costs: vec![],
+                gains: vec![amount(TokenType::MiningPower, 100)],
+                drains: vec![],
+                ventilates: false,
+            },
+        },
+        in_hand(10),
+    );
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Mining {
+                mining_def: MiningDef {
+                    initial_light_level: 100,
+                    ore_deck: ore_deck.clone(),
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, 33);
+    assert!(gs.library.validate_card_effects().is_ok());
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    // Undo the opening shuffle so the ore plays in a known order
+    if let Some(EncounterState::Mining(m)) = &mut gs.current_encounter {
+        m.ore_deck = ore_deck;
+    }
+    (gs, rng)
+}
+
+fn gem_vein_turns_left(gs: &GameState) -> Option<u32> {
+    match &gs.current_encounter {
+        Some(EncounterState::Mining(m)) => m.gem_vein.as_ref().map(|v| v.turns_left),
+        _ => panic!("expected active mining encounter"),
+    }
+}
+
+#[test]
+fn gem_vein_closes_after_its_turns() {
+    let gem_vein = OreHazard::GemVein {
+        token_type: TokenType::Insight,
+        amount: 25,
+        turns: 2,
+    };
+    let (mut gs, mut rng) = start(vec![ore_card(Some(gem_vein), 1, 0), ore_card(None, 0, 10)]);
+
+    gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+        .expect("play pick");
+    assert_eq!(gem_vein_turns_left(&gs), Some(2));
+    for turns_left in [Some(1), None] {
+        gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+            .expect("play pick");
+        assert_eq!(gem_vein_turns_left(&gs), turns_left);
+    }
+
+    gs.conclude_mining_encounter().expect("conclude");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Insight),
+        0
+    );
+}
+
+/// Insight gained by concluding on the given turn after a 2-turn gem vein is exposed.
+fn insight_when_concluding_on_turn(turn: u32) -> i64 {
+    let gem_vein = OreHazard::GemVein {
+        token_type: TokenType::Insight,
+        amount: 25,
+        turns: 2,
+    };
+    let (mut gs, mut rng) = start(vec![ore_card(Some(gem_vein), 1, 0), ore_card(None, 0, 10)]);
+    for _ in 0..turn {
+        gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+            .expect("play pick");
+    }
+    gs.conclude_mining_encounter().expect("conclude");
+    token_balance_by_type(&gs.token_balances, &TokenType::Insight)
+}
+
+#[test]
+fn gem_vein_pays_through_its_last_turn_only() {
+    assert_eq!(insight_when_concluding_on_turn(1), 25, "exposed this turn");
+    assert_eq!(insight_when_concluding_on_turn(2), 25, "turn N");
+    assert_eq!(insight_when_concluding_on_turn(3), 0, "turn N+1");
+}
+
+#[test]
+fn gas_pockets_do_not_stack() {
+    let gas = |stamina_per_turn| Some(OreHazard::Gas { stamina_per_turn });
+    let (mut gs, mut rng) = start(vec![ore_card(gas(30), 1, 0), ore_card(gas(20), 0, 10)]);
+    let stamina = token_balance_by_type(&gs.token_balances, &TokenType::Stamina);
+
+    gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+        .expect("play pick");
+    gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+        .expect("play pick");
+    gs.resolve_player_mining_card(PICK_CARD, &mut rng)
+        .expect("play pick");
+    assert_eq!(
+        token_balance_by_type(&gs.token_balances, &TokenType::Stamina),
+        stamina - 60
+    );
+}
+
+#[test]
+fn validate_rejects_invalid_hazards() {
+    let (gs, _) = start(vec![]);
+    let mut lib = gs.library.clone();
+    if let CardKind::Encounter {
+        encounter_kind: EncounterKind::Mining { mining_def },
+    } = &mut lib.cards[ENCOUNTER_CARD].kind
+    {
+        mining_def.ore_deck = vec![
+            ore_card(Some(OreHazard::CaveIn { yield_percent: 150 }), 1, 0),
+            ore_card(
+                Some(OreHazard::Gas {
+                    stamina_per_turn: 0,
+                }),
+                1,
+                0,
+            ),
+        ];
+    }
+    let errors = lib.validate_card_effects().expect_err("invalid hazards");
+    assert_eq!(errors.len(), 2);
+}
@@ -18,10 +18,11 @@ fn file_writer_writes_and_flushes() {
 
     // Send a few entries
     for i in 0..3 {
-        let entry = ActionEntry {
-            seq: i,
-            payload: ActionPayload::SetSeed { seed: i + 1 },
-        };
+        let entry = ActionEntry::new(
+            i,
+            ActionEntry::GENESIS_HASH,
+            ActionPayload::SetSeed { seed: i + 1 },
+        );
         writer.send(entry);
     }
 
@@ -65,10 +66,11 @@ fn file_writer_close_is_idempotent() {
     writer.close(); // idempotent
 
     // Send after close should not panic
-    let entry = ActionEntry {
-        seq: 0,
-        payload: ActionPayload::SetSeed { seed: 1 },
-    };
+    let entry = ActionEntry::new(
+        0,
+        ActionEntry::GENESIS_HASH,
+        ActionPayload::SetSeed { seed: 1 },
+    );
     writer.send(entry);
 
     let _ = std::fs::remove_dir_all(&dir);
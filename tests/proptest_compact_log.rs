@@ -0,0 +1,212 @@
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::compact_log;
+use my_little_cardgame::library::state_digest::StateDigest;
+use my_little_cardgame::library::types::{
+    ActionEntry, ActionPayload, CombatPhase, Discipline, EncounterOutcome, FishingBait, Token,
+    TokenLifecycle, TokenType,
+};
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use proptest::prelude::*;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+
+fn discipline() -> impl Strategy<Value = Discipline> {
+    prop::sample::select(Discipline::ALL.to_vec())
+}
+
+fn payload() -> impl Strategy<Value = ActionPayload> {
+    prop_oneof![
+        any::<u64>().prop_map(|seed| ActionPayload::SetSeed { seed }),
+        "[0-9a-z]{0,12}".prop_map(|encounter_id| ActionPayload::DrawEncounter { encounter_id }),
+        any::<usize>().prop_map(|card_id| ActionPayload::PlayCard { card_id }),
+        prop::collection::vec(any::<usize>(), 0..6)
+            .prop_map(|card_ids| ActionPayload::ApplyScouting { card_ids }),
+        Just(ActionPayload::AbortEncounter),
+        Just(ActionPayload::RetreatEncounter),
+        Just(ActionPayload::ConcludeEncounter),
+        (any::<usize>(), any::<usize>())
+            .prop_map(|(from_id, to_id)| ActionPayload::CraftSwap { from_id, to_id }),
+        any::<usize>().prop_map(|target_card_id| ActionPayload::CraftCard { target_card_id }),
+        discipline().prop_map(|discipline| ActionPayload::CraftDurability { discipline }),
+        prop::sample::select(vec![FishingBait::Chum, FishingBait::Glowworm])
+            .prop_map(|bait| ActionPayload::FishingBait { bait }),
+        discipline().prop_map(|discipline| ActionPayload::Repair { discipline }),
+        any::<usize>().prop_map(|card_id| ActionPayload::EquipTool { card_id }),
+        discipline().prop_map(|discipline| ActionPayload::UnequipTool { discipline }),
+    ]
+}
+
+fn lifecycle() -> impl Strategy<Value = TokenLifecycle> {
+    let phase = prop::sample::select(vec![
+        CombatPhase::Defending,
+        CombatPhase::Attacking,
+        CombatPhase::Resourcing,
+    ]);
+    prop_oneof![
+        Just(TokenLifecycle::Permanent),
+        Just(TokenLifecycle::PersistentCounter),
+        any::<u64>().prop_map(|duration| TokenLifecycle::FixedDuration { duration }),
+        (any::<u64>(), prop::collection::vec(phase, 0..3))
+            .prop_map(|(duration, phases)| TokenLifecycle::FixedTypeDuration { duration, phases }),
+        Just(TokenLifecycle::UntilNextAction),
+        Just(TokenLifecycle::SingleUse),
+        Just(TokenLifecycle::Conditional),
+    ]
+}
+
+fn digest() -> impl Strategy<Value = StateDigest> {
+    let token = (prop::sample::select(TokenType::all()), lifecycle()).prop_map(
+        |(token_type, lifecycle)| Token {
+            token_type,
+            lifecycle,
+        },
+    );
+    (
+        prop::collection::hash_map(token, any::<i64>(), 0..5),
+        prop::option::of(prop::sample::select(vec![
+            EncounterOutcome::Undecided,
+            EncounterOutcome::PlayerWon,
+            EncounterOutcome::PlayerLost,
+        ])),
+        "[0-9a-f]{16}",
+        "[0-9a-f]{16}",
+    )
+        .prop_map(
+            |(token_balances, last_encounter_result, library_hash, rng_hash)| StateDigest {
+                token_balances,
+                last_encounter_result,
+                library_hash,
+                rng_hash,
+            },
+        )
+}
+
+fn log_of(payloads: Vec<ActionPayload>) -> ActionLog {
+    log_with_digests(payloads.into_iter().map(|p| (p, None)).collect())
+}
+
+fn log_with_digests(actions: Vec<(ActionPayload, Option<StateDigest>)>) -> ActionLog {
+    let log = ActionLog::new();
+    for (payload, digest) in actions {
+        log.append_with_digest("Test", payload, digest);
+    }
+    log
+}
+
+proptest! {
+    #[test]
+    fn compact_export_round_trips(
+        actions in prop::collection::vec((payload(), prop::option::of(digest())), 0..40),
+        compress in any::<bool>(),
+    ) {
+        let log = log_with_digests(actions);
+        let bytes = log.export_compact(compress);
+        prop_assert!(compact_log::is_compact(&bytes));
+        let imported = ActionLog::import_compact(&bytes).expect("import");
+        prop_assert_eq!(imported.entries(), log.entries());
+        prop_assert_eq!(imported.head_hash(), log.head_hash());
+        prop_assert_eq!(imported.verify(), Ok(()));
+    }
+
+    #[test]
+    fn corrupted_compact_logs_never_decode_to_a_different_log(
+        actions in prop::collection::vec((payload(), prop::option::of(digest())), 1..20),
+        position in any::<prop::sample::Index>(),
+        flip in 1u8..=255,
+    ) {
+        let log = log_with_digests(actions);
+        let mut bytes = log.export_compact(false);
+        let i = position.index(bytes.len());
+        bytes[i] ^= flip;
+        if let Ok(entries) = compact_log::decode(&bytes) {
+            prop_assert_eq!(entries, log.entries());
+        }
+    }
+}
+
+#[test]
+fn rewind_markers_survive_the_compact_format() {
+    let log = log_of(vec![
+        ActionPayload::SetSeed { seed: 1 },
+        ActionPayload::PlayCard { card_id: 2 },
+        ActionPayload::PlayCard { card_id: 3 },
+    ]);
+    let mut entries = log.entries();
+    entries.push(ActionEntry::new(
+        1,
+        &entries[0].hash,
+        ActionPayload::Rewind { to_seq: 1 },
+    ));
+    entries.push(ActionEntry::new(
+        2,
+        &entries[0].hash,
+        ActionPayload::AbortEncounter,
+    ));
+
+    let bytes = compact_log::encode(&entries, true);
+    assert_eq!(compact_log::decode(&bytes).expect("decode"), entries);
+    let imported = ActionLog::import_compact(&bytes).expect("import");
+    let payloads: Vec<ActionPayload> = imported.entries().into_iter().map(|e| e.payload).collect();
+    assert_eq!(
+        payloads,
+        vec![
+            ActionPayload::SetSeed { seed: 1 },
+            ActionPayload::AbortEncounter
+        ]
+    );
+}
+
+#[test]
+fn compact_export_endpoint_loads_back_from_a_file() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    for seed in [5, 6, 7] {
+        let status = client
+            .post("/action")
+            .header(ContentType::JSON)
+            .body(format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed))
+            .dispatch()
+            .status();
+        assert_eq!(status, Status::Created);
+    }
+    let json = client
+        .get("/actions/log")
+        .dispatch()
+        .into_string()
+        .unwrap_or_default();
+    let logged: serde_json::Value = serde_json::from_str(&json).unwrap_or_default();
+
+    for query in ["format=compact", "format=compact&compressed=true"] {
+        let resp = client.get(format!("/actions/log?{}", query)).dispatch();
+        assert_eq!(resp.status(), Status::Ok);
+        assert_eq!(resp.content_type(), Some(ContentType::Binary));
+        let bytes = resp.into_bytes().unwrap_or_default();
+        assert!(bytes.len() < json.len() / 4, "{} is compact", query);
+
+        let path = std::env::temp_dir().join(format!(
+            "mlcg_compact_{}_{}.bin",
+            bytes.len(),
+            std::process::id()
+        ));
+        std::fs::write(&path, &bytes).unwrap();
+        let loaded = ActionLog::load_from_file(path.to_str().unwrap()).expect("load");
+        let _ = std::fs::remove_file(&path);
+
+        let entries = loaded.entries();
+        assert_eq!(entries.len(), 3);
+        for (entry, logged) in entries.iter().zip(logged["entries"].as_array().unwrap()) {
+            assert_eq!(entry.hash, logged["hash"].as_str().unwrap());
+            assert_eq!(
+                serde_json::to_value(&entry.payload).unwrap(),
+                logged["payload"]
+            );
+            assert!(entry.digest.is_some(), "{} keeps the digests", query);
+            assert_eq!(
+                serde_json::to_value(&entry.digest).unwrap(),
+                logged["digest"]
+            );
+        }
+        assert!(GameState::replay_checked(&loaded).is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::types::{ActionEntry, ActionPayload};
+use my_little_cardgame::rocket_initialize;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+
+fn post_action(client: &Client, json: &str) -> (Status, serde_json::Value) {
+    let resp = client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch();
+    let status = resp.status();
+    let body = serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
+    (status, body)
+}
+
+fn get_json(client: &Client, uri: &str) -> serde_json::Value {
+    serde_json::from_str(&client.get(uri).dispatch().into_string().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+fn rewind(client: &Client, to_seq: u64) -> (Status, serde_json::Value) {
+    let resp = client
+        .post(format!("/game/rewind?to_seq={}", to_seq))
+        .dispatch();
+    let status = resp.status();
+    let body = serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
+    (status, body)
+}
+
+/// Token balances sorted, so HashMap order doesn't matter.
+fn tokens(client: &Client) -> Vec<String> {
+    let mut balances: Vec<String> = get_json(client, "/player/tokens")
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    balances.sort();
+    balances
+}
+
+fn log_len(client: &Client) -> usize {
+    get_json(client, "/actions/log")["entries"]
+        .as_array()
+        .map_or(0, |e| e.len())
+}
+
+fn first_mining_card(client: &Client) -> u64 {
+    get_json(client, "/library/cards?location=Hand&card_kind=Mining")[0]["id"]
+        .as_u64()
+        .expect("a mining card in hand")
+}
+
+fn start_mining(client: &Client) {
+    let (status, _) = post_action(client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    let mining = get_json(client, "/encounters/hand")
+        .as_array()
+        .and_then(|s| s.iter().find(|s| s["discipline"] == "Mining").cloned())
+        .and_then(|s| s["card_id"].as_u64())
+        .expect("a mining encounter in hand");
+    let (status, _) = post_action(
+        client,
+        &format!(
+            r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+            mining
+        ),
+    );
+    assert_eq!(status, Status::Created);
+}
+
+#[test]
+fn rewind_restores_state_and_replays_the_same_line() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    start_mining(&client);
+    let branch_point = log_len(&client) as u64;
+    let encounter_before = get_json(&client, "/encounter");
+    let tokens_before = tokens(&client);
+
+    let card = first_mining_card(&client);
+    let play = format!(
+        r#"{{"action_type":"EncounterPlayCard","card_id":{}}}"#,
+        card
+    );
+    let (status, entry) = post_action(&client, &play);
+    assert_eq!(status, Status::Created);
+    assert_eq!(entry["seq"], branch_point + 1);
+    let encounter_after_play = get_json(&client, "/encounter");
+    post_action(&client, r#"{"action_type":"EncounterConcludeEncounter"}"#);
+
+    let (status, body) = rewind(&client, branch_point);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["to_seq"], branch_point);
+    assert_eq!(body["discarded_actions"], 2);
+    assert_eq!(log_len(&client), branch_point as usize);
+    assert_eq!(get_json(&client, "/encounter"), encounter_before);
+    assert_eq!(tokens(&client), tokens_before);
+
+    // The RNG was rewound too: the same play leads to the same encounter state
+    let (status, entry) = post_action(&client, &play);
+    assert_eq!(status, Status::Created);
+    assert_eq!(
+        entry["seq"],
+        branch_point + 1,
+        "seqs continue from the rewind point"
+    );
+    assert_eq!(get_json(&client, "/encounter"), encounter_after_play);
+}
+
+#[test]
+fn rewind_rejects_seqs_beyond_the_log() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    start_mining(&client);
+    let last = log_len(&client) as u64;
+    let (status, _) = rewind(&client, last + 1);
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(log_len(&client), last as usize);
+
+    let (status, body) = rewind(&client, last);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["discarded_actions"], 0);
+}
+
+#[test]
+fn loading_a_log_file_applies_rewind_markers() {
+    let first = ActionEntry::new(
+        1,
+        ActionEntry::GENESIS_HASH,
+        ActionPayload::SetSeed { seed: 1 },
+    );
+    let second = ActionEntry::new(2, &first.hash, ActionPayload::AbortEncounter);
+    let third = ActionEntry::new(3, &second.hash, ActionPayload::ConcludeEncounter);
+    let marker = ActionEntry::new(1, &first.hash, ActionPayload::Rewind { to_seq: 1 });
+    let branch = ActionEntry::new(2, &first.hash, ActionPayload::RetreatEncounter);
+    let lines = [first, second, third, marker, branch];
+    let path = std::env::temp_dir().join(format!(
+        "mlcg_rewind_marker_test_{}.jsonl",
+        std::process::id()
+    ));
+    let contents: Vec<String> = lines
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect();
+    std::fs::write(&path, contents.join("\n")).unwrap();
+
+    let log = ActionLog::load_from_file(path.to_str().unwrap()).expect("load");
+    let entries = log.entries();
+    assert_eq!(entries.len(), 2);
+    assert!(matches!(
+        entries[0].payload,
+        ActionPayload::SetSeed { seed: 1 }
+    ));
+    assert!(matches!(
+        entries[1].payload,
+        ActionPayload::RetreatEncounter
+    ));
+    assert_eq!(log.seq.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn rewind_keeps_the_sinks_while_the_log_is_shared() {
+    use my_little_cardgame::action::persistence::MemorySink;
+    use my_little_cardgame::library::GameState;
+
+    let memory = std::sync::Arc::new(MemorySink::default());
+    let mut log = ActionLog::new();
+    log.add_sink(memory.clone());
+    log.append("NewGame", ActionPayload::SetSeed { seed: 3 });
+    log.append("NewGame", ActionPayload::SetSeed { seed: 4 });
+    let mut gs = GameState::replay_from_log(&log);
+    gs.action_log = std::sync::Arc::new(log);
+    let shared = gs.action_log.clone();
+
+    gs.rewind(1).expect("rewind");
+    gs.action_log
+        .append("NewGame", ActionPayload::SetSeed { seed: 5 });
+
+    let written: Vec<ActionPayload> = memory.entries().into_iter().map(|e| e.payload).collect();
+    assert_eq!(
+        written,
+        vec![
+            ActionPayload::SetSeed { seed: 3 },
+            ActionPayload::SetSeed { seed: 4 },
+            ActionPayload::Rewind { to_seq: 1 },
+            ActionPayload::SetSeed { seed: 5 },
+        ]
+    );
+    assert_eq!(shared.entries().len(), 2, "holders of the old log keep it");
+}
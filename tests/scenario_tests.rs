@@ -61,6 +61,17 @@ fn player_token(client: &Client, token_type_name: &str) -> i64 {
         .unwrap_or(0)
 }
 
+fn player_token_max(client: &Client, token_type_name: &str) -> Option<i64> {
+    let tokens = get_json(client, "/player/tokens");
+    tokens.as_array()?.iter().find_map(|entry| {
+        if entry["token"]["token_type"].as_str()? == token_type_name {
+            entry.get("max")?.as_i64()
+        } else {
+            None
+        }
+    })
+}
+
 fn combat_state(client: &Client) -> serde_json::Value {
     get_json(client, "/encounter")
 }
@@ -80,7 +91,9 @@ fn combat_result(client: &Client) -> Option<String> {
     if resp.status() == Status::Ok {
         let body: Vec<serde_json::Value> =
             serde_json::from_str(&resp.into_string().unwrap_or_default()).unwrap_or_default();
-        body.last().and_then(|v| v.as_str()).map(String::from)
+        body.last()
+            .and_then(|v| v["outcome"].as_str())
+            .map(String::from)
     } else {
         None
     }
@@ -803,6 +816,143 @@ fn scenario_mining_encounter_full_loop() {
     assert_eq!(status, Status::Created, "ApplyScouting should succeed");
 }
 
+/// Add a card through the test library endpoint and return its id.
+fn add_library_card(client: &Client, card: serde_json::Value) -> usize {
+    let resp = client
+        .post("/tests/library/cards")
+        .header(json_header())
+        .body(card.to_string())
+        .dispatch();
+    assert_eq!(resp.status(), Status::Created);
+    let location = resp.headers().get_one("Location").expect("location header");
+    location
+        .rsplit('/')
+        .next()
+        .and_then(|id| id.parse().ok())
+        .expect("card id in location")
+}
+
+fn add_mining_card(client: &Client, power: i64, ventilates: bool, hand: u32) -> usize {
+    add_library_card(
+        client,
+        serde_json::json!({
+            "kind": {"card_kind": "Mining", "mining_effect": {
+                "gains": [{"token_type": "MiningPower", "amount": power}],
+                "ventilates": ventilates
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": hand, "discard": 0}
+        }),
+    )
+}
+
+/// Start a new game and enter a mining encounter whose ore only ever plays `hazard`.
+/// Returns a costless MiningPower 100 card with three copies in hand.
+fn start_hazard_mining(client: &Client, hazard: serde_json::Value) -> usize {
+    post_action(client, r#"{"action_type":"NewGame","seed":33}"#);
+    let pick_card = add_mining_card(client, 100, false, 3);
+    let encounter = add_library_card(
+        client,
+        serde_json::json!({
+            "kind": {"card_kind": "Encounter", "encounter_kind": {
+                "encounter_type": "Mining",
+                "mining_def": {
+                    "initial_light_level": 100,
+                    "ore_deck": [{
+                        "damages": [],
+                        "counts": {"deck": 0, "hand": 1, "discard": 0},
+                        "hazard": hazard
+                    }]
+                }
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    );
+    let (status, _) = post_action(
+        client,
+        &format!(
+            r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+            encounter
+        ),
+    );
+    assert_eq!(status, Status::Created);
+    pick_card
+}
+
+fn play_card_id(client: &Client, card_id: usize) {
+    let (status, _) = post_action(
+        client,
+        &format!(
+            r#"{{"action_type":"EncounterPlayCard","card_id":{}}}"#,
+            card_id
+        ),
+    );
+    assert_eq!(status, Status::Created);
+}
+
+#[test]
+fn scenario_mining_cave_in_ends_with_partial_yield() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let pick = start_hazard_mining(
+        &client,
+        serde_json::json!({"hazard_type": "CaveIn", "yield_percent": 50}),
+    );
+    let ore_before = player_token(&client, "Ore");
+
+    // Yield 100 × light 100% = 100; the cave-in keeps half of it
+    play_card_id(&client, pick);
+
+    let resp = client.get("/encounter").dispatch();
+    assert_eq!(resp.status(), Status::NotFound);
+    assert_eq!(combat_result(&client), Some("PlayerLost".to_string()));
+    assert_eq!(player_token(&client, "Ore") - ore_before, 50);
+}
+
+#[test]
+fn scenario_mining_gas_drains_stamina_until_ventilated() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let pick = start_hazard_mining(
+        &client,
+        serde_json::json!({"hazard_type": "Gas", "stamina_per_turn": 40}),
+    );
+    let vent = add_mining_card(&client, 0, true, 1);
+    let stamina_start = player_token(&client, "Stamina");
+
+    // Gas is released by this turn's ore play and only drains from the next turn
+    play_card_id(&client, pick);
+    assert_eq!(combat_state(&client)["gas_per_turn"], 40);
+    assert_eq!(player_token(&client, "Stamina"), stamina_start);
+
+    play_card_id(&client, pick);
+    assert_eq!(player_token(&client, "Stamina"), stamina_start - 40);
+
+    // Ventilating clears the gas before it drains this turn
+    play_card_id(&client, vent);
+    assert_eq!(player_token(&client, "Stamina"), stamina_start - 40);
+}
+
+#[test]
+fn scenario_mining_gem_vein_bonus_on_quick_conclude() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let pick = start_hazard_mining(
+        &client,
+        serde_json::json!({
+            "hazard_type": "GemVein",
+            "token_type": "Insight",
+            "amount": 25,
+            "turns": 2
+        }),
+    );
+    let insight_before = player_token(&client, "Insight");
+
+    play_card_id(&client, pick);
+    assert_eq!(combat_state(&client)["gem_vein"]["turns_left"], 2);
+
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterConcludeEncounter"}"#);
+    assert_eq!(status, Status::Created);
+    assert_eq!(combat_result(&client), Some("PlayerWon".to_string()));
+    assert_eq!(player_token(&client, "Insight") - insight_before, 25);
+}
+
 #[test]
 fn scenario_abort_mining_encounter() {
     let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
@@ -1123,6 +1273,47 @@ fn scenario_herbalism_encounter_full_loop() {
     }
 }
 
+/// Plant reward the encounter state should project from its remaining plants' potency.
+fn expected_herbalism_projection(encounter: &serde_json::Value) -> i64 {
+    let (copies, potency) = encounter["plant_hand"]
+        .as_array()
+        .expect("plant hand")
+        .iter()
+        .fold((0, 0), |(copies, potency), plant| {
+            let hand = plant["counts"]["hand"].as_i64().unwrap_or(0);
+            (
+                copies + hand,
+                potency + hand * plant["potency"].as_i64().unwrap_or(0),
+            )
+        });
+    500 * (potency / copies) / 100
+}
+
+#[test]
+fn scenario_herbalism_projected_reward_tracks_plant_potency() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    let herb_enc = herbalism_encounter_ids(&client);
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        herb_enc[0]
+    );
+    post_action(&client, &pick_json);
+
+    // Meadow Herb: potencies 80, 120, 150, 100 and 90 average to 108%
+    let encounter = combat_state(&client);
+    assert_eq!(encounter["projected_reward"]["Plant"], 540);
+    assert_eq!(encounter["consolation_percent"], 20);
+
+    if play_one_herbalism_card(&client) {
+        let encounter = combat_state(&client);
+        assert_eq!(
+            encounter["projected_reward"]["Plant"],
+            expected_herbalism_projection(&encounter)
+        );
+    }
+}
+
 #[test]
 fn scenario_abort_herbalism_encounter() {
     let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
@@ -1357,6 +1548,33 @@ fn scenario_woodcutting_encounter_full_loop() {
     );
 }
 
+#[test]
+fn scenario_woodcutting_pattern_preview() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+
+    let resp = client.get("/encounter/woodcutting/patterns").dispatch();
+    assert_eq!(resp.status(), Status::NotFound);
+
+    let wc_enc = woodcutting_encounter_ids(&client);
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        wc_enc[0]
+    );
+    post_action(&client, &pick_json);
+
+    let preview = get_json(&client, "/encounter/woodcutting/patterns");
+    assert_eq!(preview["remaining_plays"], 8);
+    assert_eq!(preview["best_so_far"]["pattern"], "HighCard");
+    let reachable = preview["reachable"].as_array().expect("reachable list");
+    assert_eq!(reachable[0]["pattern"], "EightOfAKind");
+    assert_eq!(reachable[0]["multiplier"], 55.0);
+
+    play_one_woodcutting_card(&client);
+    let preview = get_json(&client, "/encounter/woodcutting/patterns");
+    assert_eq!(preview["remaining_plays"], 7);
+}
+
 #[test]
 fn scenario_abort_woodcutting_encounter() {
     let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
@@ -1640,6 +1858,148 @@ fn scenario_abort_fishing_encounter() {
     );
 }
 
+#[test]
+fn scenario_fishing_spot_picks_a_species() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+
+    let fc_enc = fishing_encounter_ids(&client);
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        fc_enc[0]
+    );
+    let (status, _) = post_action(&client, &pick_json);
+    assert_eq!(status, Status::Created);
+
+    let encounter = combat_state(&client);
+    let species = encounter["species"]["name"].as_str().expect("species name");
+    assert!(
+        ["Pike", "Trout", "Carp"].contains(&species),
+        "River Spot should pick one of its species, got {}",
+        species
+    );
+    // Only the telegraphed Carp reveals its next fish card
+    assert_eq!(encounter["revealed_fish"].is_u64(), species == "Carp");
+    assert_eq!(encounter["turns_lost"].as_u64(), Some(0));
+}
+
+#[test]
+fn scenario_fishing_bait_needs_plant_and_round_one() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+
+    let fc_enc = fishing_encounter_ids(&client);
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        fc_enc[0]
+    );
+    let (status, _) = post_action(&client, &pick_json);
+    assert_eq!(status, Status::Created);
+
+    // A fresh game has no Plant to spend on bait
+    assert_eq!(player_token(&client, "Plant"), 0);
+    let bait_json = r#"{"action_type":"EncounterFishingBait","bait":"Chum"}"#;
+    let (status, _) = post_action(&client, bait_json);
+    assert_eq!(status, Status::BadRequest);
+    assert!(combat_state(&client)["bait"].is_null());
+
+    // After the first cast bait is no longer allowed
+    assert!(play_one_fishing_card(&client));
+    let (status, body) = post_action(&client, bait_json);
+    assert_eq!(status, Status::BadRequest);
+    assert!(body.to_string().contains("first cast"), "{}", body);
+
+    let log = get_json(&client, "/actions/log");
+    let baits = log["entries"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .filter(|e| e["payload"]["type"] == "FishingBait")
+        .count();
+    assert_eq!(baits, 0, "Rejected bait must not be logged");
+}
+
+#[test]
+fn scenario_durability_pools_report_max_and_repair_needs_rest_or_crafting() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+
+    for pool in [
+        "MiningDurability",
+        "HerbalismDurability",
+        "WoodcuttingDurability",
+        "FishingDurability",
+    ] {
+        assert_eq!(player_token_max(&client, pool), Some(10000), "{}", pool);
+        assert_eq!(player_token(&client, pool), 10000, "{}", pool);
+    }
+    assert_eq!(player_token_max(&client, "Ore"), None);
+
+    let (status, _) = post_action(
+        &client,
+        r#"{"action_type":"EncounterRepair","discipline":"Mining"}"#,
+    );
+    assert_eq!(status, Status::BadRequest);
+
+    // Disciplines are a closed enum now
+    let (status, _) = post_action(
+        &client,
+        r#"{"action_type":"EncounterRepair","discipline":"Cooking"}"#,
+    );
+    assert_eq!(status, Status::UnprocessableEntity);
+}
+
+#[test]
+fn scenario_equip_and_unequip_pickaxe_between_encounters() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+
+    let tools = get_json(&client, "/library/cards?location=Library&card_kind=Tool");
+    let pickaxe_id = tools[0]["id"].as_u64().expect("a starting tool");
+    assert_eq!(tools[0]["kind"]["discipline"], "Mining");
+
+    let (status, _) = post_action(
+        &client,
+        &format!(r#"{{"action_type":"EquipTool","card_id":{}}}"#, pickaxe_id),
+    );
+    assert_eq!(status, Status::Created);
+    let equipped = get_json(&client, "/player/tools");
+    assert_eq!(equipped[0]["card_id"].as_u64(), Some(pickaxe_id));
+    assert_eq!(equipped[0]["discipline"], "Mining");
+
+    // Equipment cannot change mid-encounter
+    pick_encounter(&client, mining_encounter_ids(&client)[0]);
+    let (status, _) = post_action(
+        &client,
+        r#"{"action_type":"UnequipTool","discipline":"Mining"}"#,
+    );
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterAbort"}"#);
+    assert_eq!(status, Status::Created);
+
+    let (status, _) = post_action(
+        &client,
+        r#"{"action_type":"UnequipTool","discipline":"Mining"}"#,
+    );
+    assert_eq!(status, Status::Created);
+    assert_eq!(get_json(&client, "/player/tools"), serde_json::json!([]));
+
+    let log = get_json(&client, "/actions/log");
+    let types: Vec<&str> = log["entries"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .filter_map(|e| e["payload"]["type"].as_str())
+        .collect();
+    assert!(types.contains(&"EquipTool") && types.contains(&"UnequipTool"));
+}
+
 // ---- Step 9.2: Cost system tests ----
 
 #[test]
@@ -2033,6 +2393,41 @@ fn scenario_combat_victory_grants_milestone_insight() {
     }
 }
 
+#[test]
+fn scenario_combat_victory_reports_reward_breakdown() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    let combat_enc = combat_encounter_ids(&client);
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        combat_enc[0]
+    );
+    post_action(&client, &pick_json);
+
+    for _ in 0..80 {
+        if !play_one_round(&client) {
+            break;
+        }
+    }
+
+    let results = get_json(&client, "/encounter/results");
+    let last = results
+        .as_array()
+        .and_then(|r| r.last())
+        .expect("combat result recorded");
+    if last["outcome"] == "PlayerWon" {
+        let reward = &last["reward"];
+        assert_eq!(reward["rewards"]["Renown"], 5);
+        assert!(
+            !reward["loot"].as_object().expect("loot map").is_empty(),
+            "Gnome loot table should roll loot"
+        );
+        assert!(player_token(&client, "Renown") >= 5);
+    } else {
+        assert!(last["reward"].is_null());
+    }
+}
+
 #[test]
 fn scenario_fishing_range_modification_cards_exist() {
     let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
@@ -2767,3 +3162,448 @@ fn scenario_crafting_expansion_cards_exist() {
         "Should have 1 crafting encounter card"
     );
 }
+
+// ======================== Drain (life steal) tests ========================
+
+/// Find a hand card of `card_kind` whose `effect_key` payload has a non-empty `drains` list.
+fn drain_card_id(client: &Client, card_kind: &str, effect_key: &str) -> usize {
+    let cards = get_json(
+        client,
+        &format!("/library/cards?location=Hand&card_kind={}", card_kind),
+    );
+    cards
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .find_map(|c| {
+            let drains = c.get("kind")?.get(effect_key)?.get("drains")?.as_array()?;
+            if drains.is_empty() {
+                None
+            } else {
+                c.get("id")?.as_u64().map(|v| v as usize)
+            }
+        })
+        .expect("Should find a drain card in hand")
+}
+
+fn pick_encounter(client: &Client, encounter_id: usize) {
+    let pick_json = format!(
+        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+        encounter_id
+    );
+    let (status, _) = post_action(client, &pick_json);
+    assert_eq!(status, Status::Created, "PickEncounter should succeed");
+}
+
+fn play_card(client: &Client, card_id: usize) {
+    let json = format!(
+        r#"{{"action_type":"EncounterPlayCard","card_id":{}}}"#,
+        card_id
+    );
+    let (status, _) = post_action(client, &json);
+    assert_eq!(
+        status,
+        Status::Created,
+        "Playing card {} should succeed",
+        card_id
+    );
+}
+
+/// Read a token balance from the enemy's compact token map in combat state.
+fn enemy_token(client: &Client, token_type_name: &str) -> i64 {
+    combat_state(client)
+        .get("enemy_tokens")
+        .and_then(|v| v.get(token_type_name))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0)
+}
+
+/// Highest rolled damage among enemy attack cards still in hand.
+fn max_enemy_attack(client: &Client) -> i64 {
+    combat_state(client)
+        .get("enemy_attack_deck")
+        .and_then(|v| v.as_array())
+        .map(|deck| {
+            deck.iter()
+                .filter(|card| card["counts"]["hand"].as_u64().unwrap_or(0) > 0)
+                .flat_map(|card| card["effects"].as_array().cloned().unwrap_or_default())
+                .filter_map(|effect| effect["rolled_value"].as_i64())
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+}
+
+/// Find an Attack card in hand with an effect referencing a Drain PlayerCardEffect.
+fn vampiric_attack_id(client: &Client) -> Option<usize> {
+    let all_cards = get_json(client, "/library/cards");
+    let drain_effect_ids: Vec<u64> = all_cards
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .filter(|c| c["kind"]["kind"]["effect_type"].as_str() == Some("Drain"))
+        .filter_map(|c| c["id"].as_u64())
+        .collect();
+    let attacks = get_json(client, "/library/cards?location=Hand&card_kind=Attack");
+    attacks.as_array()?.iter().find_map(|c| {
+        let effects = c["kind"]["effects"].as_array()?;
+        let drains = effects.iter().any(|e| {
+            e["effect_id"]
+                .as_u64()
+                .is_some_and(|id| drain_effect_ids.contains(&id))
+        });
+        if drains {
+            c["id"].as_u64().map(|v| v as usize)
+        } else {
+            None
+        }
+    })
+}
+
+/// Start combat with a Vampiric Attack (500 damage, 30% Health life steal) whose damage
+/// effect costs `health_cost_percent`% of its value in Health, then play it on a turn where
+/// the player's Shield covers the enemy's counterattack.
+/// Returns the player's Health before the attack and the Health damage it dealt.
+fn play_vampiric_attack_under_shield(client: &Client, health_cost_percent: u32) -> (i64, i64) {
+    let (status, _) = post_action(client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(client, combat_encounter_ids(client)[0]);
+    let life_steal = add_library_card(
+        client,
+        serde_json::json!({
+            "kind": {"card_kind": "PlayerCardEffect", "kind": {
+                "effect_type": "Drain",
+                "source_token": "Health",
+                "target_token": "Health",
+                "percent": 30
+            }},
+            "counts": {"library": 1, "deck": 0, "hand": 0, "discard": 0}
+        }),
+    );
+    // Card 0 is the shared 400-600 damage effect
+    add_library_card(
+        client,
+        serde_json::json!({
+            "kind": {"card_kind": "Attack", "effects": [
+                {"effect_id": 0, "rolled_value": 500, "rolled_costs": [
+                    {"token_type": "Health", "rolled_percent": health_cost_percent}
+                ]},
+                {"effect_id": life_steal, "rolled_value": 30}
+            ]},
+            "counts": {"library": 0, "deck": 2, "hand": 1, "discard": 0}
+        }),
+    );
+
+    for _ in 0..20 {
+        // Defending: stack shield so the enemy's counterattack cannot reach Health
+        let defence = hand_card_ids_by_kind(client, "Defence");
+        let Some(&defence_id) = defence.first() else {
+            break;
+        };
+        play_card(client, defence_id);
+        if combat_state(client).get("outcome").and_then(|v| v.as_str()) != Some("Undecided") {
+            break;
+        }
+
+        // Attacking
+        let shield = player_token(client, "Shield");
+        let enemy_health_before = enemy_token(client, "Health");
+        match vampiric_attack_id(client) {
+            Some(vampiric_id)
+                if shield >= max_enemy_attack(client) && enemy_health_before > 600 =>
+            {
+                let health_before = player_health(client);
+                play_card(client, vampiric_id);
+                let dealt = enemy_health_before - enemy_token(client, "Health");
+                assert!(dealt > 0, "Vampiric Attack should deal Health damage");
+                return (health_before, dealt);
+            }
+            _ => {
+                let attacks = hand_card_ids_by_kind(client, "Attack");
+                let Some(&attack_id) = attacks.first() else {
+                    break;
+                };
+                play_card(client, attack_id);
+            }
+        }
+
+        // Resourcing
+        let resources = hand_card_ids_by_kind(client, "Resource");
+        let Some(&resource_id) = resources.first() else {
+            break;
+        };
+        play_card(client, resource_id);
+        if combat_state(client).get("outcome").and_then(|v| v.as_str()) != Some("Undecided") {
+            break;
+        }
+    }
+    panic!("Vampiric Attack should be playable under full shield");
+}
+
+/// Scenario: a Vampiric Attack heals the player for 30% of the Health damage it dealt.
+/// Its 200 Health cost keeps the heal below MaxHealth.
+#[test]
+fn scenario_combat_drain_heals_from_damage_dealt() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (health_before, dealt) = play_vampiric_attack_under_shield(&client, 40);
+    assert_eq!(
+        player_health(&client),
+        health_before - 200 + dealt * 30 / 100,
+        "Player should heal 30% of the {} damage dealt",
+        dealt
+    );
+}
+
+/// Scenario: life steal at full Health leaves the player at MaxHealth.
+#[test]
+fn scenario_combat_drain_stops_at_max_health() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (health_before, _) = play_vampiric_attack_under_shield(&client, 0);
+    let max_health = player_token(&client, "MaxHealth");
+    assert_eq!(health_before, max_health, "attack is played at full Health");
+    assert_eq!(player_health(&client), max_health);
+}
+
+/// Scenario: a leeching pick converts 20% of the yield it mines into Stamina.
+#[test]
+fn scenario_mining_drain_converts_yield_to_stamina() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(&client, mining_encounter_ids(&client)[0]);
+    add_library_card(
+        &client,
+        serde_json::json!({
+            "kind": {"card_kind": "Mining", "mining_effect": {
+                "gains": [{"token_type": "MiningPower", "amount": 250}],
+                "drains": [{"source_token": "MiningYield", "target_token": "Stamina", "percent": 20}]
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    );
+
+    let card_id = drain_card_id(&client, "Mining", "mining_effect");
+    let light = encounter_token(&client, "MiningLightLevel");
+    let yield_before = encounter_token(&client, "MiningYield");
+    let stamina_before = player_token(&client, "Stamina");
+    play_card(&client, card_id);
+
+    let yield_gain = 250 * light / 100;
+    assert_eq!(
+        encounter_token(&client, "MiningYield"),
+        yield_before + yield_gain
+    );
+    assert_eq!(
+        player_token(&client, "Stamina"),
+        stamina_before + yield_gain * 20 / 100
+    );
+}
+
+/// Scenario: a sap gathering card drains half of the Plant it gathers into durability.
+#[test]
+fn scenario_herbalism_drain_converts_plant_to_durability() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(&client, herbalism_encounter_ids(&client)[0]);
+    add_library_card(
+        &client,
+        serde_json::json!({
+            "kind": {"card_kind": "Herbalism", "herbalism_effect": {
+                "costs": [{"token_type": "HerbalismDurability", "amount": 50}],
+                "match_mode": {"Or": {"types": []}},
+                "gains": [{"token_type": "Plant", "amount": 20}],
+                "drains": [{"source_token": "Plant", "target_token": "HerbalismDurability", "percent": 50}]
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    );
+
+    let card_id = drain_card_id(&client, "Herbalism", "herbalism_effect");
+    let plant_before = player_token(&client, "Plant");
+    let durability_before = player_token(&client, "HerbalismDurability");
+    play_card(&client, card_id);
+
+    assert_eq!(player_token(&client, "Plant"), plant_before + 20);
+    assert_eq!(
+        player_token(&client, "HerbalismDurability"),
+        durability_before - 50 + 10
+    );
+}
+
+/// Scenario: a bark peeling card drains half of the Lumber it earns into durability, both
+/// when played and from the Lumber reward paid at conclusion.
+#[test]
+fn scenario_woodcutting_drain_converts_lumber_to_durability() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(&client, woodcutting_encounter_ids(&client)[0]);
+    add_library_card(
+        &client,
+        serde_json::json!({
+            "kind": {"card_kind": "Woodcutting", "woodcutting_effect": {
+                "chop_types": [],
+                "chop_values": [],
+                "costs": [{"token_type": "WoodcuttingDurability", "amount": 50}],
+                "gains": [{"token_type": "Lumber", "amount": 20}],
+                "drains": [{"source_token": "Lumber", "target_token": "WoodcuttingDurability", "percent": 50}]
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    );
+
+    let card_id = drain_card_id(&client, "Woodcutting", "woodcutting_effect");
+    let lumber_before = player_token(&client, "Lumber");
+    let durability_before = player_token(&client, "WoodcuttingDurability");
+    let durability_max =
+        player_token_max(&client, "WoodcuttingDurability").expect("durability has a max");
+    play_card(&client, card_id);
+
+    // Gains resolve before the durability cost, so the drain is capped at the maximum
+    assert_eq!(player_token(&client, "Lumber"), lumber_before + 20);
+    assert_eq!(
+        player_token(&client, "WoodcuttingDurability"),
+        (durability_before + 10).min(durability_max) - 50
+    );
+
+    // The Lumber reward paid at conclusion is drained too
+    let lumber_before = player_token(&client, "Lumber");
+    let durability_before = player_token(&client, "WoodcuttingDurability");
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterConcludeEncounter"}"#);
+    assert_eq!(status, Status::Created);
+    let reward = player_token(&client, "Lumber") - lumber_before;
+    assert!(reward > 0, "concluding pays a Lumber reward");
+    let durability_after = player_token(&client, "WoodcuttingDurability");
+    assert!(durability_after > durability_before);
+    assert_eq!(
+        durability_after,
+        (durability_before + reward * 50 / 100).min(durability_max)
+    );
+}
+
+/// Scenario: a gutting card drains half of the Fish it catches into durability.
+#[test]
+fn scenario_fishing_drain_converts_fish_to_durability() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(&client, fishing_encounter_ids(&client)[0]);
+    add_library_card(
+        &client,
+        serde_json::json!({
+            "kind": {"card_kind": "Fishing", "fishing_effect": {
+                "values": [],
+                "costs": [{"token_type": "FishingDurability", "amount": 50}],
+                "gains": [{"token_type": "Fish", "amount": 20}],
+                "drains": [{"source_token": "Fish", "target_token": "FishingDurability", "percent": 50}]
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    );
+
+    let card_id = drain_card_id(&client, "Fishing", "fishing_effect");
+    let fish_before = player_token(&client, "Fish");
+    let durability_before = player_token(&client, "FishingDurability");
+    play_card(&client, card_id);
+
+    assert_eq!(player_token(&client, "Fish"), fish_before + 20);
+    assert_eq!(
+        player_token(&client, "FishingDurability"),
+        durability_before - 50 + 10
+    );
+}
+
+// ======================== Combat retreat tests ========================
+
+/// Scenario: retreat from the Gnome for 200 Stamina, recorded as a loss, then keep scouting.
+#[test]
+fn scenario_retreat_from_combat_costs_stamina() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":42}"#);
+    assert_eq!(status, Status::Created);
+    pick_encounter(&client, combat_encounter_ids(&client)[0]);
+
+    // Abort is still rejected for combat; retreat is the way out
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterAbort"}"#);
+    assert_eq!(status, Status::BadRequest);
+
+    let stamina_before = player_token(&client, "Stamina");
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterRetreat"}"#);
+    assert_eq!(status, Status::Created, "Retreat should succeed");
+    assert_eq!(player_token(&client, "Stamina"), stamina_before - 200);
+    assert_eq!(combat_result(&client), Some("PlayerLost".to_string()));
+
+    // A second retreat has no combat to leave
+    let (status, _) = post_action(&client, r#"{"action_type":"EncounterRetreat"}"#);
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, _) = post_action(
+        &client,
+        r#"{"action_type":"EncounterApplyScouting","card_ids":[]}"#,
+    );
+    assert_eq!(status, Status::Created, "Scouting should follow a retreat");
+}
+
+// ======================== Encounter entry cost tests ========================
+
+fn add_mining_encounter_with_entry_cost(client: &Client, stamina: i64) -> usize {
+    add_library_card(
+        client,
+        serde_json::json!({
+            "kind": {"card_kind": "Encounter", "encounter_kind": {
+                "encounter_type": "Mining",
+                "mining_def": {
+                    "initial_light_level": 100,
+                    "ore_deck": [{
+                        "damages": [],
+                        "counts": {"deck": 0, "hand": 1, "discard": 0}
+                    }],
+                    "entry_cost": [{"token_type": "Stamina", "amount": stamina}]
+                }
+            }},
+            "counts": {"library": 0, "deck": 0, "hand": 1, "discard": 0}
+        }),
+    )
+}
+
+/// Scenario: `/encounters/hand` shows entry costs up front, and picking needs them paid.
+#[test]
+fn scenario_encounter_entry_cost_is_shown_and_paid_on_pick() {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    let (status, _) = post_action(&client, r#"{"action_type":"NewGame","seed":40}"#);
+    assert_eq!(status, Status::Created);
+
+    let stamina = player_token(&client, "Stamina");
+    let too_expensive = add_mining_encounter_with_entry_cost(&client, stamina + 1);
+    let affordable = add_mining_encounter_with_entry_cost(&client, 100);
+
+    let summaries = get_json(&client, "/encounters/hand");
+    let summaries = summaries.as_array().expect("summary list");
+    let summarized: Vec<usize> = summaries
+        .iter()
+        .filter_map(|s| s["card_id"].as_u64().map(|id| id as usize))
+        .collect();
+    assert_eq!(summarized, encounter_hand_ids(&client));
+    let summary = summaries
+        .iter()
+        .find(|s| s["card_id"].as_u64() == Some(affordable as u64))
+        .expect("affordable encounter summarized");
+    assert_eq!(summary["discipline"], "Mining");
+    assert_eq!(summary["entry_cost"][0]["token_type"], "Stamina");
+    assert_eq!(summary["entry_cost"][0]["amount"], 100);
+
+    let (status, _) = post_action(
+        &client,
+        &format!(
+            r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+            too_expensive
+        ),
+    );
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(player_token(&client, "Stamina"), stamina);
+    assert!(encounter_hand_ids(&client).contains(&too_expensive));
+
+    pick_encounter(&client, affordable);
+    assert_eq!(player_token(&client, "Stamina"), stamina - 100);
+}
@@ -0,0 +1,198 @@
+use my_little_cardgame::action::persistence::{snapshot_path, write_snapshot};
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::snapshot::GameSnapshot;
+use my_little_cardgame::library::types::{ActionEntry, Token, TokenType};
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::lock::Mutex;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+use std::sync::Arc;
+
+fn post_action(client: &Client, json: &str) -> Status {
+    client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch()
+        .status()
+}
+
+fn card_ids(client: &Client, uri: &str) -> Vec<u64> {
+    let cards: serde_json::Value =
+        serde_json::from_str(&client.get(uri).dispatch().into_string().unwrap_or_default())
+            .unwrap_or_default();
+    cards
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .filter_map(|c| c["id"].as_u64())
+        .collect()
+}
+
+/// Play a short game over HTTP (equip the pickaxe, mine, scout, start the next encounter)
+/// and return its action log.
+fn played_log() -> Vec<ActionEntry> {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    assert_eq!(
+        post_action(&client, r#"{"action_type":"NewGame","seed":41}"#),
+        Status::Created
+    );
+    let pickaxe = card_ids(&client, "/library/cards?card_kind=Tool")[0];
+    assert_eq!(
+        post_action(
+            &client,
+            &format!(r#"{{"action_type":"EquipTool","card_id":{}}}"#, pickaxe)
+        ),
+        Status::Created
+    );
+
+    let summaries: serde_json::Value = serde_json::from_str(
+        &client
+            .get("/encounters/hand")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    let mining = summaries
+        .as_array()
+        .and_then(|s| s.iter().find(|s| s["discipline"] == "Mining"))
+        .and_then(|s| s["card_id"].as_u64())
+        .expect("a mining encounter in hand");
+    assert_eq!(
+        post_action(
+            &client,
+            &format!(
+                r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+                mining
+            )
+        ),
+        Status::Created
+    );
+    for _ in 0..3 {
+        let Some(card) = card_ids(&client, "/library/cards?location=Hand&card_kind=Mining")
+            .first()
+            .copied()
+        else {
+            break;
+        };
+        post_action(
+            &client,
+            &format!(
+                r#"{{"action_type":"EncounterPlayCard","card_id":{}}}"#,
+                card
+            ),
+        );
+    }
+    post_action(&client, r#"{"action_type":"EncounterConcludeEncounter"}"#);
+    post_action(
+        &client,
+        r#"{"action_type":"EncounterApplyScouting","card_ids":[]}"#,
+    );
+    let next = card_ids(&client, "/library/cards?location=Hand&card_kind=Encounter")[0];
+    post_action(
+        &client,
+        &format!(
+            r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+            next
+        ),
+    );
+
+    let state = client
+        .rocket()
+        .state::<Arc<Mutex<GameState>>>()
+        .expect("managed game state");
+    let entries = state
+        .try_lock()
+        .expect("no request in flight")
+        .action_log
+        .entries();
+    assert!(entries.len() >= 6, "the session logged its actions");
+    entries
+}
+
+fn log_of(entries: &[ActionEntry]) -> ActionLog {
+    let log = ActionLog::new();
+    for e in entries {
+        log.append("Replay", e.payload.clone());
+    }
+    log
+}
+
+/// Serialized form used to compare states; maps become JSON objects, so order doesn't matter.
+fn state_json(gs: &GameState, rng: &rand_pcg::Lcg64Xsh32) -> serde_json::Value {
+    serde_json::to_value(gs.snapshot(rng)).expect("serializable snapshot")
+}
+
+fn snapshot_at(entries: &[ActionEntry], seq: usize) -> GameSnapshot {
+    let (gs, rng) = GameState::replay_with_rng(&log_of(&entries[..seq]));
+    let json = serde_json::to_string(&gs.snapshot(&rng)).expect("serialize");
+    serde_json::from_str(&json).expect("snapshot JSON round-trips")
+}
+
+#[test]
+fn snapshot_plus_tail_equals_full_replay() {
+    let entries = played_log();
+    let full_log = log_of(&entries);
+    let (full, full_rng) = GameState::replay_with_rng(&full_log);
+    let expected = state_json(&full, &full_rng);
+
+    for seq in 0..=entries.len() {
+        let snapshot = snapshot_at(&entries, seq);
+        assert_eq!(snapshot.last_seq, seq as u64);
+        let (resumed, rng) = GameState::resume_from_snapshot(Some(&snapshot), &full_log);
+        assert_eq!(
+            state_json(&resumed, &rng),
+            expected,
+            "snapshot at seq {} plus the tail differs from a full replay",
+            seq
+        );
+        assert_eq!(resumed.action_log.entries().len(), entries.len());
+    }
+}
+
+#[test]
+fn resume_from_file_uses_newest_valid_snapshot() {
+    let entries = played_log();
+    let dir = std::env::temp_dir().join(format!(
+        "my_little_cardgame_snapshots_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let log_path = dir.join("actions.jsonl");
+    let log_path_str = log_path.to_str().unwrap();
+    log_of(&entries).write_all_to_file(log_path_str).unwrap();
+
+    let honest = snapshot_at(&entries, 2);
+    write_snapshot(&log_path, &honest).unwrap();
+    // A newer snapshot carrying a marker proves the loader starts from it
+    let marker = Token::persistent(TokenType::Renown);
+    let mut marked = snapshot_at(&entries, 4);
+    marked.token_balances.insert(marker.clone(), 12345);
+    write_snapshot(&log_path, &marked).unwrap();
+    // Newer but invalid: past the end of the log, and a truncated file
+    let mut beyond = snapshot_at(&entries, 4);
+    beyond.last_seq = entries.len() as u64 + 5;
+    write_snapshot(&log_path, &beyond).unwrap();
+    std::fs::write(
+        snapshot_path(&log_path, entries.len() as u64 + 9),
+        "{\"last_seq\":",
+    )
+    .unwrap();
+
+    let (resumed, _) = GameState::resume_from_file(log_path_str).expect("resume");
+    assert_eq!(resumed.token_balances.get(&marker).copied(), Some(12345));
+    assert_eq!(resumed.action_log.entries().len(), entries.len());
+
+    std::fs::remove_file(snapshot_path(&log_path, 4)).unwrap();
+    let (resumed, rng) = GameState::resume_from_file(log_path_str).expect("resume");
+    let (full, full_rng) = GameState::replay_with_rng(&log_of(&entries));
+    assert_eq!(state_json(&resumed, &rng), state_json(&full, &full_rng));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
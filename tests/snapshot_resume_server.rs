@@ -0,0 +1,125 @@
+//! Runs alone in its own binary: it sets `ACTION_LOG_FILE`, which every `rocket_initialize`
+//! in the process would pick up.
+
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::lock::Mutex;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+use std::sync::Arc;
+
+fn post_action(client: &Client, json: &str) -> Status {
+    client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch()
+        .status()
+}
+
+/// Player token balances, sorted so that HashMap order doesn't matter.
+fn tokens(client: &Client) -> Vec<String> {
+    let balances: serde_json::Value = serde_json::from_str(
+        &client
+            .get("/player/tokens")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    let mut balances: Vec<String> = balances
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    balances.sort();
+    balances
+}
+
+#[test]
+fn server_writes_snapshots_and_resumes_from_them() {
+    let dir = std::env::temp_dir().join(format!(
+        "my_little_cardgame_resume_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let log_path = dir.join("actions.jsonl");
+    std::env::set_var("ACTION_LOG_FILE", &log_path);
+    std::env::set_var("ACTION_LOG_SNAPSHOT_INTERVAL", "2");
+
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    assert_eq!(
+        post_action(&client, r#"{"action_type":"NewGame","seed":41}"#),
+        Status::Created
+    );
+    for _ in 0..2 {
+        assert_eq!(
+            post_action(
+                &client,
+                r#"{"action_type":"UnequipTool","discipline":"Mining"}"#
+            ),
+            Status::BadRequest,
+            "failed actions are not logged"
+        );
+    }
+    let tools: serde_json::Value = serde_json::from_str(
+        &client
+            .get("/library/cards?card_kind=Tool")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    let pickaxe = tools[0]["id"].as_u64().expect("the pickaxe");
+    assert_eq!(
+        post_action(
+            &client,
+            &format!(r#"{{"action_type":"EquipTool","card_id":{}}}"#, pickaxe)
+        ),
+        Status::Created
+    );
+    assert_eq!(
+        post_action(
+            &client,
+            r#"{"action_type":"UnequipTool","discipline":"Mining"}"#
+        ),
+        Status::Created
+    );
+    let tokens_before = tokens(&client);
+    let state = client
+        .rocket()
+        .state::<Arc<Mutex<GameState>>>()
+        .expect("managed game state")
+        .clone();
+    state.try_lock().expect("no request in flight").shutdown();
+    drop(client);
+
+    let snapshot = my_little_cardgame::action::persistence::snapshot_path(&log_path, 2);
+    assert!(snapshot.exists(), "a snapshot is written every 2 actions");
+
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    assert_eq!(tokens(&client), tokens_before);
+    let resumed = client
+        .rocket()
+        .state::<Arc<Mutex<GameState>>>()
+        .expect("managed game state")
+        .clone();
+    assert_eq!(
+        resumed
+            .try_lock()
+            .expect("no request in flight")
+            .action_log
+            .entries()
+            .len(),
+        3
+    );
+
+    std::env::remove_var("ACTION_LOG_FILE");
+    std::env::remove_var("ACTION_LOG_SNAPSHOT_INTERVAL");
+    let _ = std::fs::remove_dir_all(&dir);
+}
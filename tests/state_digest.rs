@@ -0,0 +1,138 @@
+use my_little_cardgame::library::action_log::ActionLog;
+use my_little_cardgame::library::types::{ActionEntry, Token, TokenType};
+use my_little_cardgame::library::GameState;
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::lock::Mutex;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::serde_json;
+use std::sync::Arc;
+
+fn post_action(client: &Client, json: &str) -> Status {
+    client
+        .post("/action")
+        .header(ContentType::JSON)
+        .body(json)
+        .dispatch()
+        .status()
+}
+
+fn hand(client: &Client) -> Vec<serde_json::Value> {
+    serde_json::from_str::<serde_json::Value>(
+        &client
+            .get("/library/cards?location=Hand")
+            .dispatch()
+            .into_string()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default()
+    .as_array()
+    .cloned()
+    .unwrap_or_default()
+}
+
+/// Play `steps` actions, each time taking the first of these that the server accepts:
+/// play a hand card, conclude, scout, pick an encounter, abort.
+fn played_log(seed: u64, steps: usize) -> Vec<ActionEntry> {
+    let client = Client::tracked(rocket_initialize()).expect("valid rocket instance");
+    assert_eq!(
+        post_action(
+            &client,
+            &format!(r#"{{"action_type":"NewGame","seed":{}}}"#, seed)
+        ),
+        Status::Created
+    );
+    for _ in 0..steps {
+        let cards = hand(&client);
+        let mut candidates: Vec<String> = cards
+            .iter()
+            .filter(|c| c["kind"]["card_kind"] != "Encounter")
+            .filter_map(|c| c["id"].as_u64())
+            .map(|id| format!(r#"{{"action_type":"EncounterPlayCard","card_id":{}}}"#, id))
+            .collect();
+        candidates.push(r#"{"action_type":"EncounterConcludeEncounter"}"#.to_string());
+        candidates.push(r#"{"action_type":"EncounterApplyScouting","card_ids":[]}"#.to_string());
+        candidates.extend(
+            cards
+                .iter()
+                .filter(|c| c["kind"]["card_kind"] == "Encounter")
+                .filter_map(|c| c["id"].as_u64())
+                .map(|id| {
+                    format!(
+                        r#"{{"action_type":"EncounterPickEncounter","card_id":{}}}"#,
+                        id
+                    )
+                }),
+        );
+        candidates.push(r#"{"action_type":"EncounterAbort"}"#.to_string());
+        if !candidates
+            .iter()
+            .any(|action| post_action(&client, action) == Status::Created)
+        {
+            break;
+        }
+    }
+    let state = client
+        .rocket()
+        .state::<Arc<Mutex<GameState>>>()
+        .expect("managed game state");
+    let entries = state
+        .try_lock()
+        .expect("no request in flight")
+        .action_log
+        .entries();
+    entries
+}
+
+fn log_of(entries: &[ActionEntry]) -> ActionLog {
+    let log = ActionLog::new();
+    for e in entries {
+        log.record(e.clone());
+    }
+    log
+}
+
+#[test]
+fn every_logged_action_records_a_digest_that_replay_reproduces() {
+    for seed in [3, 42] {
+        let entries = played_log(seed, 60);
+        assert!(entries.len() > 20, "the session played through encounters");
+        assert!(entries.iter().all(|e| e.digest.is_some()));
+
+        let (replayed, rng) =
+            GameState::replay_checked(&log_of(&entries)).expect("replay matches every digest");
+        assert_eq!(
+            Some(replayed.digest(&rng)),
+            entries.last().and_then(|e| e.digest.clone())
+        );
+    }
+}
+
+#[test]
+fn replay_reports_the_first_diverging_seq_with_a_field_diff() {
+    let mut entries = played_log(42, 12);
+    let health = Token::persistent(TokenType::Health);
+    let tampered = 3;
+    let digest = entries[tampered - 1].digest.as_mut().expect("a digest");
+    let recorded = digest.token_balances.get(&health).copied().unwrap_or(0);
+    digest.token_balances.insert(health, recorded + 1);
+    digest.rng_hash = "0".repeat(16);
+
+    let divergence =
+        GameState::replay_checked(&log_of(&entries)).expect_err("the tampered digest diverges");
+    assert_eq!(divergence.seq, tampered as u64);
+    let fields: Vec<&str> = divergence
+        .differences
+        .iter()
+        .map(|d| d.field.as_str())
+        .collect();
+    assert_eq!(
+        fields,
+        vec!["token_balances.Health:PersistentCounter", "rng_hash"]
+    );
+    assert_eq!(
+        divergence.differences[0].recorded,
+        (recorded + 1).to_string()
+    );
+    assert_eq!(divergence.differences[0].replayed, recorded.to_string());
+}
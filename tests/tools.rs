@@ -0,0 +1,185 @@
+mod common;
+
+use common::{deck_in_hand, game_with, in_hand, library_only};
+use my_little_cardgame::library::types::{
+    CardKind, Discipline, EncounterKind, EncounterState, MiningCardEffect, MiningDef, OreCard,
+    Token, TokenAmount, TokenType,
+};
+use my_little_cardgame::library::{GameState, Library};
+
+const MINING_CARD: usize = 0;
+const PICKAXE: usize = 1;
+const ENCOUNTER_CARD: usize = 2;
+
+fn mining_power(amount: i64) -> TokenAmount {
+    TokenAmount {
+        token_type: TokenType::MiningPower,
+        amount,
+        cap: None,
+    }
+}
+
+fn setup(tool_durability: u32) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::Mining {
+            mining_effect: MiningCardEffect {
+                costs: vec![],
+                gains: vec![mining_power(100)],
+                drains: vec![],
+                ventilates: false,
+            },
+        },
+        in_hand(10),
+    );
+    lib.add_card(
+        CardKind::Tool {
+            discipline: Discipline::Mining,
+            modifiers: vec![mining_power(50)],
+            durability: tool_durability,
+        },
+        library_only(),
+    );
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Mining {
+                mining_def: MiningDef {
+                    initial_light_level: 100,
+                    ore_deck: vec![OreCard {
+                        damages: vec![],
+                        counts: deck_in_hand(3),
+                        hazard: None,
+                    }],
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (gs, rng) = game_with(lib, 39);
+    assert!(gs.library.validate_card_effects().is_ok());
+    (gs, rng)
+}
+
+fn mining_yield(gs: &GameState) -> i64 {
+    match &gs.current_encounter {
+        Some(EncounterState::Mining(m)) => m
+            .encounter_tokens
+            .get(&Token::persistent(TokenType::MiningYield))
+            .copied()
+            .unwrap_or(0),
+        _ => panic!("expected active mining encounter"),
+    }
+}
+
+fn durability_left(gs: &GameState) -> Option<u32> {
+    gs.equipped_tools
+        .get(&Discipline::Mining)
+        .map(|t| t.durability_left)
+}
+
+#[test]
+fn equipped_tool_adds_its_modifiers_and_breaks_when_worn_out() {
+    let (mut gs, mut rng) = setup(2);
+    gs.equip_tool(PICKAXE).expect("equip");
+    assert_eq!(gs.library.get(PICKAXE).map(|c| c.counts.library), Some(0));
+
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("first play");
+    assert_eq!(mining_yield(&gs), 150);
+    assert_eq!(durability_left(&gs), Some(1));
+
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("second play");
+    assert_eq!(mining_yield(&gs), 300);
+    assert_eq!(durability_left(&gs), None, "the pickaxe broke");
+
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("third play");
+    assert_eq!(mining_yield(&gs), 400);
+    assert_eq!(gs.library.get(PICKAXE).map(|c| c.counts.library), Some(0));
+}
+
+#[test]
+fn unequipped_tool_keeps_its_wear() {
+    let (mut gs, mut rng) = setup(5);
+    gs.equip_tool(PICKAXE).expect("equip");
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("play");
+    assert!(gs.unequip_tool(&Discipline::Mining).is_err());
+    gs.abort_encounter();
+
+    gs.unequip_tool(&Discipline::Mining).expect("unequip");
+    assert_eq!(gs.library.get(PICKAXE).map(|c| c.counts.library), Some(1));
+    assert_eq!(durability_left(&gs), None);
+
+    gs.equip_tool(PICKAXE).expect("re-equip");
+    assert_eq!(durability_left(&gs), Some(4));
+}
+
+#[test]
+fn only_the_unequipped_copy_keeps_its_wear() {
+    let (mut gs, mut rng) = setup(2);
+    gs.library.cards[PICKAXE].counts.library = 2;
+    gs.equip_tool(PICKAXE).expect("equip");
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("play");
+    gs.abort_encounter();
+    gs.unequip_tool(&Discipline::Mining).expect("unequip");
+    assert_eq!(gs.stored_tool_durability.get(&PICKAXE), Some(&1));
+
+    // The worn copy comes back out first and breaks on its last use
+    gs.equip_tool(PICKAXE).expect("re-equip");
+    assert_eq!(durability_left(&gs), Some(1));
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    gs.resolve_player_mining_card(MINING_CARD, &mut rng)
+        .expect("play");
+    assert_eq!(durability_left(&gs), None, "the worn copy broke");
+    gs.abort_encounter();
+
+    assert_eq!(gs.library.get(PICKAXE).map(|c| c.counts.library), Some(1));
+    gs.equip_tool(PICKAXE).expect("equip the other copy");
+    assert_eq!(durability_left(&gs), Some(2), "the other copy is unworn");
+}
+
+#[test]
+fn equip_rules() {
+    let (mut gs, mut rng) = setup(5);
+    assert!(gs.equip_tool(MINING_CARD).is_err(), "not a tool");
+    gs.equip_tool(PICKAXE).expect("equip");
+    assert!(gs.equip_tool(PICKAXE).is_err(), "no copy left to equip");
+    assert!(gs.unequip_tool(&Discipline::Fishing).is_err());
+
+    gs.unequip_tool(&Discipline::Mining).expect("unequip");
+    gs.start_mining_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start mining");
+    assert!(
+        gs.equip_tool(PICKAXE).is_err(),
+        "tools are equipped between encounters"
+    );
+}
+
+#[test]
+fn tools_are_craftable_and_validated() {
+    let (gs, _) = setup(20);
+    let cost = &gs.library.get(PICKAXE).expect("pickaxe").crafting_cost;
+    assert!(cost.values().all(|&c| c > 0) && !cost.is_empty());
+
+    let mut lib = Library::new();
+    lib.add_card(
+        CardKind::Tool {
+            discipline: Discipline::Fishing,
+            modifiers: vec![],
+            durability: 0,
+        },
+        library_only(),
+    );
+    assert_eq!(lib.validate_card_effects().map_err(|e| e.len()), Err(1));
+}
@@ -0,0 +1,153 @@
+use my_little_cardgame::rocket_initialize;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::serde_json;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Launch a server on a free port and return that port with a handle to stop it.
+async fn launch() -> (u16, rocket::Shutdown) {
+    let (port_tx, port_rx) = rocket::tokio::sync::oneshot::channel();
+    let port_tx = std::sync::Mutex::new(Some(port_tx));
+    let rocket = rocket_initialize()
+        .configure(rocket::Config {
+            port: 0,
+            log_level: rocket::config::LogLevel::Off,
+            ..rocket::Config::debug_default()
+        })
+        .attach(rocket::fairing::AdHoc::on_liftoff("port", move |rocket| {
+            let port = rocket.config().port;
+            Box::pin(async move {
+                if let Some(tx) = port_tx.lock().unwrap().take() {
+                    let _ = tx.send(port);
+                }
+            })
+        }))
+        .ignite()
+        .await
+        .expect("ignite");
+    let shutdown = rocket.shutdown();
+    rocket::tokio::spawn(rocket.launch());
+    (port_rx.await.expect("liftoff"), shutdown)
+}
+
+/// Send every frame over one socket and collect the replies.
+async fn play_over_socket(port: u16, frames: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let (mut socket, _) =
+        tokio_tungstenite::connect_async(format!("ws://127.0.0.1:{}/action/ws", port))
+            .await
+            .expect("websocket handshake");
+    for frame in frames {
+        socket
+            .send(Message::Text(frame.to_string()))
+            .await
+            .expect("send");
+    }
+    let mut replies = Vec::new();
+    while replies.len() < frames.len() {
+        match socket.next().await.expect("reply").expect("frame") {
+            Message::Text(text) => replies.push(serde_json::from_str(&text).expect("json reply")),
+            _ => continue,
+        }
+    }
+    let _ = socket.close(None).await;
+    replies
+}
+
+#[rocket::async_test]
+async fn socket_replies_match_post_action_in_order() {
+    let actions = [
+        serde_json::json!({"action_type": "NewGame", "seed": 11}),
+        serde_json::json!({"action_type": "EncounterAbort"}),
+        serde_json::json!({"action_type": "EncounterPickEncounter", "card_id": 999_999}),
+        serde_json::json!({"action_type": "NewGame", "seed": 12}),
+    ];
+
+    // The same actions through POST /action on a game of its own
+    let client = Client::tracked(rocket_initialize())
+        .await
+        .expect("valid rocket instance");
+    let mut expected = Vec::new();
+    for action in &actions {
+        let response = client
+            .post("/action")
+            .header(ContentType::JSON)
+            .body(action.to_string())
+            .dispatch()
+            .await;
+        let status = response.status().code;
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap_or_default())
+                .unwrap_or_default();
+        expected.push((status, body));
+    }
+
+    let (port, shutdown) = launch().await;
+    let frames: Vec<serde_json::Value> = actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let mut frame = action.clone();
+            frame["id"] = format!("req-{}", i).into();
+            frame
+        })
+        .collect();
+    let replies = play_over_socket(port, &frames).await;
+    shutdown.notify();
+
+    assert_eq!(replies.len(), expected.len());
+    for (i, (reply, (status, body))) in replies.iter().zip(&expected).enumerate() {
+        assert_eq!(reply["id"], format!("req-{}", i));
+        assert_eq!(reply["status"], *status);
+        if *status == Status::Created.code {
+            assert_eq!(reply["entry"], *body, "frame {}", i);
+        } else {
+            assert_eq!(reply["error"], *body, "frame {}", i);
+        }
+        assert!(reply.get("deltas").is_none());
+    }
+    assert_eq!(
+        expected.iter().map(|(s, _)| *s).collect::<Vec<_>>(),
+        vec![201, 400, 404, 201]
+    );
+}
+
+#[rocket::async_test]
+async fn socket_sends_deltas_on_request_and_rejects_bad_frames() {
+    let (port, shutdown) = launch().await;
+    let replies = play_over_socket(
+        port,
+        &[
+            serde_json::json!({"id": 1, "deltas": true, "action_type": "NewGame", "seed": 3}),
+            serde_json::json!({"id": 2, "action_type": "NoSuchAction"}),
+            serde_json::json!({"id": 3, "deltas": true, "action_type": "EncounterAbort"}),
+        ],
+    )
+    .await;
+    shutdown.notify();
+
+    assert_eq!(replies[0]["status"], 201);
+    let deltas = replies[0]["deltas"].as_array().expect("deltas");
+    assert_eq!(deltas[0]["event"], "action-appended");
+    assert_eq!(deltas[0]["entry"], replies[0]["entry"]);
+    assert!(deltas
+        .iter()
+        .all(|d| d["seq"] == replies[0]["entry"]["seq"]));
+
+    assert_eq!(replies[1]["id"], 2);
+    assert_eq!(replies[1]["status"], 422);
+    assert!(replies[1]["error"]["message"].is_string());
+
+    assert_eq!(replies[2]["id"], 3);
+    assert_eq!(replies[2]["status"], 400);
+    assert_eq!(replies[2]["deltas"], serde_json::json!([]));
+}
+
+#[rocket::async_test]
+async fn plain_get_is_told_to_upgrade() {
+    let client = Client::tracked(rocket_initialize())
+        .await
+        .expect("valid rocket instance");
+    let response = client.get("/action/ws").dispatch().await;
+    assert_eq!(response.status(), Status::UpgradeRequired);
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::{amount, game_with, in_hand, library_only};
+use my_little_cardgame::library::types::{
+    token_balance_by_type, CardCounts, CardKind, ChopType, EncounterKind, EncounterState, Token,
+    TokenType, WoodcuttingCardEffect, WoodcuttingDef, WoodcuttingPattern,
+};
+use my_little_cardgame::library::{GameState, Library};
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+const LIGHT_CHOP: usize = 0;
+const HEAVY_CHOP: usize = 1;
+const ENCOUNTER_CARD: usize = 2;
+
+fn chop_card(chop_type: ChopType, value: u32) -> CardKind {
+    CardKind::Woodcutting {
+        woodcutting_effect: WoodcuttingCardEffect {
+            chop_types: vec![chop_type],
+            chop_values: vec![value],
+            costs: vec![amount(TokenType::WoodcuttingDurability, 1)],
+            gains: vec![],
+            drains: vec![],
+        },
+    }
+}
+
+/// A forest of `max_plays` plays with LightChop (value 2) and HeavyChop (value 5) cards.
+fn start(
+    max_plays: u32,
+    pattern_multipliers: HashMap<WoodcuttingPattern, f64>,
+) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let deck = CardCounts {
+        library: 0,
+        deck: 10,
+        hand: 5,
+        discard: 0,
+    };
+    start_with_copies(max_plays, pattern_multipliers, deck)
+}
+
+/// Like `start`, with `copies` of each chop card.
+fn start_with_copies(
+    max_plays: u32,
+    pattern_multipliers: HashMap<WoodcuttingPattern, f64>,
+    copies: CardCounts,
+) -> (GameState, rand_pcg::Lcg64Xsh32) {
+    let mut lib = Library::new();
+    lib.add_card(chop_card(ChopType::LightChop, 2), copies.clone());
+    lib.add_card(chop_card(ChopType::HeavyChop, 5), copies);
+    lib.add_card(
+        CardKind::Encounter {
+            encounter_kind: EncounterKind::Woodcutting {
+                woodcutting_def: WoodcuttingDef {
+                    max_plays,
+                    base_rewards: HashMap::from([(Token::persistent(TokenType::Lumber), 100)]),
+                    pattern_multipliers,
+                    ..Default::default()
+                },
+            },
+        },
+        library_only(),
+    );
+    let (mut gs, mut rng) = game_with(lib, 31);
+    gs.start_woodcutting_encounter(ENCOUNTER_CARD, &mut rng)
+        .expect("start woodcutting");
+    (gs, rng)
+}
+
+fn lumber(gs: &GameState) -> i64 {
+    token_balance_by_type(&gs.token_balances, &TokenType::Lumber)
+}
+
+#[test]
+fn default_table_keeps_standard_multipliers() {
+    let (mut gs, mut rng) = start(3, WoodcuttingPattern::default_multipliers());
+    for _ in 0..3 {
+        gs.resolve_player_woodcutting_card(LIGHT_CHOP, &mut rng)
+            .expect("play chop");
+    }
+    // Three LightChops of value 2: Triple (2.0) outranks Value Triple (19.0)
+    assert!(gs.current_encounter.is_none());
+    assert_eq!(lumber(&gs), 200);
+}
+
+#[test]
+fn custom_table_skips_patterns_it_does_not_list() {
+    let multipliers = HashMap::from([
+        (WoodcuttingPattern::ValueTriple, 7.0),
+        (WoodcuttingPattern::HighCard, 0.5),
+    ]);
+    let (mut gs, mut rng) = start(3, multipliers.clone());
+    for _ in 0..3 {
+        gs.resolve_player_woodcutting_card(LIGHT_CHOP, &mut rng)
+            .expect("play chop");
+    }
+    assert_eq!(lumber(&gs), 700);
+
+    let (mut gs, mut rng) = start(2, multipliers);
+    gs.resolve_player_woodcutting_card(LIGHT_CHOP, &mut rng)
+        .expect("play chop");
+    gs.resolve_player_woodcutting_card(HEAVY_CHOP, &mut rng)
+        .expect("play chop");
+    assert_eq!(lumber(&gs), 50);
+}
+
+#[test]
+fn preview_lists_reachable_patterns_and_best_so_far() {
+    let (mut gs, mut rng) = start(4, WoodcuttingPattern::default_multipliers());
+    gs.resolve_player_woodcutting_card(LIGHT_CHOP, &mut rng)
+        .expect("play chop");
+    gs.resolve_player_woodcutting_card(LIGHT_CHOP, &mut rng)
+        .expect("play chop");
+
+    let preview = gs.woodcutting_pattern_preview().expect("preview");
+    assert_eq!(preview.remaining_plays, 2);
+    assert_eq!(preview.best_so_far.pattern, WoodcuttingPattern::Pair);
+    assert_eq!(preview.best_so_far.multiplier, 4.5);
+    let reachable: Vec<WoodcuttingPattern> = preview.reachable.iter().map(|s| s.pattern).collect();
+    assert_eq!(
+        reachable,
+        vec![
+            WoodcuttingPattern::FourOfAKind,
+            WoodcuttingPattern::ValueQuads,
+            WoodcuttingPattern::Triple,
+            WoodcuttingPattern::ValueTriple,
+            WoodcuttingPattern::Pair,
+            WoodcuttingPattern::HighCard,
+        ]
+    );
+
+    match &gs.current_encounter {
+        Some(EncounterState::Woodcutting(w)) => assert!(w.pattern.is_none()),
+        _ => panic!("expected active woodcutting encounter"),
+    }
+}
+
+#[test]
+fn preview_only_reaches_patterns_the_owned_copies_can_make() {
+    let (gs, _) = start_with_copies(3, WoodcuttingPattern::default_multipliers(), in_hand(1));
+    let preview = gs.woodcutting_pattern_preview().expect("preview");
+    let reachable: Vec<WoodcuttingPattern> = preview.reachable.iter().map(|s| s.pattern).collect();
+    // One copy of each chop can never pair up, however many plays are left
+    assert_eq!(reachable, vec![WoodcuttingPattern::HighCard]);
+}
+
+#[test]
+fn preview_requires_active_woodcutting_encounter() {
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(31);
+    let gs = GameState::new_with_rng(&mut rng);
+    assert!(gs.woodcutting_pattern_preview().is_err());
+}
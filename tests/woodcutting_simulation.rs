@@ -0,0 +1,90 @@
+use my_little_cardgame::library::types::{
+    CardKind, EncounterKind, WoodcuttingDef, WoodcuttingPattern,
+};
+use my_little_cardgame::library::{simulate_woodcutting_patterns, GameState};
+use rand::SeedableRng;
+
+fn starting_game() -> (GameState, WoodcuttingDef) {
+    let mut rng = rand_pcg::Lcg64Xsh32::seed_from_u64(32);
+    let gs = GameState::new_with_rng(&mut rng);
+    let woodcutting_def = gs
+        .library
+        .cards
+        .iter()
+        .find_map(|card| match &card.kind {
+            CardKind::Encounter {
+                encounter_kind: EncounterKind::Woodcutting { woodcutting_def },
+            } => Some(woodcutting_def.clone()),
+            _ => None,
+        })
+        .expect("starting library has a woodcutting encounter");
+    (gs, woodcutting_def)
+}
+
+/// Best-pattern probabilities for the starting woodcutting deck (8 plays), measured with
+/// 400k simulated hands. Update when the deck composition changes on purpose.
+const EXPECTED_FREQUENCIES: [(WoodcuttingPattern, f64); 16] = [
+    (WoodcuttingPattern::EightOfAKind, 0.0001),
+    (WoodcuttingPattern::SevenOfAKind, 0.0016),
+    (WoodcuttingPattern::PerfectStraight, 0.0269),
+    (WoodcuttingPattern::SixOfAKind, 0.0162),
+    (WoodcuttingPattern::LongStraight, 0.1733),
+    (WoodcuttingPattern::FullSet, 0.2741),
+    (WoodcuttingPattern::FiveOfAKind, 0.0612),
+    (WoodcuttingPattern::FullHouse, 0.1812),
+    (WoodcuttingPattern::FourOfAKind, 0.0062),
+    (WoodcuttingPattern::ShortStraight, 0.0552),
+    (WoodcuttingPattern::TwoPairTypes, 0.0766),
+    (WoodcuttingPattern::ValueQuads, 0.0028),
+    (WoodcuttingPattern::Triple, 0.0960),
+    (WoodcuttingPattern::ValueTriple, 0.0016),
+    (WoodcuttingPattern::Pair, 0.0268),
+    (WoodcuttingPattern::HighCard, 0.0),
+];
+const EXPECTED_LUMBER: f64 = 1828.0;
+const HANDS: u32 = 50_000;
+
+/// Allowed drift: 10% of the expected probability, but never tighter than half a percent.
+fn tolerance(expected: f64) -> f64 {
+    (expected * 0.1).max(0.005)
+}
+
+#[test]
+#[ignore = "slow Monte Carlo run; use cargo test -- --ignored"]
+fn starting_deck_pattern_probabilities_stay_within_tolerance() {
+    let (gs, woodcutting_def) = starting_game();
+    let sim = simulate_woodcutting_patterns(&gs.library, &woodcutting_def, HANDS, 32);
+
+    let mut drifted = Vec::new();
+    for (pattern, expected) in EXPECTED_FREQUENCIES {
+        let actual = sim.frequencies.get(&pattern).copied().unwrap_or(0.0);
+        if (actual - expected).abs() > tolerance(expected) {
+            drifted.push(format!(
+                "{:?}: expected {:.4}, got {:.4}",
+                pattern, expected, actual
+            ));
+        }
+    }
+    assert!(
+        drifted.is_empty(),
+        "Pattern probabilities drifted: {:?}",
+        drifted
+    );
+    assert!(
+        (sim.expected_lumber - EXPECTED_LUMBER).abs() <= EXPECTED_LUMBER * 0.05,
+        "Expected Lumber drifted: expected {:.0}, got {:.0}",
+        EXPECTED_LUMBER,
+        sim.expected_lumber
+    );
+}
+
+#[test]
+fn simulation_is_seed_deterministic() {
+    let (gs, woodcutting_def) = starting_game();
+    let first = simulate_woodcutting_patterns(&gs.library, &woodcutting_def, 500, 7);
+    let second = simulate_woodcutting_patterns(&gs.library, &woodcutting_def, 500, 7);
+    assert_eq!(first, second);
+    assert_eq!(first.hands, 500);
+    let total: f64 = first.frequencies.values().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}